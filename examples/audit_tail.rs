@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: MIT
+
+//! Enable auditing, and print the audit events as they are generated.
+//!
+//! By default the events are printed the way `ausearch` does. With `--raw`,
+//! the records are printed one per line, as in `/var/log/audit/audit.log`.
+//!
+//! This requires the `CAP_AUDIT_CONTROL` capability to enable auditing,
+//! and `CAP_AUDIT_READ` to receive the events:
+//!
+//! ```sh
+//! cargo build --example audit_tail --features client
+//! sudo ./target/debug/examples/audit_tail
+//! ```
+
+use std::io;
+
+use netlink_packet_audit::{
+    client::{AuditClient, AuditListener},
+    AusearchWriter,
+};
+
+fn main() -> io::Result<()> {
+    let raw = match std::env::args().nth(1).as_deref() {
+        None => false,
+        Some("--raw") => true,
+        Some(_) => {
+            eprintln!("usage: audit_tail [--raw]");
+            std::process::exit(1);
+        }
+    };
+
+    let mut client = AuditClient::new()?;
+    let status = client.get_status()?;
+    if status.enabled == 0 {
+        client.set_enabled(true)?;
+    }
+
+    let mut listener = AuditListener::new()?;
+    let mut writer = AusearchWriter::new(io::stdout());
+    loop {
+        let message = listener.next_message()?;
+        if raw {
+            println!("{message}");
+        } else {
+            writer.write(&message)?;
+        }
+    }
+}
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: MIT
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use netlink_packet_audit::{AuditFramer, AuditMessage};
+use netlink_packet_core::NetlinkMessage;
+use netlink_packet_utils::traits::Emitable;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(message) = NetlinkMessage::<AuditMessage>::deserialize(data) {
+        // whatever was parsed must be emitted without panicking
+        let mut buf = vec![0; message.buffer_len()];
+        message.serialize(&mut buf);
+    }
+
+    let mut framer = AuditFramer::new();
+    if framer.push(data).is_ok() {
+        while let Some(result) = framer.next_audit_message() {
+            if let Ok(message) = result {
+                let _ = message.to_string();
+                let mut buf = vec![0; message.buffer_len()];
+                message.emit(&mut buf);
+            }
+        }
+    }
+});
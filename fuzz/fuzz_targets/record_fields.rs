@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MIT
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use netlink_packet_audit::{
+    constants::*, filter::Filter, AuditMessage, EventId, EventPayload,
+    RecordFields,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let record = String::from_utf8_lossy(data);
+    for field in RecordFields::new(&record) {
+        let _ = field.decoded();
+    }
+    let _ = EventId::from_record(data);
+
+    let message = AuditMessage::Event(EventPayload::new(
+        AUDIT_SYSCALL.into(),
+        data.to_vec(),
+    ));
+    let mut filter = Filter::new()
+        .uid_range("auid", 1000..=59999)
+        .key("exec")
+        .exe("/usr/bin/*")
+        .syscalls([59]);
+    let _ = filter.accept(&message);
+});
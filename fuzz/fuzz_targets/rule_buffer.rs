@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: MIT
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use netlink_packet_audit::{RuleBuffer, RuleMessage};
+use netlink_packet_utils::traits::{Emitable, Parseable};
+
+fuzz_target!(|data: &[u8]| {
+    let buf = match RuleBuffer::new_checked(data) {
+        Ok(buf) => buf,
+        Err(_) => return,
+    };
+    let _ = (buf.try_syscalls(), buf.try_fields(), buf.try_values());
+    let _ = (buf.try_field_flags(), buf.try_buf());
+    if let Ok(rule) = RuleMessage::parse(&buf) {
+        let mut emitted = vec![0; rule.buffer_len()];
+        rule.emit(&mut emitted);
+    }
+});
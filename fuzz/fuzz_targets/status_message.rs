@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: MIT
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use netlink_packet_audit::{
+    StatusMessage, StatusMessageBuffer, StatusParseMode,
+};
+use netlink_packet_utils::traits::{Emitable, ParseableParametrized};
+
+fuzz_target!(|data: &[u8]| {
+    let buf = StatusMessageBuffer::new(data);
+    let _ = (buf.try_mask(), buf.try_enabled(), buf.try_lost());
+    let _ = (buf.feature_bitmap(), buf.backlog_wait_time_actual());
+    for mode in [
+        StatusParseMode::Strict,
+        StatusParseMode::Lenient,
+        StatusParseMode::Padded,
+    ] {
+        if let Ok(status) = StatusMessage::parse_with_param(&buf, mode) {
+            let mut emitted = vec![0; status.buffer_len()];
+            status.emit(&mut emitted);
+        }
+    }
+});
@@ -0,0 +1,212 @@
+// SPDX-License-Identifier: MIT
+
+//! Parsing of the text logs written by `auditd` (`/var/log/audit/audit.log`).
+//!
+//! Each line is turned into the same [`AuditMessage`] the record would have
+//! been parsed into if it had been received from the kernel, so that live
+//! and offline analysis can share the same code.
+//!
+//! ```no_run
+//! use std::{fs::File, io::BufReader};
+//!
+//! use netlink_packet_audit::audit_log::AuditLogReader;
+//!
+//! let file = File::open("/var/log/audit/audit.log")?;
+//! let mut reader = AuditLogReader::new(BufReader::new(file));
+//! while let Some(record) = reader.next_record()? {
+//!     println!("{:?}", record.message);
+//! }
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::{
+    io::{self, BufRead},
+    str::FromStr,
+};
+
+use netlink_packet_utils::DecodeError;
+
+use crate::{message_type_from_name, AuditMessage, Utf8Policy};
+
+/// Separator between the raw record and the fields `auditd` adds when
+/// `log_format = ENRICHED` (ASCII group separator)
+const ENRICHMENT_SEPARATOR: char = '\x1d';
+
+/// A line of an `auditd` log file
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct AuditLogRecord {
+    /// Name of the host that sent the record, for the `node=` prefix
+    /// added by `auditd` when `name_format` is set
+    pub node: Option<String>,
+    /// The record, as it would have been received from the kernel
+    pub message: AuditMessage,
+    /// Fields interpreted by `auditd` when `log_format = ENRICHED`, such
+    /// as `("AUID", "root")`
+    pub enrichment: Vec<(String, String)>,
+}
+
+impl FromStr for AuditLogRecord {
+    type Err = DecodeError;
+
+    /// Parse a line such as `node=host type=SYSCALL msg=audit(...): ...`
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let err =
+            || DecodeError::from(format!("invalid audit log line {line:?}"));
+        let line = line.trim_end_matches(['\n', '\r']);
+        let (record, enrichment) = match line.split_once(ENRICHMENT_SEPARATOR) {
+            Some((record, enrichment)) => {
+                (record, parse_enrichment(enrichment))
+            }
+            None => (line, Vec::new()),
+        };
+
+        let (node, record) = match record.strip_prefix("node=") {
+            Some(rest) => {
+                let (node, rest) = rest.split_once(' ').ok_or_else(err)?;
+                (Some(node.to_string()), rest)
+            }
+            None => (None, record),
+        };
+        let (message_type, data) = record
+            .strip_prefix("type=")
+            .and_then(|rest| rest.split_once(" msg="))
+            .ok_or_else(err)?;
+        let message_type = message_type_from_name(message_type)
+            .ok_or_else(|| format!("unknown message type {message_type:?}"))?;
+        let message = AuditMessage::parse_payload(
+            data.as_bytes(),
+            message_type,
+            Utf8Policy::Raw,
+        )?;
+
+        Ok(AuditLogRecord {
+            node,
+            message,
+            enrichment,
+        })
+    }
+}
+
+/// Parse the `KEY="value" KEY=value` fields added by `auditd`
+fn parse_enrichment(s: &str) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+    let mut rest = s.trim_start();
+    while let Some((key, value)) = rest.split_once('=') {
+        let (value, next) = match value.strip_prefix('"') {
+            Some(quoted) => match quoted.split_once('"') {
+                Some((value, next)) => (value, next),
+                None => (quoted, ""),
+            },
+            None => value.split_once(' ').unwrap_or((value, "")),
+        };
+        fields.push((key.to_string(), value.to_string()));
+        rest = next.trim_start();
+    }
+    fields
+}
+
+/// Read the records of an `auditd` log file, one per line
+#[derive(Debug)]
+pub struct AuditLogReader<R> {
+    reader: R,
+    line: String,
+}
+
+impl<R: BufRead> AuditLogReader<R> {
+    pub fn new(reader: R) -> Self {
+        AuditLogReader {
+            reader,
+            line: String::new(),
+        }
+    }
+
+    /// Return the next record, or `None` at the end of the file. Empty
+    /// lines are skipped.
+    pub fn next_record(&mut self) -> io::Result<Option<AuditLogRecord>> {
+        loop {
+            self.line.clear();
+            if self.reader.read_line(&mut self.line)? == 0 {
+                return Ok(None);
+            }
+            if self.line.trim().is_empty() {
+                continue;
+            }
+            return self.line.parse().map(Some).map_err(|e: DecodeError| {
+                io::Error::new(io::ErrorKind::InvalidData, e.to_string())
+            });
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{constants::*, EventId, UserMessage, UserMessageKind};
+
+    #[test]
+    fn test_parse_line() {
+        let record: AuditLogRecord =
+            "type=SYSCALL msg=audit(1592992200.383:66): arch=c000003e \
+            syscall=59 auid=1000\n"
+                .parse()
+                .unwrap();
+        assert_eq!(record.node, None);
+        assert!(record.enrichment.is_empty());
+        assert_eq!(record.message.message_type(), AUDIT_SYSCALL);
+        assert_eq!(
+            record.message.event_id(),
+            Some(EventId::new(1592992200, 383, 66))
+        );
+        assert_eq!(
+            record.message.data(),
+            Some(
+                &b"audit(1592992200.383:66): arch=c000003e syscall=59 \
+                auid=1000"[..]
+            )
+        );
+
+        let record: AuditLogRecord =
+            "node=host1 type=USER_LOGIN msg=audit(1592992200.400:67): \
+            pid=1 uid=0\x1dUID=\"root\" AUID=\"john doe\" ARCH=x86_64"
+                .parse()
+                .unwrap();
+        assert_eq!(record.node.as_deref(), Some("host1"));
+        assert_eq!(
+            record.message,
+            AuditMessage::User(UserMessage::new(
+                UserMessageKind::UserLogin,
+                b"audit(1592992200.400:67): pid=1 uid=0".to_vec()
+            ))
+        );
+        assert_eq!(
+            record.enrichment,
+            vec![
+                ("UID".to_string(), "root".to_string()),
+                ("AUID".to_string(), "john doe".to_string()),
+                ("ARCH".to_string(), "x86_64".to_string()),
+            ]
+        );
+
+        assert!("type=NOT_A_TYPE msg=audit(1.0:1): "
+            .parse::<AuditLogRecord>()
+            .is_err());
+        assert!("garbage".parse::<AuditLogRecord>().is_err());
+    }
+
+    #[test]
+    fn test_reader() {
+        let log = "type=CWD msg=audit(1.000:1): cwd=\"/\"\n\n\
+            type=UNKNOWN[1399] msg=audit(1.000:1): x=1\n";
+        let mut reader = AuditLogReader::new(log.as_bytes());
+        let record = reader.next_record().unwrap().unwrap();
+        assert_eq!(record.message.message_type(), AUDIT_CWD);
+        let record = reader.next_record().unwrap().unwrap();
+        assert_eq!(u16::from(record.message.message_type()), 1399);
+        assert!(reader.next_record().unwrap().is_none());
+    }
+}
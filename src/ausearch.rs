@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: MIT
+
+use std::io::{self, Write};
+
+use crate::{
+    grouper::EventGrouper, sequence::civil_from_days, AuditMessage, EventId,
+    EventLimits,
+};
+
+const DAYS: [&str; 7] = ["Thu", "Fri", "Sat", "Sun", "Mon", "Tue", "Wed"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct",
+    "Nov", "Dec",
+];
+
+/// Write audit records the way `ausearch` prints them:
+///
+/// ```text
+/// ----
+/// time->Wed Jun 24 09:50:00 2020
+/// type=PROCTITLE msg=audit(1592992200.383:66): proctitle=...
+/// type=SYSCALL msg=audit(1592992200.383:66): arch=c000003e syscall=59 ...
+/// ```
+///
+/// The records of concurrent events may be interleaved, so the records of
+/// each event are buffered until its last record (`EOE` or `PROCTITLE`,
+/// or the only record of a userspace message) is received, and then
+/// written in reverse order, as `ausearch` does. Events are written
+/// earlier when the [`EventLimits`] are reached, and all the pending
+/// events are written when [`AusearchWriter::flush`] is called. `EOE`
+/// records are not written. Unlike `ausearch`, the time is rendered in
+/// UTC.
+///
+/// Messages that do not belong to an event, such as the replies to
+/// control requests, are written on their own, without a time line.
+#[derive(Debug)]
+pub struct AusearchWriter<W> {
+    writer: W,
+    grouper: EventGrouper,
+}
+
+impl<W: Write> AusearchWriter<W> {
+    pub fn new(writer: W) -> Self {
+        AusearchWriter {
+            writer,
+            grouper: EventGrouper::default(),
+        }
+    }
+
+    /// Set the limits on the records buffered while waiting for the end of
+    /// the events
+    pub fn limits(mut self, limits: EventLimits) -> Self {
+        self.grouper.set_limits(limits);
+        self
+    }
+
+    /// Return the number of events written before their last record was
+    /// received, because of the limits
+    pub fn forced_flushes(&self) -> u64 {
+        self.grouper.forced_flushes()
+    }
+
+    pub fn write(&mut self, message: &AuditMessage) -> io::Result<()> {
+        let writer = &mut self.writer;
+        self.grouper
+            .push(message, |id, records| write_event(writer, id, records))
+    }
+
+    /// Write the records of the pending events
+    pub fn flush(&mut self) -> io::Result<()> {
+        let writer = &mut self.writer;
+        self.grouper
+            .flush(|id, records| write_event(writer, id, records))
+    }
+
+    /// Write the records of the pending events, and return the underlying
+    /// writer
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.flush()?;
+        Ok(self.writer)
+    }
+}
+
+fn write_event<W: Write>(
+    writer: &mut W,
+    id: Option<EventId>,
+    records: &[AuditMessage],
+) -> io::Result<()> {
+    writeln!(writer, "----")?;
+    if let Some(id) = id {
+        writeln!(writer, "time->{}", ctime(id.seconds))?;
+    }
+    for record in records.iter().rev() {
+        writeln!(writer, "{record}")?;
+    }
+    writer.flush()
+}
+
+/// Format a timestamp like `ctime(3)` does, in UTC
+fn ctime(timestamp: u64) -> String {
+    let days = timestamp / 86400;
+    let secs = timestamp % 86400;
+    let (year, month, day) = civil_from_days(days);
+
+    format!(
+        "{} {} {:2} {:02}:{:02}:{:02} {}",
+        DAYS[(days % 7) as usize],
+        MONTHS[(month - 1) as usize],
+        day,
+        secs / 3600,
+        secs % 3600 / 60,
+        secs % 60,
+        year
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{constants::*, EventPayload};
+
+    fn record(message_type: u16, data: &str) -> AuditMessage {
+        AuditMessage::Event(EventPayload::new(
+            message_type.into(),
+            data.as_bytes().to_vec(),
+        ))
+    }
+
+    #[test]
+    fn test_ctime() {
+        assert_eq!(ctime(0), "Thu Jan  1 00:00:00 1970");
+        assert_eq!(ctime(1592992200), "Wed Jun 24 09:50:00 2020");
+        assert_eq!(ctime(951782400), "Tue Feb 29 00:00:00 2000");
+    }
+
+    #[test]
+    fn test_writer() {
+        let mut writer = AusearchWriter::new(vec![]);
+        writer
+            .write(&record(AUDIT_SYSCALL, "audit(1592992200.383:66): a=1"))
+            .unwrap();
+        writer
+            .write(&record(AUDIT_CWD, "audit(1592992200.383:66): cwd=\"/\""))
+            .unwrap();
+        writer
+            .write(&record(AUDIT_EOE, "audit(1592992200.383:66): "))
+            .unwrap();
+        writer.write(&AuditMessage::GetStatus(None)).unwrap();
+        writer
+            .write(&record(AUDIT_USER_AVC, "audit(1592992201.000:67): b=2"))
+            .unwrap();
+        let out = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert_eq!(
+            out,
+            "----\n\
+            time->Wed Jun 24 09:50:00 2020\n\
+            type=CWD msg=audit(1592992200.383:66): cwd=\"/\"\n\
+            type=SYSCALL msg=audit(1592992200.383:66): a=1\n\
+            ----\n\
+            type=GET\n\
+            ----\n\
+            time->Wed Jun 24 09:50:01 2020\n\
+            type=USER_AVC msg=audit(1592992201.000:67): b=2\n"
+        );
+    }
+}
@@ -1,15 +1,19 @@
 // SPDX-License-Identifier: MIT
 
+use std::borrow::Cow;
+
 use anyhow::Context;
-use netlink_packet_utils::{
-    traits::{Parseable, ParseableParametrized},
-    DecodeError,
-};
+use netlink_packet_utils::{traits::ParseableParametrized, DecodeError};
 
 use crate::{
     constants::*,
+    is_kernel_event_type,
     rules::{RuleBuffer, RuleMessage},
-    AuditMessage, StatusMessage, StatusMessageBuffer,
+    AuditDecodeError, AuditMessage, AuditMessageRef, AuditMessageType,
+    FeaturesMessage, FeaturesMessageBuffer, MakeEquivMessage,
+    MakeEquivMessageBuffer, SignalInfoMessage, SignalInfoMessageBuffer,
+    StatusMessage, StatusMessageBuffer, StatusParseMode, TtyStatusMessage,
+    TtyStatusMessageBuffer, UserMessageKind, Utf8Policy,
 };
 
 #[non_exhaustive]
@@ -37,7 +41,7 @@ impl<'a, T: AsRef<[u8]> + ?Sized> AuditBuffer<&'a T> {
     }
 }
 
-impl<'a, T: AsRef<[u8]> + AsMut<[u8]> + ?Sized> AuditBuffer<&'a mut T> {
+impl<T: AsRef<[u8]> + AsMut<[u8]> + ?Sized> AuditBuffer<&mut T> {
     pub fn inner_mut(&mut self) -> &mut [u8] {
         self.buffer.as_mut()
     }
@@ -50,50 +54,135 @@ impl<'a, T: AsRef<[u8]> + ?Sized> ParseableParametrized<AuditBuffer<&'a T>, u16>
         buf: &AuditBuffer<&'a T>,
         message_type: u16,
     ) -> Result<Self, DecodeError> {
-        use self::AuditMessage::*;
-        let message = match message_type {
-            AUDIT_GET if buf.length() == 0 => GetStatus(None),
-            AUDIT_GET => {
-                let err = "failed to parse AUDIT_GET message";
-                let buf = StatusMessageBuffer::new(buf.inner());
-                GetStatus(Some(StatusMessage::parse(&buf).context(err)?))
-            }
-            AUDIT_SET => {
-                let err = "failed to parse AUDIT_SET message";
-                let buf = StatusMessageBuffer::new(buf.inner());
-                SetStatus(StatusMessage::parse(&buf).context(err)?)
-            }
-            AUDIT_ADD_RULE => {
-                let err = "failed to parse AUDIT_ADD_RULE message";
-                let buf = RuleBuffer::new_checked(buf.inner()).context(err)?;
-                AddRule(RuleMessage::parse(&buf).context(err)?)
-            }
-            AUDIT_DEL_RULE => {
-                let err = "failed to parse AUDIT_DEL_RULE message";
-                let buf = RuleBuffer::new_checked(buf.inner()).context(err)?;
-                DelRule(RuleMessage::parse(&buf).context(err)?)
-            }
-            AUDIT_LIST_RULES if buf.length() == 0 => ListRules(None),
-            AUDIT_LIST_RULES => {
-                let err = "failed to parse AUDIT_LIST_RULES message";
-                let buf = RuleBuffer::new_checked(buf.inner()).context(err)?;
-                ListRules(Some(RuleMessage::parse(&buf).context(err)?))
-            }
-            i if (AUDIT_EVENT_MESSAGE_MIN..AUDIT_EVENT_MESSAGE_MAX)
-                .contains(&i) =>
-            {
-                let data = String::from_utf8(buf.inner().to_vec()).context(
-                    "failed to parse audit event data as a valid string",
-                )?;
-                Event((i, data))
-            }
-            i => {
-                let data = String::from_utf8(buf.inner().to_vec()).context(
-                    "failed to parse audit event data as a valid string",
-                )?;
-                Other((i, data))
-            }
-        };
-        Ok(message)
+        Self::parse_with_param(buf, (message_type, Utf8Policy::default()))
+    }
+}
+
+impl<'a, T: AsRef<[u8]> + ?Sized>
+    ParseableParametrized<AuditBuffer<&'a T>, (u16, Utf8Policy)>
+    for AuditMessage
+{
+    fn parse_with_param(
+        buf: &AuditBuffer<&'a T>,
+        (message_type, utf8_policy): (u16, Utf8Policy),
+    ) -> Result<Self, DecodeError> {
+        AuditMessage::parse_payload(buf.inner(), message_type, utf8_policy)
+            .with_context(|| {
+                format!(
+                    "failed to parse {} message",
+                    AuditMessageType::from(message_type)
+                )
+            })
+            .map_err(DecodeError::from)
+    }
+}
+
+impl AuditMessage {
+    /// Parse the payload of a message of the given type. This is what the
+    /// `ParseableParametrized` implementations use, but the error is
+    /// returned as is, so that callers can tell the failures apart.
+    pub fn parse_payload(
+        payload: &[u8],
+        message_type: u16,
+        utf8_policy: Utf8Policy,
+    ) -> Result<Self, AuditDecodeError> {
+        AuditMessageRef::parse_payload(payload, message_type, utf8_policy)
+            .map(AuditMessageRef::into_owned)
+    }
+}
+
+impl<'a> AuditMessageRef<'a> {
+    /// Same as [`AuditMessage::parse_payload`], but the payload of events
+    /// and unknown messages is borrowed from `payload` instead of copied.
+    pub fn parse_payload(
+        payload: &'a [u8],
+        message_type: u16,
+        utf8_policy: Utf8Policy,
+    ) -> Result<Self, AuditDecodeError> {
+        if let Some(message) = parse_control(payload, message_type)? {
+            return Ok(AuditMessageRef::Control(message));
+        }
+        let data = parse_data(payload, utf8_policy)?;
+        if UserMessageKind::is_user_message(message_type) {
+            Ok(AuditMessageRef::User((message_type.into(), data)))
+        } else if is_kernel_event_type(message_type) {
+            Ok(AuditMessageRef::Event((message_type.into(), data)))
+        } else {
+            Ok(AuditMessageRef::Other((message_type.into(), data)))
+        }
+    }
+}
+
+/// Parse the control messages, or return `None` if `message_type` is not a
+/// control message this crate knows about.
+fn parse_control(
+    payload: &[u8],
+    message_type: u16,
+) -> Result<Option<AuditMessage>, AuditDecodeError> {
+    use self::AuditMessage::*;
+
+    let message = match message_type {
+        AUDIT_GET if payload.is_empty() => GetStatus(None),
+        AUDIT_GET => GetStatus(Some(StatusMessage::try_parse(
+            &StatusMessageBuffer::new(payload),
+            StatusParseMode::default(),
+        )?)),
+        AUDIT_SET => SetStatus(StatusMessage::try_parse(
+            &StatusMessageBuffer::new(payload),
+            StatusParseMode::default(),
+        )?),
+        AUDIT_ADD_RULE => {
+            AddRule(RuleMessage::try_parse(&RuleBuffer::new(payload))?)
+        }
+        AUDIT_DEL_RULE => {
+            DelRule(RuleMessage::try_parse(&RuleBuffer::new(payload))?)
+        }
+        AUDIT_LIST_RULES if payload.is_empty() => ListRules(None),
+        AUDIT_LIST_RULES => {
+            ListRules(Some(RuleMessage::try_parse(&RuleBuffer::new(payload))?))
+        }
+        AUDIT_GET_FEATURE if payload.is_empty() => GetFeature(None),
+        AUDIT_GET_FEATURE => GetFeature(Some(FeaturesMessage::try_parse(
+            &FeaturesMessageBuffer::new(payload),
+        )?)),
+        AUDIT_SET_FEATURE => SetFeature(FeaturesMessage::try_parse(
+            &FeaturesMessageBuffer::new(payload),
+        )?),
+        AUDIT_SIGNAL_INFO if payload.is_empty() => SignalInfo(None),
+        AUDIT_SIGNAL_INFO => SignalInfo(Some(SignalInfoMessage::try_parse(
+            &SignalInfoMessageBuffer::new(payload),
+        )?)),
+        AUDIT_TTY_GET if payload.is_empty() => TtyGet(None),
+        AUDIT_TTY_GET => TtyGet(Some(TtyStatusMessage::try_parse(
+            &TtyStatusMessageBuffer::new(payload),
+        )?)),
+        AUDIT_TTY_SET => TtySet(TtyStatusMessage::try_parse(
+            &TtyStatusMessageBuffer::new(payload),
+        )?),
+        AUDIT_TRIM => Trim,
+        AUDIT_MAKE_EQUIV => MakeEquiv(MakeEquivMessage::try_parse(
+            &MakeEquivMessageBuffer::new(payload),
+        )?),
+        _ => return Ok(None),
+    };
+    Ok(Some(message))
+}
+
+fn parse_data(
+    data: &[u8],
+    policy: Utf8Policy,
+) -> Result<Cow<'_, [u8]>, AuditDecodeError> {
+    match policy {
+        Utf8Policy::Raw => Ok(Cow::Borrowed(data)),
+        Utf8Policy::Strict => match std::str::from_utf8(data) {
+            Ok(_) => Ok(Cow::Borrowed(data)),
+            Err(e) => Err(AuditDecodeError::NonUtf8Payload {
+                valid_up_to: e.valid_up_to(),
+            }),
+        },
+        Utf8Policy::Lossy => match String::from_utf8_lossy(data) {
+            Cow::Borrowed(s) => Ok(Cow::Borrowed(s.as_bytes())),
+            Cow::Owned(s) => Ok(Cow::Owned(s.into_bytes())),
+        },
     }
 }
@@ -0,0 +1,61 @@
+// SPDX-License-Identifier: MIT
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+/// Byte order of the integers in a netlink message, or in a capture file.
+///
+/// Netlink messages use the byte order of the machine that sent them.
+/// Decoding the messages captured on a machine with another byte order
+/// requires to give it explicitly, see for instance
+/// [`StatusMessageBuffer::with_byte_order`](crate::StatusMessageBuffer::with_byte_order)
+/// or [`AuditFramer::set_byte_order`](crate::AuditFramer::set_byte_order).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+impl Endianness {
+    /// Byte order of the current machine
+    pub fn native() -> Self {
+        if cfg!(target_endian = "big") {
+            Endianness::Big
+        } else {
+            Endianness::Little
+        }
+    }
+
+    /// Return `true` if this is the byte order of the current machine
+    pub fn is_native(self) -> bool {
+        self == Self::native()
+    }
+
+    pub(crate) fn read_u16(self, buf: &[u8]) -> u16 {
+        match self {
+            Endianness::Little => LittleEndian::read_u16(buf),
+            Endianness::Big => BigEndian::read_u16(buf),
+        }
+    }
+
+    pub(crate) fn read_u32(self, buf: &[u8]) -> u32 {
+        match self {
+            Endianness::Little => LittleEndian::read_u32(buf),
+            Endianness::Big => BigEndian::read_u32(buf),
+        }
+    }
+
+    pub(crate) fn write_u32(self, buf: &mut [u8], value: u32) {
+        match self {
+            Endianness::Little => LittleEndian::write_u32(buf, value),
+            Endianness::Big => BigEndian::write_u32(buf, value),
+        }
+    }
+}
+
+/// The native byte order
+impl Default for Endianness {
+    fn default() -> Self {
+        Self::native()
+    }
+}
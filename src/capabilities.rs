@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_utils::{traits::Parseable, DecodeError};
+
+use crate::{
+    constants::*, rules::RuleFlags, FeaturesMessage, StatusLayout,
+    StatusMessage, StatusMessageBuffer, STATUS_MESSAGE_LAYOUTS,
+};
+
+/// Summary of what the running kernel supports, derived from the replies
+/// to an `AUDIT_GET` and an `AUDIT_GET_FEATURE` request.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct AuditKernelCapabilities {
+    /// `AUDIT_FEATURE_BITMAP_*` features reported in the status
+    pub feature_bitmap: u32,
+    /// Layout of the status message sent by the kernel, which tells which
+    /// status fields are present
+    pub status_layout: Option<StatusLayout>,
+    /// Filter lists rules can be added to
+    pub filters: Vec<RuleFlags>,
+    /// Reply to `AUDIT_GET_FEATURE`, if the kernel supports it
+    pub features: Option<FeaturesMessage>,
+}
+
+impl AuditKernelCapabilities {
+    /// Build the capabilities from the payload of the `AUDIT_GET` reply,
+    /// and the reply to `AUDIT_GET_FEATURE` if any (kernels older than
+    /// 3.13 reject this request).
+    pub fn new<T: AsRef<[u8]>>(
+        status: &StatusMessageBuffer<T>,
+        features: Option<FeaturesMessage>,
+    ) -> Result<Self, DecodeError> {
+        let parsed = StatusMessage::parse(status)?;
+        let mut filters = vec![
+            RuleFlags::FilterUser,
+            RuleFlags::FilterTask,
+            RuleFlags::FilterExit,
+            RuleFlags::FilterType,
+        ];
+        if parsed.feature_bitmap & AUDIT_FEATURE_BITMAP_FILTER_FS != 0 {
+            filters.push(RuleFlags::FilterFs);
+        }
+        Ok(AuditKernelCapabilities {
+            feature_bitmap: parsed.feature_bitmap,
+            status_layout: status.layout(),
+            filters,
+            features,
+        })
+    }
+
+    /// Return `true` if the kernel reports the given
+    /// `AUDIT_FEATURE_BITMAP_*` feature
+    pub fn has_feature(&self, feature: u32) -> bool {
+        self.feature_bitmap & feature == feature
+    }
+
+    /// Return the features reported in the status. Bits this crate does not
+    /// know about are kept: they are reported by kernels newer than this
+    /// crate.
+    pub fn features(&self) -> FeatureBitmap {
+        FeatureBitmap::from_raw(self.feature_bitmap)
+    }
+
+    /// Return `true` if the given status field (as named in
+    /// [`StatusLayout::last_field`]) is sent by the kernel
+    pub fn has_status_field(&self, name: &str) -> bool {
+        let len = match self.status_layout {
+            Some(layout) => layout.len,
+            None => return false,
+        };
+        STATUS_MESSAGE_LAYOUTS
+            .iter()
+            .any(|layout| layout.last_field == name && layout.len <= len)
+    }
+
+    /// Return `true` if rules can be added to the given filter list
+    pub fn supports_filter(&self, filter: RuleFlags) -> bool {
+        self.filters.contains(&filter)
+    }
+
+    /// Return whether the login uid is immutable, if the kernel reported
+    /// its features.
+    pub fn loginuid_immutable(&self) -> Option<bool> {
+        self.features
+            .as_ref()
+            .map(|f| f.is_enabled(AUDIT_FEATURE_LOGINUID_IMMUTABLE))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use netlink_packet_utils::traits::Emitable;
+
+    use super::*;
+
+    #[test]
+    fn test_capabilities() {
+        let status = StatusMessage {
+            feature_bitmap: AUDIT_FEATURE_BITMAP_BACKLOG_LIMIT
+                | AUDIT_FEATURE_BITMAP_FILTER_FS,
+            ..Default::default()
+        };
+        let mut bytes = vec![0; status.buffer_len()];
+        status.emit(&mut bytes);
+        let features = FeaturesMessage {
+            features: crate::feature_to_mask(AUDIT_FEATURE_LOGINUID_IMMUTABLE),
+            ..FeaturesMessage::new()
+        };
+
+        // Linux < 5.10, which does not send backlog_wait_time_actual
+        let buf = StatusMessageBuffer::new(&bytes[..40]);
+        let caps = AuditKernelCapabilities::new(&buf, Some(features)).unwrap();
+        assert!(caps.has_feature(AUDIT_FEATURE_BITMAP_BACKLOG_LIMIT));
+        assert!(!caps.has_feature(AUDIT_FEATURE_BITMAP_LOST_RESET));
+        assert_eq!(
+            caps.features(),
+            FeatureBitmap::BACKLOG_LIMIT | FeatureBitmap::FILTER_FS
+        );
+        assert!(caps.has_status_field("backlog_wait_time"));
+        assert!(!caps.has_status_field("backlog_wait_time_actual"));
+        assert!(caps.supports_filter(RuleFlags::FilterFs));
+        assert!(!caps.supports_filter(RuleFlags::FilterEntry));
+        assert_eq!(caps.loginuid_immutable(), Some(true));
+    }
+}
@@ -0,0 +1,565 @@
+// SPDX-License-Identifier: MIT
+
+//! Decoding of the netlink traffic captured on an `nlmon` interface, and
+//! recording of audit traffic in the same format.
+//!
+//! ```no_run
+//! use std::fs::File;
+//!
+//! use netlink_packet_audit::{capture::CaptureReader, AuditFramer};
+//!
+//! let mut reader = CaptureReader::new(File::open("audit.pcap")?)?;
+//! let mut framer = AuditFramer::new();
+//! while let Some(packet) = reader.next_packet()? {
+//!     if !packet.is_audit() {
+//!         continue;
+//!     }
+//!     framer.push(&packet.data)?;
+//!     while let Some(msg) = framer.next_audit_message() {
+//!         println!("{:?}: {msg:?}", packet.timestamp);
+//!     }
+//! }
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! The frames received from an audit socket can be saved with a
+//! [`CaptureWriter`], to be analyzed later with the reader above, or with
+//! `tcpdump` or `wireshark`.
+
+use std::{
+    io::{self, Read, Write},
+    time::{Duration, SystemTime},
+};
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian, NativeEndian};
+use bytes::BytesMut;
+
+use crate::codec::{next_frame, AuditCodecStats, DecodeConfig};
+pub use crate::Endianness;
+
+/// Link type of the `nlmon` captures (`LINKTYPE_NETLINK`)
+pub const LINKTYPE_NETLINK: u32 = 253;
+/// Length of the cooked header preceding the netlink messages
+pub const NLMON_HEADER_LEN: usize = 16;
+/// Netlink protocol number of the audit subsystem
+pub const NETLINK_AUDIT: u16 = 9;
+
+const PCAP_HEADER_LEN: usize = 24;
+const PCAP_RECORD_HEADER_LEN: usize = 16;
+const PCAP_MAGIC_MICROS: u32 = 0xa1b2_c3d4;
+const PCAP_MAGIC_NANOS: u32 = 0xa1b2_3c4d;
+/// Larger records are considered corrupted
+const MAX_RECORD_LEN: usize = 256 * 1024;
+/// `ARPHRD_NETLINK`, the hardware type of the `nlmon` interfaces
+const ARPHRD_NETLINK: u16 = 824;
+/// `PACKET_USER`: the packet was sent by a userspace socket
+const PACKET_USER: u16 = 3;
+/// `PACKET_KERNEL`: the packet was sent by the kernel socket
+const PACKET_KERNEL: u16 = 4;
+
+/// Sender of a captured packet, as recorded by `nlmon` in the packet type
+/// of the cooked header
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[non_exhaustive]
+pub enum PacketOrigin {
+    /// Sent by the kernel (`PACKET_KERNEL`), such as the audit events and
+    /// the replies to the requests
+    Kernel,
+    /// Sent by a userspace process (`PACKET_USER`), such as the requests
+    User,
+}
+
+impl PacketOrigin {
+    /// Origin of the messages received from the socket with the given
+    /// port id: the kernel socket is the only one with port id 0
+    pub fn from_port_id(port_id: u32) -> Self {
+        if port_id == 0 {
+            PacketOrigin::Kernel
+        } else {
+            PacketOrigin::User
+        }
+    }
+
+    fn from_packet_type(packet_type: u16) -> Option<Self> {
+        match packet_type {
+            PACKET_KERNEL => Some(PacketOrigin::Kernel),
+            PACKET_USER => Some(PacketOrigin::User),
+            _ => None,
+        }
+    }
+
+    fn packet_type(self) -> u16 {
+        match self {
+            PacketOrigin::Kernel => PACKET_KERNEL,
+            PacketOrigin::User => PACKET_USER,
+        }
+    }
+}
+
+/// Packet captured on an `nlmon` interface
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct CapturedPacket {
+    /// Time at which the packet was captured
+    pub timestamp: SystemTime,
+    /// Netlink protocol of the socket the packet was sent on
+    pub protocol: u16,
+    /// Sender of the packet, `None` if the packet type is unknown
+    pub origin: Option<PacketOrigin>,
+    /// Netlink messages, converted to the native byte order
+    pub data: BytesMut,
+}
+
+impl CapturedPacket {
+    /// Return `true` if the packet was sent on an audit socket
+    pub fn is_audit(&self) -> bool {
+        self.protocol == NETLINK_AUDIT
+    }
+}
+
+/// Reader for the pcap files produced by capturing the traffic of an
+/// `nlmon` interface (`tcpdump -i nlmon0 -w audit.pcap`)
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CaptureReader<R> {
+    reader: R,
+    file_order: Endianness,
+    netlink_order: Endianness,
+    nanos: bool,
+}
+
+impl<R: Read> CaptureReader<R> {
+    /// Read the pcap header from `reader`
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut header = [0; PCAP_HEADER_LEN];
+        reader.read_exact(&mut header)?;
+        let (file_order, nanos) = match (
+            LittleEndian::read_u32(&header[0..4]),
+            BigEndian::read_u32(&header[0..4]),
+        ) {
+            (PCAP_MAGIC_MICROS, _) => (Endianness::Little, false),
+            (PCAP_MAGIC_NANOS, _) => (Endianness::Little, true),
+            (_, PCAP_MAGIC_MICROS) => (Endianness::Big, false),
+            (_, PCAP_MAGIC_NANOS) => (Endianness::Big, true),
+            (magic, _) => {
+                return Err(invalid_data(format!(
+                    "not a pcap file (magic number is {magic:#x})"
+                )))
+            }
+        };
+        let link_type = file_order.read_u32(&header[20..24]);
+        if link_type != LINKTYPE_NETLINK {
+            return Err(invalid_data(format!(
+                "unsupported link type {link_type} (expected \
+                {LINKTYPE_NETLINK})"
+            )));
+        }
+        Ok(CaptureReader {
+            reader,
+            file_order,
+            // pcap files are written in the byte order of the machine
+            // that captured the traffic
+            netlink_order: file_order,
+            nanos,
+        })
+    }
+
+    /// Byte order of the pcap file, which is usually the byte order of the
+    /// machine the traffic was captured on
+    pub fn file_byte_order(&self) -> Endianness {
+        self.file_order
+    }
+
+    /// Set the byte order of the captured netlink messages, when it differs
+    /// from the byte order of the pcap file.
+    ///
+    /// The messages of the packets are converted to the native byte order:
+    /// the netlink headers, and the integer fields of the control messages
+    /// (status, rules, features, ...), so that captures made on a machine
+    /// of a different byte order can be decoded as usual.
+    pub fn set_netlink_byte_order(&mut self, order: Endianness) {
+        self.netlink_order = order;
+    }
+
+    /// Read the next packet, or return `None` at the end of the file
+    pub fn next_packet(&mut self) -> io::Result<Option<CapturedPacket>> {
+        let mut header = [0; PCAP_RECORD_HEADER_LEN];
+        match self.reader.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                return Ok(None)
+            }
+            Err(e) => return Err(e),
+        }
+        let seconds = self.file_order.read_u32(&header[0..4]);
+        let fraction = self.file_order.read_u32(&header[4..8]);
+        let len = self.file_order.read_u32(&header[8..12]) as usize;
+        if len > MAX_RECORD_LEN {
+            return Err(invalid_data(format!(
+                "record length {len} exceeds the maximum of {MAX_RECORD_LEN}"
+            )));
+        }
+        let mut data = BytesMut::zeroed(len);
+        self.reader.read_exact(&mut data)?;
+
+        let fraction = if self.nanos {
+            Duration::from_nanos(fraction.into())
+        } else {
+            Duration::from_micros(fraction.into())
+        };
+        let timestamp = SystemTime::UNIX_EPOCH
+            + Duration::from_secs(seconds.into())
+            + fraction;
+        let (protocol, origin) = strip_nlmon_header(&mut data)?;
+        if self.netlink_order != Endianness::native() {
+            convert_messages(&mut data, self.netlink_order);
+        }
+        Ok(Some(CapturedPacket {
+            timestamp,
+            protocol,
+            origin,
+            data,
+        }))
+    }
+}
+
+/// Writer for pcap files in the format of the `nlmon` captures, so that
+/// they can be read back with a [`CaptureReader`], `tcpdump` or
+/// `wireshark`.
+///
+/// The file is written in native byte order, with nanosecond timestamps.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct CaptureWriter<W> {
+    writer: W,
+}
+
+impl<W: Write> CaptureWriter<W> {
+    /// Write the pcap header to `writer`
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        let mut header = [0; PCAP_HEADER_LEN];
+        NativeEndian::write_u32(&mut header[0..4], PCAP_MAGIC_NANOS);
+        // version 2.4
+        NativeEndian::write_u16(&mut header[4..6], 2);
+        NativeEndian::write_u16(&mut header[6..8], 4);
+        NativeEndian::write_u32(&mut header[16..20], MAX_RECORD_LEN as u32);
+        NativeEndian::write_u32(&mut header[20..24], LINKTYPE_NETLINK);
+        writer.write_all(&header)?;
+        Ok(CaptureWriter { writer })
+    }
+
+    /// Write netlink messages sent by `origin`, such as the buffer filled
+    /// by `recv` on an audit socket (sent by [`PacketOrigin::Kernel`]), as
+    /// captured at `timestamp`. The origin of a received buffer can be
+    /// derived from the port id of its sender with
+    /// [`PacketOrigin::from_port_id`].
+    pub fn write_packet(
+        &mut self,
+        timestamp: SystemTime,
+        origin: PacketOrigin,
+        data: &[u8],
+    ) -> io::Result<()> {
+        let len = NLMON_HEADER_LEN + data.len();
+        if len > MAX_RECORD_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "packet length {len} exceeds the maximum of \
+                    {MAX_RECORD_LEN}"
+                ),
+            ));
+        }
+        let timestamp = timestamp
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default();
+
+        let mut header = [0; PCAP_RECORD_HEADER_LEN + NLMON_HEADER_LEN];
+        // the pcap format has 32 bits timestamps
+        NativeEndian::write_u32(&mut header[0..4], timestamp.as_secs() as u32);
+        NativeEndian::write_u32(&mut header[4..8], timestamp.subsec_nanos());
+        NativeEndian::write_u32(&mut header[8..12], len as u32);
+        NativeEndian::write_u32(&mut header[12..16], len as u32);
+
+        let cooked = &mut header[PCAP_RECORD_HEADER_LEN..];
+        BigEndian::write_u16(&mut cooked[0..2], origin.packet_type());
+        BigEndian::write_u16(&mut cooked[2..4], ARPHRD_NETLINK);
+        BigEndian::write_u16(&mut cooked[14..16], NETLINK_AUDIT);
+
+        self.writer.write_all(&header)?;
+        self.writer.write_all(data)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Flush the packets written so far, and return the underlying writer
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Remove the cooked header from a captured packet, and return the netlink
+/// protocol and the origin it contains.
+fn strip_nlmon_header(
+    data: &mut BytesMut,
+) -> io::Result<(u16, Option<PacketOrigin>)> {
+    if data.len() < NLMON_HEADER_LEN {
+        return Err(invalid_data(format!(
+            "captured packet is {} bytes long, which is shorter than the \
+            {NLMON_HEADER_LEN} bytes nlmon header",
+            data.len()
+        )));
+    }
+    let header = data.split_to(NLMON_HEADER_LEN);
+    let origin =
+        PacketOrigin::from_packet_type(BigEndian::read_u16(&header[0..2]));
+    Ok((BigEndian::read_u16(&header[14..16]), origin))
+}
+
+/// Convert the netlink messages in `data` from the given byte order to the
+/// native byte order (see [`AuditFramer::set_byte_order`]). As when
+/// decoding them, the length of the messages is repaired: the converted
+/// messages are those the framer would have split the packet into.
+///
+/// [`AuditFramer::set_byte_order`]: crate::AuditFramer::set_byte_order
+fn convert_messages(data: &mut BytesMut, order: Endianness) {
+    let config = DecodeConfig {
+        byte_order: order,
+        ..Default::default()
+    };
+    let mut src = data.split();
+    while let Some(frame) =
+        next_frame(&mut src, &config, &mut AuditCodecStats::default(), &mut ())
+    {
+        data.unsplit(frame);
+    }
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        constants::*,
+        rules::{RuleField, RuleFieldFlags, RuleMessage, RULE_BUF_MIN_LEN},
+        AuditFramer, AuditMessage, EventPayload, StatusMessage,
+    };
+    use netlink_packet_core::{
+        ErrorMessage, NetlinkHeader, NetlinkMessage, NetlinkPayload,
+        NETLINK_HEADER_LEN,
+    };
+
+    fn pcap(data: &str, order: Endianness) -> Vec<u8> {
+        fn put_u32(buf: &mut Vec<u8>, order: Endianness, value: u32) {
+            match order {
+                Endianness::Little => buf.extend(value.to_le_bytes()),
+                Endianness::Big => buf.extend(value.to_be_bytes()),
+            }
+        }
+        fn put_u16(buf: &mut Vec<u8>, order: Endianness, value: u16) {
+            match order {
+                Endianness::Little => buf.extend(value.to_le_bytes()),
+                Endianness::Big => buf.extend(value.to_be_bytes()),
+            }
+        }
+
+        let mut buf = vec![];
+        put_u32(&mut buf, order, PCAP_MAGIC_MICROS);
+        put_u16(&mut buf, order, 2);
+        put_u16(&mut buf, order, 4);
+        put_u32(&mut buf, order, 0);
+        put_u32(&mut buf, order, 0);
+        put_u32(&mut buf, order, 65535);
+        put_u32(&mut buf, order, LINKTYPE_NETLINK);
+
+        let len = NLMON_HEADER_LEN + NETLINK_HEADER_LEN + data.len();
+        put_u32(&mut buf, order, 1592992200);
+        put_u32(&mut buf, order, 383_000);
+        put_u32(&mut buf, order, len as u32);
+        put_u32(&mut buf, order, len as u32);
+
+        buf.extend([0, 4, 0x03, 0x38, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 9]);
+        put_u32(&mut buf, order, (NETLINK_HEADER_LEN + data.len()) as u32);
+        put_u16(&mut buf, order, AUDIT_SYSCALL);
+        put_u16(&mut buf, order, 0);
+        put_u32(&mut buf, order, 0);
+        put_u32(&mut buf, order, 0);
+        buf.extend(data.as_bytes());
+        buf
+    }
+
+    #[test]
+    fn test_capture_reader() {
+        let data = "audit(1592992200.383:66): pid=1";
+        for order in [Endianness::Little, Endianness::Big] {
+            let file = pcap(data, order);
+            let mut reader = CaptureReader::new(&file[..]).unwrap();
+            assert_eq!(reader.file_byte_order(), order);
+            let packet = reader.next_packet().unwrap().unwrap();
+            assert!(reader.next_packet().unwrap().is_none());
+            assert!(packet.is_audit());
+            assert_eq!(packet.origin, Some(PacketOrigin::Kernel));
+            assert_eq!(
+                packet.timestamp,
+                SystemTime::UNIX_EPOCH + Duration::from_millis(1592992200383)
+            );
+
+            let mut framer = AuditFramer::new();
+            framer.push(&packet.data).unwrap();
+            assert_eq!(
+                framer.next_audit_message(),
+                Some(Ok(AuditMessage::Event(EventPayload::new(
+                    AUDIT_SYSCALL.into(),
+                    data.into()
+                ))))
+            );
+        }
+    }
+
+    #[test]
+    fn test_capture_writer() {
+        let mut buf = vec![];
+        let msg = AuditMessage::Event(EventPayload::new(
+            AUDIT_SYSCALL.into(),
+            b"audit(1592992200.383:66): pid=1".to_vec(),
+        ));
+        let mut nl_msg = NetlinkMessage::new(
+            NetlinkHeader::default(),
+            NetlinkPayload::from(msg.clone()),
+        );
+        nl_msg.finalize();
+        let mut packet = vec![0; nl_msg.buffer_len()];
+        nl_msg.serialize(&mut packet);
+
+        let timestamp =
+            SystemTime::UNIX_EPOCH + Duration::from_nanos(1592992200383000123);
+        let mut writer = CaptureWriter::new(&mut buf).unwrap();
+        let origins = [PacketOrigin::from_port_id(0), PacketOrigin::User];
+        for origin in origins {
+            writer.write_packet(timestamp, origin, &packet).unwrap();
+        }
+        writer.into_inner().unwrap();
+
+        let mut reader = CaptureReader::new(&buf[..]).unwrap();
+        assert_eq!(reader.file_byte_order(), Endianness::native());
+        for origin in origins {
+            let captured = reader.next_packet().unwrap().unwrap();
+            assert!(captured.is_audit());
+            assert_eq!(captured.origin, Some(origin));
+            assert_eq!(captured.timestamp, timestamp);
+            let mut framer = AuditFramer::new();
+            framer.push(&captured.data).unwrap();
+            assert_eq!(framer.next_audit_message(), Some(Ok(msg.clone())));
+        }
+        assert!(reader.next_packet().unwrap().is_none());
+
+        let mut writer = CaptureWriter::new(vec![]).unwrap();
+        assert_eq!(
+            writer
+                .write_packet(
+                    timestamp,
+                    PacketOrigin::Kernel,
+                    &vec![0; MAX_RECORD_LEN]
+                )
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::InvalidInput
+        );
+    }
+
+    /// Swap the bytes of the netlink header at the beginning of `data`,
+    /// and of the given 32 bits words of the payload
+    fn swap_message(data: &mut [u8], payload_words: usize) {
+        for field in [0..4, 4..6, 6..8, 8..12, 12..16] {
+            data[field].reverse();
+        }
+        for word in data[NETLINK_HEADER_LEN..]
+            .chunks_exact_mut(4)
+            .take(payload_words)
+        {
+            word.reverse();
+        }
+    }
+
+    #[test]
+    fn test_foreign_byte_order() {
+        let foreign = match Endianness::native() {
+            Endianness::Little => Endianness::Big,
+            Endianness::Big => Endianness::Little,
+        };
+        let mut status = StatusMessage::new();
+        status.pid = 42;
+        let mut rule = RuleMessage::new();
+        rule.fields
+            .push((RuleField::Watch("/etc".into()), RuleFieldFlags::Equal));
+        let mut ack = ErrorMessage::default();
+        ack.header = vec![0; NETLINK_HEADER_LEN];
+        NativeEndian::write_u16(&mut ack.header[4..6], AUDIT_ADD_RULE);
+        // an event whose text looks like a netlink header
+        let event = AuditMessage::Event(EventPayload::new(
+            AUDIT_SYSCALL.into(),
+            b"\x20\0\0\0\xea\x03\0\0\0\0\0\0\0\0\0\0abcd".to_vec(),
+        ));
+        let payloads = [
+            NetlinkPayload::from(AuditMessage::GetStatus(Some(status))),
+            NetlinkPayload::from(AuditMessage::ListRules(Some(rule))),
+            NetlinkPayload::Error(ack),
+            NetlinkPayload::from(event),
+        ];
+
+        let mut data = vec![];
+        for payload in payloads.iter().cloned() {
+            let mut msg =
+                NetlinkMessage::new(NetlinkHeader::default(), payload);
+            msg.finalize();
+            let mut bytes = vec![0; msg.buffer_len()];
+            msg.serialize(&mut bytes);
+            let words = match msg.header.message_type {
+                AUDIT_GET => (msg.buffer_len() - NETLINK_HEADER_LEN) / 4,
+                AUDIT_LIST_RULES => RULE_BUF_MIN_LEN / 4,
+                AUDIT_SYSCALL => {
+                    // the kernel does not count the header in the length
+                    // of the events
+                    let len = (bytes.len() - NETLINK_HEADER_LEN) as u32;
+                    NativeEndian::write_u32(&mut bytes[0..4], len);
+                    0
+                }
+                _ => {
+                    swap_message(&mut bytes[NETLINK_HEADER_LEN + 4..], 0);
+                    1
+                }
+            };
+            swap_message(&mut bytes, words);
+            data.extend(bytes);
+        }
+        let status_len = foreign.read_u32(&data[0..4]) as usize;
+        assert_eq!(foreign.read_u32(&data[NETLINK_HEADER_LEN + 12..]), 42);
+
+        let mut data = BytesMut::from(&data[..]);
+        convert_messages(&mut data, foreign);
+        let rule_len = NativeEndian::read_u32(&data[status_len..]) as usize;
+        assert_eq!(&data[status_len + rule_len - 4..][..4], b"/etc");
+        let mut framer = AuditFramer::new();
+        framer.push(&data).unwrap();
+        for payload in payloads.iter() {
+            assert_eq!(&framer.next_message().unwrap().payload, payload);
+        }
+        assert!(framer.next_message::<AuditMessage>().is_none());
+    }
+
+    #[test]
+    fn test_invalid_capture() {
+        let mut file = pcap("", Endianness::Little);
+        file[20] = 1;
+        assert_eq!(
+            CaptureReader::new(&file[..]).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert!(CaptureReader::new(&[0u8; 24][..]).is_err());
+    }
+}
@@ -0,0 +1,856 @@
+// SPDX-License-Identifier: MIT
+
+//! Blocking client for the kernel audit subsystem, for tools that send a
+//! few requests and do not need an async runtime.
+//!
+//! ```no_run
+//! use netlink_packet_audit::client::AuditClient;
+//!
+//! let mut client = AuditClient::new()?;
+//! let status = client.get_status()?;
+//! println!("audit enabled: {}", status.enabled);
+//! for rule in client.list_rules()? {
+//!     println!("{rule:?}");
+//! }
+//! # Ok::<(), std::io::Error>(())
+//! ```
+//!
+//! Monitoring tools can observe the events without registering as the
+//! audit daemon with an [`AuditListener`], which joins the read-only
+//! multicast group (this requires `CAP_AUDIT_READ`).
+
+use std::{collections::VecDeque, fmt, io, path::Path, thread, time::Duration};
+
+use netlink_packet_core::{NetlinkMessage, NetlinkPayload};
+use netlink_sys::{protocols::NETLINK_AUDIT, Socket, SocketAddr};
+
+use crate::{
+    constants::{AUDIT_LOCKED, AUDIT_NLGRP_READLOG, AUDIT_STATUS_ENABLED},
+    requests,
+    rules::{RuleMessage, RulePlan},
+    AuditFramer, AuditMessage, FeaturesMessage, LostEventTracker, LostEvents,
+    ReplyCollector, SignalInfoMessage, StatusMessage,
+};
+
+const RECV_BUFFER_LEN: usize = 64 * 1024;
+
+/// Synchronous audit netlink client.
+///
+/// Each method sends a request and blocks until the kernel has answered
+/// it. Sequence numbers are assigned by the client, and the errors
+/// reported by the kernel are returned as [`io::Error`]s, built from the
+/// [`AuditNetlinkError`].
+///
+/// The socket is not subscribed to the audit events multicast group, so
+/// only the replies to the requests are received.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct AuditClient {
+    socket: Socket,
+    framer: AuditFramer,
+    sequence_number: u32,
+    recv_buf: Vec<u8>,
+    events: VecDeque<AuditMessage>,
+}
+
+impl AuditClient {
+    /// Open an audit netlink socket, connected to the kernel
+    pub fn new() -> io::Result<Self> {
+        let mut socket = Socket::new(NETLINK_AUDIT)?;
+        socket.bind_auto()?;
+        socket.connect(&SocketAddr::new(0, 0))?;
+        Ok(AuditClient {
+            socket,
+            framer: AuditFramer::new(),
+            sequence_number: 0,
+            recv_buf: Vec::with_capacity(RECV_BUFFER_LEN),
+            events: VecDeque::new(),
+        })
+    }
+
+    pub fn socket(&self) -> &Socket {
+        &self.socket
+    }
+
+    pub fn framer_mut(&mut self) -> &mut AuditFramer {
+        &mut self.framer
+    }
+
+    /// Send a request, such as the ones built by the [`requests`] module,
+    /// and return the messages the kernel answered with. The sequence
+    /// number of the request is overwritten.
+    ///
+    /// Commands (`NLM_F_ACK`) return once acknowledged, dumps
+    /// (`NLM_F_DUMP`) once `NLMSG_DONE` is received, and other requests
+    /// after the first reply.
+    ///
+    /// If the kernel denies the request with `EPERM`, the error wraps a
+    /// [`MissingCapability`] naming the capability the request requires,
+    /// or telling that the audit configuration is locked.
+    pub fn request(
+        &mut self,
+        request: NetlinkMessage<AuditMessage>,
+    ) -> io::Result<Vec<AuditMessage>> {
+        self.send(request).map(ReplyCollector::into_replies)
+    }
+
+    /// Send a request, and return the complete answer of the kernel
+    fn send(
+        &mut self,
+        request: NetlinkMessage<AuditMessage>,
+    ) -> io::Result<ReplyCollector> {
+        let (capability, operation) = match request.payload {
+            NetlinkPayload::InnerMessage(ref msg) if msg.is_user_message() => {
+                (AuditCapability::AuditWrite, msg.message_type().to_string())
+            }
+            NetlinkPayload::InnerMessage(ref msg) => (
+                AuditCapability::AuditControl,
+                msg.message_type().to_string(),
+            ),
+            _ => (AuditCapability::AuditControl, "netlink".to_string()),
+        };
+        let is_get = matches!(
+            request.payload,
+            NetlinkPayload::InnerMessage(AuditMessage::GetStatus(_))
+        );
+        match self.exchange(request) {
+            Err(e) if e.raw_os_error() == Some(libc::EPERM) => {
+                // A locked configuration denies the changes with EPERM
+                // too. The status can only be read with the
+                // capability, so it tells both causes apart.
+                let cause = if !is_get && self.is_locked() {
+                    DenialCause::Locked
+                } else {
+                    DenialCause::Capability
+                };
+                Err(MissingCapability::check(
+                    e,
+                    capability,
+                    cause,
+                    &format!("{operation} request"),
+                ))
+            }
+            result => result,
+        }
+    }
+
+    /// Return `true` if the status can be read, and reports a locked
+    /// configuration
+    fn is_locked(&mut self) -> bool {
+        let replies = self
+            .exchange(requests::get_status())
+            .map(ReplyCollector::into_replies);
+        matches!(
+            replies.as_deref(),
+            Ok([AuditMessage::GetStatus(Some(status))])
+                if status.enabled == AUDIT_LOCKED
+        )
+    }
+
+    fn exchange(
+        &mut self,
+        mut request: NetlinkMessage<AuditMessage>,
+    ) -> io::Result<ReplyCollector> {
+        self.sequence_number = self.sequence_number.wrapping_add(1).max(1);
+        request.header.sequence_number = self.sequence_number;
+        request.finalize();
+        let mut buf = vec![0; request.buffer_len()];
+        request.serialize(&mut buf);
+        self.socket.send(&buf, 0)?;
+
+        let mut collector = ReplyCollector::new(&request);
+        loop {
+            let mut complete = Ok(false);
+            while let Some(msg) = self.framer.next_message::<AuditMessage>() {
+                complete = collector.push(msg);
+                if !matches!(complete, Ok(false)) {
+                    break;
+                }
+            }
+            self.events.extend(collector.take_events());
+            if complete? {
+                return Ok(collector);
+            }
+            self.recv_buf.clear();
+            self.socket.recv(&mut self.recv_buf, 0)?;
+            self.framer.push(&self.recv_buf)?;
+        }
+    }
+
+    /// Send a request the kernel answers with a single message
+    fn query(
+        &mut self,
+        request: NetlinkMessage<AuditMessage>,
+    ) -> io::Result<AuditMessage> {
+        self.request(request)?.pop().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "missing reply")
+        })
+    }
+
+    /// Send a command, and wait for the kernel to acknowledge it
+    fn command(
+        &mut self,
+        request: NetlinkMessage<AuditMessage>,
+    ) -> io::Result<()> {
+        self.request(request).map(|_| ())
+    }
+
+    /// Return the status of the audit subsystem (`AUDIT_GET`)
+    pub fn get_status(&mut self) -> io::Result<StatusMessage> {
+        match self.query(requests::get_status())? {
+            AuditMessage::GetStatus(Some(status)) => Ok(status),
+            msg => Err(unexpected_reply(&msg)),
+        }
+    }
+
+    /// Return the state of the `AUDIT_FEATURE_*` features
+    /// (`AUDIT_GET_FEATURE`). Kernels older than 3.13 reject the request
+    /// with `EINVAL`.
+    pub fn get_features(&mut self) -> io::Result<FeaturesMessage> {
+        match self.query(requests::get_features())? {
+            AuditMessage::GetFeature(Some(features)) => Ok(features),
+            msg => Err(unexpected_reply(&msg)),
+        }
+    }
+
+    /// Query the status and the features of the audit subsystem, and
+    /// summarize them. The features are left out on kernels that do not
+    /// support `AUDIT_GET_FEATURE`.
+    pub fn health_check(&mut self) -> io::Result<AuditHealth> {
+        let status = self.get_status()?;
+        let features = match self.get_features() {
+            Ok(features) => Some(features),
+            Err(e) if e.raw_os_error() == Some(libc::EINVAL) => None,
+            Err(e) => return Err(e),
+        };
+        Ok(AuditHealth::new(status, features))
+    }
+
+    /// Update the fields of the status selected by `status.mask`
+    /// (`AUDIT_SET`)
+    pub fn set_status(&mut self, status: StatusMessage) -> io::Result<()> {
+        self.command(requests::set_status(status))
+    }
+
+    /// Reset the kernel's lost messages counter, and return the value it
+    /// had before the reset. This requires a kernel advertising
+    /// `AUDIT_FEATURE_BITMAP_LOST_RESET`.
+    pub fn reset_lost(&mut self) -> io::Result<u32> {
+        let request = requests::set_status(StatusMessage::reset_lost());
+        let collector = self.send(request)?;
+        Ok(collector
+            .ack()
+            .and_then(StatusMessage::lost_from_ack)
+            .unwrap_or(0))
+    }
+
+    /// Poll the status, and return the events lost since the previous
+    /// call with the same tracker. Call it periodically to notice event
+    /// loss. If the tracker is configured to raise the backlog limit, the
+    /// limit is raised before returning.
+    pub fn check_lost_events(
+        &mut self,
+        tracker: &mut LostEventTracker,
+    ) -> io::Result<Option<LostEvents>> {
+        let status = self.get_status()?;
+        let lost = match tracker.observe(&status) {
+            Some(lost) => lost,
+            None => return Ok(None),
+        };
+        warn!(
+            "{} audit events lost (backlog {}/{})",
+            lost.count, lost.backlog, lost.backlog_limit
+        );
+        if let Some(msg) = tracker.backlog_increase(&lost) {
+            self.set_status(msg)?;
+        }
+        Ok(Some(lost))
+    }
+
+    /// Enable or disable auditing
+    pub fn set_enabled(&mut self, enabled: bool) -> io::Result<()> {
+        self.set_status(StatusMessage {
+            mask: AUDIT_STATUS_ENABLED,
+            enabled: enabled.into(),
+            ..Default::default()
+        })
+    }
+
+    /// Return the rules currently loaded (`AUDIT_LIST_RULES`)
+    pub fn list_rules(&mut self) -> io::Result<Vec<RuleMessage>> {
+        self.request(requests::list_rules())?
+            .into_iter()
+            .map(|msg| match msg {
+                AuditMessage::ListRules(Some(rule)) => Ok(rule),
+                msg => Err(unexpected_reply(&msg)),
+            })
+            .collect()
+    }
+
+    /// Load a rule (`AUDIT_ADD_RULE`)
+    pub fn add_rule(&mut self, rule: RuleMessage) -> io::Result<()> {
+        self.command(requests::add_rule(rule))
+    }
+
+    /// Delete a rule (`AUDIT_DEL_RULE`). The rule must match the loaded
+    /// one exactly.
+    pub fn delete_rule(&mut self, rule: RuleMessage) -> io::Result<()> {
+        self.command(requests::del_rule(rule))
+    }
+
+    /// Return the process that last sent a termination or reload signal
+    /// to the audit daemon (`AUDIT_SIGNAL_INFO`)
+    pub fn signal_info(&mut self) -> io::Result<SignalInfoMessage> {
+        match self.query(requests::signal_info())? {
+            AuditMessage::SignalInfo(Some(info)) => Ok(info),
+            msg => Err(unexpected_reply(&msg)),
+        }
+    }
+
+    /// Return the pid of the audit daemon currently registered with the
+    /// kernel, or `None` if there is none.
+    ///
+    /// The pid reported by the status is confirmed with an
+    /// `AUDIT_SIGNAL_INFO` query. If the kernel does not answer it, the
+    /// pid is looked up in `/proc` instead, and ignored when the process
+    /// no longer exists: a daemon that died without unregistering is
+    /// only noticed by the kernel when it next fails to deliver an event.
+    pub fn registered_daemon(&mut self) -> io::Result<Option<u32>> {
+        let pid = self.get_status()?.pid;
+        if pid == 0 {
+            return Ok(None);
+        }
+        if let Err(e) = self.signal_info() {
+            debug!("AUDIT_SIGNAL_INFO failed ({e}), looking up pid {pid}");
+            let alive = Path::new(&format!("/proc/{pid}")).exists();
+            return Ok(alive.then_some(pid));
+        }
+        Ok(Some(pid))
+    }
+
+    /// Register the process `pid` as the audit daemon, with this client's
+    /// socket receiving the events. A `pid` of 0 unregisters the current
+    /// daemon.
+    ///
+    /// If another daemon is registered and still alive, the kernel
+    /// refuses the change, and the returned error is of kind
+    /// [`io::ErrorKind::AlreadyExists`] (`EEXIST`).
+    pub fn set_pid(&mut self, pid: u32) -> io::Result<()> {
+        self.set_status(StatusMessage::new_pid(pid))
+    }
+
+    /// Register the current process as the audit daemon, retrying up to
+    /// `retries` times, `interval` apart, while another daemon is
+    /// registered. This gives a daemon that is shutting down the time to
+    /// exit: the kernel lets a new daemon take over as soon as the
+    /// previous one is gone.
+    ///
+    /// Once registered, the events are read with
+    /// [`AuditClient::next_event`]. Before exiting, the daemon should
+    /// unregister with `set_pid(0)`.
+    pub fn register_daemon(
+        &mut self,
+        retries: u32,
+        interval: Duration,
+    ) -> io::Result<()> {
+        let pid = std::process::id();
+        let mut attempts = 0;
+        loop {
+            match self.set_pid(pid) {
+                Err(e)
+                    if e.kind() == io::ErrorKind::AlreadyExists
+                        && attempts < retries =>
+                {
+                    attempts += 1;
+                    debug!(
+                        "another audit daemon is registered, retrying in \
+                        {interval:?} ({attempts}/{retries})"
+                    );
+                    thread::sleep(interval);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Block until the next event is received. Events are only sent to
+    /// the registered audit daemon (see [`AuditClient::register_daemon`]).
+    ///
+    /// Events received while waiting for the answer to a request are
+    /// kept, and returned first.
+    pub fn next_event(&mut self) -> io::Result<AuditMessage> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Ok(event);
+            }
+            while let Some(msg) = self.framer.next_message::<AuditMessage>() {
+                // skip the late answers to requests that were given up on
+                if msg.header.sequence_number != 0 {
+                    continue;
+                }
+                if let NetlinkPayload::InnerMessage(event) = msg.payload {
+                    return Ok(event);
+                }
+            }
+            self.recv_buf.clear();
+            self.socket.recv(&mut self.recv_buf, 0)?;
+            self.framer.push(&self.recv_buf)?;
+        }
+    }
+}
+
+/// Summary of the state of the audit subsystem, returned by
+/// [`AuditClient::health_check`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct AuditHealth {
+    /// Status reported by the kernel
+    pub status: StatusMessage,
+    /// Features reported by the kernel, if it supports
+    /// `AUDIT_GET_FEATURE`
+    pub features: Option<FeaturesMessage>,
+}
+
+impl AuditHealth {
+    pub fn new(
+        status: StatusMessage,
+        features: Option<FeaturesMessage>,
+    ) -> Self {
+        AuditHealth { status, features }
+    }
+
+    /// Return `true` if auditing is enabled, possibly with a locked
+    /// configuration
+    pub fn is_enabled(&self) -> bool {
+        self.status.enabled != 0
+    }
+
+    /// Return `true` if the configuration is locked until the next reboot
+    /// (`auditctl -e 2`)
+    pub fn is_locked(&self) -> bool {
+        self.status.enabled == AUDIT_LOCKED
+    }
+
+    /// Return `true` if an audit daemon is registered
+    pub fn has_daemon(&self) -> bool {
+        self.status.pid != 0
+    }
+
+    /// Fraction of the backlog in use, between 0 and 1, or `None` if the
+    /// backlog is not limited
+    pub fn backlog_usage(&self) -> Option<f64> {
+        match self.status.backlog_limit {
+            0 => None,
+            limit => Some(f64::from(self.status.backlog) / f64::from(limit)),
+        }
+    }
+
+    /// Number of events the kernel lost since boot, or since the counter
+    /// was last reset
+    pub fn lost(&self) -> u32 {
+        self.status.lost
+    }
+
+    /// Version of the features structure reported by the kernel
+    pub fn features_version(&self) -> Option<u32> {
+        self.features.as_ref().map(|features| features.version)
+    }
+}
+
+/// Reconcile the rules loaded in the kernel with a desired set of rules.
+///
+/// ```no_run
+/// use netlink_packet_audit::client::{AuditClient, RuleManager};
+/// # let desired = vec![];
+///
+/// let mut client = AuditClient::new()?;
+/// let plan = RuleManager::new(&mut client).dry_run(true).apply(&desired)?;
+/// println!("would delete {:?} and add {:?}", plan.delete, plan.add);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct RuleManager<'a> {
+    client: &'a mut AuditClient,
+    dry_run: bool,
+}
+
+impl<'a> RuleManager<'a> {
+    pub fn new(client: &'a mut AuditClient) -> Self {
+        RuleManager {
+            client,
+            dry_run: false,
+        }
+    }
+
+    /// When enabled, [`RuleManager::apply`] only computes the changes
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Compute the changes needed to load exactly the `desired` rules
+    pub fn plan(&mut self, desired: &[RuleMessage]) -> io::Result<RulePlan> {
+        let installed = self.client.list_rules()?;
+        Ok(RulePlan::new(&installed, desired))
+    }
+
+    /// Delete the loaded rules that are not desired, then add the missing
+    /// ones, and return the changes that were made. Applying the same
+    /// rules again makes no change.
+    ///
+    /// The desired rules are validated before any change is made. If the
+    /// kernel rejects a change, the error is returned, and the changes
+    /// that preceded it are kept.
+    pub fn apply(&mut self, desired: &[RuleMessage]) -> io::Result<RulePlan> {
+        for rule in desired {
+            rule.validate().map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidInput, e.to_string())
+            })?;
+        }
+        let plan = self.plan(desired)?;
+        if self.dry_run {
+            return Ok(plan);
+        }
+        for rule in &plan.delete {
+            self.client.delete_rule(rule.clone())?;
+        }
+        for rule in &plan.add {
+            self.client.add_rule(rule.clone())?;
+        }
+        Ok(plan)
+    }
+}
+
+/// Audit daemon session: auditing is enabled and the current process is
+/// registered as the audit daemon while the session is alive, and the
+/// previous state is restored when it ends.
+///
+/// ```no_run
+/// use netlink_packet_audit::client::{AuditClient, Session};
+///
+/// let mut client = AuditClient::new()?;
+/// let mut session = Session::start(&mut client)?;
+/// for _ in 0..10 {
+///     println!("{}", session.client().next_event()?);
+/// }
+/// session.close()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Session<'a> {
+    client: &'a mut AuditClient,
+    was_enabled: bool,
+    closed: bool,
+}
+
+impl<'a> Session<'a> {
+    /// Enable auditing if it is disabled, and register the current process
+    /// as the audit daemon. This fails if another daemon is registered.
+    pub fn start(client: &'a mut AuditClient) -> io::Result<Self> {
+        let status = client.get_status()?;
+        let was_enabled = status.enabled != 0;
+        if !was_enabled {
+            client.set_enabled(true)?;
+        }
+        if let Err(e) = client.set_pid(std::process::id()) {
+            // best effort: the registration error is the one to report
+            if !was_enabled {
+                if let Err(restore) = client.set_enabled(false) {
+                    warn!("failed to disable auditing again: {restore}");
+                }
+            }
+            return Err(e);
+        }
+        Ok(Session {
+            client,
+            was_enabled,
+            closed: false,
+        })
+    }
+
+    /// Client the events are received with
+    pub fn client(&mut self) -> &mut AuditClient {
+        self.client
+    }
+
+    /// Unregister the process, and disable auditing if it was disabled
+    /// when the session started. Unlike dropping the session, this
+    /// reports the errors.
+    pub fn close(mut self) -> io::Result<()> {
+        self.restore()
+    }
+
+    fn restore(&mut self) -> io::Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+        self.closed = true;
+        self.client.set_pid(0)?;
+        if !self.was_enabled {
+            self.client.set_enabled(false)?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Session<'_> {
+    fn drop(&mut self) {
+        if let Err(e) = self.restore() {
+            warn!("failed to restore the audit configuration: {e}");
+        }
+    }
+}
+
+/// Listener for the audit events multicast to the `AUDIT_NLGRP_READLOG`
+/// group.
+///
+/// Unlike the audit daemon, which receives the events on the socket it
+/// registered with `AUDIT_STATUS_PID`, listeners do not affect the kernel
+/// backlog: when a listener does not keep up, its socket buffer overflows
+/// and the events are dropped for this listener only. Such overruns are
+/// counted by [`AuditListener::overruns`], and the listener keeps going.
+/// The dropped events can be detected with
+/// [`AuditFramer::on_sequence_gap`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct AuditListener {
+    socket: Socket,
+    framer: AuditFramer,
+    overruns: u64,
+    recv_buf: Vec<u8>,
+}
+
+impl AuditListener {
+    /// Open an audit netlink socket, and join the read-only multicast
+    /// group
+    pub fn new() -> io::Result<Self> {
+        let mut socket = Socket::new(NETLINK_AUDIT)?;
+        socket.bind_auto()?;
+        socket.add_membership(AUDIT_NLGRP_READLOG).map_err(|e| {
+            MissingCapability::check(
+                e,
+                AuditCapability::AuditRead,
+                DenialCause::Capability,
+                "joining the audit multicast group",
+            )
+        })?;
+        Ok(AuditListener {
+            socket,
+            framer: AuditFramer::new(),
+            overruns: 0,
+            recv_buf: Vec::with_capacity(RECV_BUFFER_LEN),
+        })
+    }
+
+    pub fn socket(&self) -> &Socket {
+        &self.socket
+    }
+
+    pub fn framer_mut(&mut self) -> &mut AuditFramer {
+        &mut self.framer
+    }
+
+    /// Number of times the socket buffer overflowed, and events were lost
+    pub fn overruns(&self) -> u64 {
+        self.overruns
+    }
+
+    /// Block until the next record is received
+    pub fn next_message(&mut self) -> io::Result<AuditMessage> {
+        loop {
+            if let Some(result) = self.framer.next_audit_message() {
+                return result.map_err(io::Error::from);
+            }
+            self.recv_buf.clear();
+            match self.socket.recv(&mut self.recv_buf, 0) {
+                Ok(_) => self.framer.push(&self.recv_buf)?,
+                Err(e) if e.raw_os_error() == Some(libc::ENOBUFS) => {
+                    self.overruns += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Capability a process needs to perform audit operations
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum AuditCapability {
+    /// `CAP_AUDIT_CONTROL`, to configure the audit subsystem and its rules
+    AuditControl,
+    /// `CAP_AUDIT_READ`, to receive the events multicast to listeners
+    AuditRead,
+    /// `CAP_AUDIT_WRITE`, to send user space records
+    AuditWrite,
+}
+
+impl fmt::Display for AuditCapability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AuditCapability::AuditControl => "CAP_AUDIT_CONTROL",
+            AuditCapability::AuditRead => "CAP_AUDIT_READ",
+            AuditCapability::AuditWrite => "CAP_AUDIT_WRITE",
+        })
+    }
+}
+
+/// Why the kernel denied an operation with `EPERM`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum DenialCause {
+    /// The process lacks the capability. The kernel also denies the
+    /// operations of processes outside the initial user and pid
+    /// namespaces with the same error, whatever their capabilities.
+    Capability,
+    /// The audit configuration is locked until the next reboot
+    /// (`auditctl -e 2`), so it cannot be changed even with the
+    /// capability.
+    Locked,
+}
+
+/// Error returned by the client when the kernel denies an operation with
+/// `EPERM`. It is wrapped in an [`io::Error`] of kind
+/// [`io::ErrorKind::PermissionDenied`]:
+///
+/// ```no_run
+/// use netlink_packet_audit::client::{AuditClient, MissingCapability};
+///
+/// let mut client = AuditClient::new()?;
+/// if let Err(e) = client.set_enabled(true) {
+///     match e.get_ref().and_then(|e| e.downcast_ref::<MissingCapability>()) {
+///         Some(missing) => eprintln!("{missing} ({:?})", missing.cause),
+///         None => eprintln!("{e}"),
+///     }
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[non_exhaustive]
+pub struct MissingCapability {
+    /// Capability the operation requires
+    pub capability: AuditCapability,
+    /// Operation that was denied
+    pub operation: String,
+    /// Most likely reason of the denial
+    pub cause: DenialCause,
+}
+
+impl MissingCapability {
+    /// Replace an `EPERM` error by a [`MissingCapability`] error
+    fn check(
+        error: io::Error,
+        capability: AuditCapability,
+        cause: DenialCause,
+        operation: &str,
+    ) -> io::Error {
+        if error.raw_os_error() != Some(libc::EPERM) {
+            return error;
+        }
+        io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            MissingCapability {
+                capability,
+                operation: operation.to_string(),
+                cause,
+            },
+        )
+    }
+}
+
+impl fmt::Display for MissingCapability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.cause {
+            DenialCause::Capability => write!(
+                f,
+                "{} was denied: it requires the {} capability, and a \
+                process in the initial user and pid namespaces",
+                self.operation, self.capability
+            ),
+            DenialCause::Locked => write!(
+                f,
+                "{} was denied: the audit configuration is locked until \
+                the next reboot",
+                self.operation
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MissingCapability {}
+
+fn unexpected_reply(msg: &AuditMessage) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("unexpected reply of type {}", msg.message_type()),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_health() {
+        let status = StatusMessage {
+            enabled: AUDIT_LOCKED,
+            backlog: 2048,
+            backlog_limit: 8192,
+            lost: 3,
+            ..Default::default()
+        };
+        let health = AuditHealth::new(status, Some(FeaturesMessage::new()));
+        assert!(health.is_enabled());
+        assert!(health.is_locked());
+        assert!(!health.has_daemon());
+        assert_eq!(health.backlog_usage(), Some(0.25));
+        assert_eq!(health.lost(), 3);
+        assert_eq!(health.features_version(), Some(1));
+
+        let health = AuditHealth::new(StatusMessage::new(), None);
+        assert!(!health.is_enabled());
+        assert_eq!(health.backlog_usage(), None);
+        assert_eq!(health.features_version(), None);
+    }
+
+    #[test]
+    fn test_missing_capability() {
+        let err = MissingCapability::check(
+            io::Error::from_raw_os_error(libc::EPERM),
+            AuditCapability::AuditControl,
+            DenialCause::Capability,
+            "ADD_RULE request",
+        );
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        assert_eq!(
+            err.to_string(),
+            "ADD_RULE request was denied: it requires the CAP_AUDIT_CONTROL \
+            capability, and a process in the initial user and pid namespaces"
+        );
+        let err = MissingCapability::check(
+            io::Error::from_raw_os_error(libc::EPERM),
+            AuditCapability::AuditControl,
+            DenialCause::Locked,
+            "ADD_RULE request",
+        );
+        let missing = err
+            .get_ref()
+            .and_then(|e| e.downcast_ref::<MissingCapability>())
+            .unwrap();
+        assert_eq!(missing.cause, DenialCause::Locked);
+        assert_eq!(
+            err.to_string(),
+            "ADD_RULE request was denied: the audit configuration is locked \
+            until the next reboot"
+        );
+        let err = MissingCapability::check(
+            io::Error::from_raw_os_error(libc::EINVAL),
+            AuditCapability::AuditRead,
+            DenialCause::Capability,
+            "joining the audit multicast group",
+        );
+        assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+    }
+}
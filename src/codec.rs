@@ -1,10 +1,29 @@
 // SPDX-License-Identifier: MIT
 
-use std::{fmt::Debug, io};
+use std::{
+    fmt::Debug,
+    io,
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
 
+use byteorder::{ByteOrder, NativeEndian};
 use bytes::BytesMut;
 use netlink_packet_core::{
-    NetlinkBuffer, NetlinkDeserializable, NetlinkMessage, NetlinkSerializable,
+    DoneMessage, NetlinkBuffer, NetlinkDeserializable, NetlinkHeader,
+    NetlinkMessage, NetlinkPayload, NetlinkSerializable, NETLINK_HEADER_LEN,
+    NLMSG_DONE, NLMSG_ERROR,
+};
+use netlink_packet_utils::traits::Parseable;
+use netlink_packet_utils::DecodeError;
+
+#[cfg(doc)]
+use crate::AuditFramer;
+use crate::{
+    constants::*, rules::RULE_BUF_MIN_LEN, AuditFramerBuilder, Endianness,
 };
 pub(crate) use netlink_proto::{NetlinkCodec, NetlinkMessageCodec};
 
@@ -20,108 +39,680 @@ pub(crate) use netlink_proto::{NetlinkCodec, NetlinkMessageCodec};
 /// - https://github.com/torvalds/linux/blob/b5013d084e03e82ceeab4db8ae8ceeaebe76b0eb/kernel/audit.c#L2386
 /// - https://github.com/mozilla/libaudit-go/issues/24
 /// - https://github.com/linux-audit/audit-userspace/issues/78
+///
+/// The codec is not tied to [`AuditMessage`](crate::AuditMessage): its
+/// methods are generic over the message type, so the length repair logic
+/// can be reused with any [`NetlinkDeserializable`] type.
 #[non_exhaustive]
 pub struct NetlinkAuditCodec {
     // we don't need an instance of this, just the type
     _private: (),
 }
 
-impl NetlinkMessageCodec for NetlinkAuditCodec {
-    fn decode<T>(src: &mut BytesMut) -> io::Result<Option<NetlinkMessage<T>>>
-    where
-        T: NetlinkDeserializable + Debug,
-    {
-        debug!("NetlinkAuditCodec: decoding next message");
+/// Counters describing the health of the decoder
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct AuditCodecStats {
+    /// Number of messages successfully decoded
+    pub frames_decoded: u64,
+    /// Number of bytes consumed from the buffer, including the discarded
+    /// ones
+    pub bytes_processed: u64,
+    /// Number of frames whose length had to be fixed
+    pub length_corrections: u64,
+    /// Number of times the buffer had to be discarded because it did not
+    /// start with a valid netlink header
+    pub truncated_frames: u64,
+    /// Number of frames that could not be deserialized
+    pub decode_errors: u64,
+    /// Number of frames skipped because they exceeded the maximum frame
+    /// length
+    pub oversized_frames: u64,
+    /// Number of bytes rejected because they would have made the buffer
+    /// exceed its maximum length
+    pub rejected_bytes: u64,
+}
+
+impl AuditCodecStats {
+    const fn new() -> Self {
+        AuditCodecStats {
+            frames_decoded: 0,
+            bytes_processed: 0,
+            length_corrections: 0,
+            truncated_frames: 0,
+            decode_errors: 0,
+            oversized_frames: 0,
+            rejected_bytes: 0,
+        }
+    }
+
+    fn add(&mut self, other: &AuditCodecStats) {
+        self.frames_decoded += other.frames_decoded;
+        self.bytes_processed += other.bytes_processed;
+        self.length_corrections += other.length_corrections;
+        self.truncated_frames += other.truncated_frames;
+        self.decode_errors += other.decode_errors;
+        self.oversized_frames += other.oversized_frames;
+        self.rejected_bytes += other.rejected_bytes;
+    }
+}
+
+/// Statistics of the [`NetlinkAuditCodec`]. The codec has no state of its
+/// own, since `netlink-proto` calls its methods without an instance, so
+/// they are shared by all the connections of the process.
+static CODEC_STATS: Mutex<AuditCodecStats> = Mutex::new(AuditCodecStats::new());
+/// Whether [`CODEC_STATS`] are collected. When they are not, decoding does
+/// not take the lock.
+static CODEC_STATS_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Hooks called by the decoder, to record custom metrics or sample frames.
+/// All the methods do nothing by default.
+pub trait DecodeObserver {
+    /// Called for each frame split from the buffer, before it is
+    /// deserialized. `frame` includes the header, whose length is the
+    /// corrected one.
+    fn on_frame(&mut self, _frame: &[u8]) {}
+
+    /// Called when the length of a frame is corrected, see
+    /// [`NetlinkAuditCodec`]
+    fn on_length_fixup(&mut self, _original: u32, _corrected: u32) {}
+
+    /// Called when bytes are discarded, either because they do not form a
+    /// valid frame, or because the frame could not be deserialized
+    fn on_error(&mut self, _bytes: &[u8], _error: &DecodeError) {}
+}
+
+impl DecodeObserver for () {}
+
+/// How the decoder handles the `nlmsg_len` of the received frames
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Hash)]
+#[non_exhaustive]
+pub enum LengthPolicy {
+    /// Work around the kernel audit bugs described in
+    /// [`NetlinkAuditCodec`]
+    #[default]
+    Repair,
+    /// Trust the length of the frames
+    Strict,
+}
+
+/// Decoder settings
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub(crate) struct DecodeConfig {
+    /// Frames with a larger `nlmsg_len` are skipped
+    pub max_frame_len: Option<usize>,
+    pub length_policy: LengthPolicy,
+    /// Keep incomplete frames in the buffer until the rest of their bytes
+    /// is received, instead of discarding them
+    pub retain_partial_frames: bool,
+    /// Byte order of the frames, which are converted to the native byte
+    /// order when it differs
+    pub byte_order: Endianness,
+}
+
+/// Return `true` if `src` starts with a frame that is not complete yet
+pub(crate) fn is_partial_frame(src: &[u8], order: Endianness) -> bool {
+    src.len() < NETLINK_HEADER_LEN
+        || order.read_u32(&src[0..4]) as usize > src.len()
+}
+
+/// Convert a netlink header to the native byte order, and return its length
+/// and message type. Converting a header twice restores its original byte
+/// order.
+pub(crate) fn convert_header(
+    header: &mut [u8],
+    order: Endianness,
+) -> (u32, u16) {
+    let length = order.read_u32(&header[0..4]);
+    let message_type = order.read_u16(&header[4..6]);
+    let flags = order.read_u16(&header[6..8]);
+    let sequence_number = order.read_u32(&header[8..12]);
+    let port_number = order.read_u32(&header[12..16]);
+    NativeEndian::write_u32(&mut header[0..4], length);
+    NativeEndian::write_u16(&mut header[4..6], message_type);
+    NativeEndian::write_u16(&mut header[6..8], flags);
+    NativeEndian::write_u32(&mut header[8..12], sequence_number);
+    NativeEndian::write_u32(&mut header[12..16], port_number);
+    (length, message_type)
+}
 
-        loop {
-            // If there's nothing to read, return Ok(None)
-            if src.is_empty() {
-                trace!("buffer is empty");
-                return Ok(None);
+/// Convert the integer fields at the beginning of the payload of the control
+/// messages to the native byte order. Their strings, like the text of the
+/// events, do not depend on the byte order.
+pub(crate) fn convert_payload(
+    message_type: u16,
+    payload: &mut [u8],
+    order: Endianness,
+) {
+    let words = match message_type {
+        // all the fields of `audit_status` are integers
+        AUDIT_GET | AUDIT_SET => payload.len() / 4,
+        AUDIT_ADD_RULE | AUDIT_DEL_RULE | AUDIT_LIST_RULES => {
+            RULE_BUF_MIN_LEN / 4
+        }
+        AUDIT_GET_FEATURE | AUDIT_SET_FEATURE => 4,
+        AUDIT_SIGNAL_INFO | AUDIT_TTY_GET | AUDIT_TTY_SET
+        | AUDIT_MAKE_EQUIV => 2,
+        NLMSG_ERROR => {
+            // error code, followed by the header of the request
+            if payload.len() >= 4 + NETLINK_HEADER_LEN {
+                convert_header(&mut payload[4..4 + NETLINK_HEADER_LEN], order);
             }
+            1
+        }
+        _ => 0,
+    };
+    for word in payload.chunks_exact_mut(4).take(words) {
+        let value = order.read_u32(word);
+        NativeEndian::write_u32(word, value);
+    }
+}
+
+/// Split the next frame from `src`, applying the workarounds for the
+/// kernel audit length bugs described in [`NetlinkAuditCodec`]. The length
+/// in the header of the returned frame is the corrected one.
+///
+/// `src` is expected to hold complete datagrams. The buffer is cleared if
+/// no valid netlink header can be found.
+pub(crate) fn next_frame(
+    src: &mut BytesMut,
+    config: &DecodeConfig,
+    stats: &mut AuditCodecStats,
+    observer: &mut dyn DecodeObserver,
+) -> Option<BytesMut> {
+    loop {
+        // If there's nothing to read, return None
+        if src.is_empty() {
+            trace!("buffer is empty");
+            return None;
+        }
 
-            // This is a bit hacky because we don't want to keep `src`
-            // borrowed, since we need to mutate it later.
-            let src_len = src.len();
-            let len = match NetlinkBuffer::new_checked(src.as_mut()) {
-                Ok(mut buf) => {
-                    if (src_len as isize - buf.length() as isize) <= 16 {
-                        // The audit messages are sometimes truncated,
-                        // because the length specified in the header,
-                        // does not take the header itself into
-                        // account. To workaround this, we tweak the
-                        // length. We've noticed two occurences of
-                        // truncated packets:
-                        //
-                        // - the length of the header is not included (see also:
-                        //   https://github.com/mozilla/libaudit-go/issues/24)
-                        // - some rule message have some padding for alignment (see
-                        //   https://github.com/linux-audit/audit-userspace/issues/78) which is not
-                        //   taken into account in the buffer length.
-                        //
-                        // How do we know that's the right length? Due to an
-                        // implementation detail and to
-                        // the fact that netlink is a datagram protocol.
-                        //
-                        // - our implementation of Stream always calls the codec
-                        //   with at most 1 message in the buffer, so we know
-                        //   the extra bytes do not belong to another message.
-                        // - because netlink is a datagram protocol, we receive
-                        //   entire messages, so we know that if those extra
-                        //   bytes do not belong to another message, they belong
-                        //   to this one.
-                        warn!("found what looks like a truncated audit packet");
-                        // also write correct length to buffer so parsing does
-                        // not fail:
-                        warn!(
-                            "setting packet length to {} instead of {}",
-                            src_len,
-                            buf.length()
+        if config.retain_partial_frames
+            && is_partial_frame(src, config.byte_order)
+        {
+            trace!("waiting for the rest of a {} bytes frame", src.len());
+            return None;
+        }
+
+        let foreign = !config.byte_order.is_native();
+        if foreign && src.len() >= NETLINK_HEADER_LEN {
+            convert_header(&mut src[..NETLINK_HEADER_LEN], config.byte_order);
+        }
+
+        // This is a bit hacky because we don't want to keep `src`
+        // borrowed, since we need to mutate it later.
+        let src_len = src.len();
+        let len = match NetlinkBuffer::new_checked(src.as_mut()) {
+            Ok(buf) if config.length_policy == LengthPolicy::Strict => {
+                buf.length() as usize
+            }
+            Ok(mut buf) => {
+                if config.retain_partial_frames
+                    && src_len - buf.length() as usize <= 16
+                    && src_len != buf.length() as usize
+                {
+                    // The extra bytes may be the beginning of the next
+                    // frame, or belong to this one (see below). Only more
+                    // bytes or the end of the stream can tell.
+                    trace!("waiting for more bytes to delimit the frame");
+                    if foreign {
+                        // the header is converted again on the next call
+                        convert_header(
+                            &mut src[..NETLINK_HEADER_LEN],
+                            config.byte_order,
                         );
-                        buf.set_length(src_len as u32);
-                        src_len
-                    } else {
-                        buf.length() as usize
                     }
+                    return None;
                 }
-                Err(e) => {
-                    // We either received a truncated packet, or the
-                    // packet if malformed (invalid length field). In
-                    // both case, we can't decode the datagram, and we
-                    // cannot find the start of the next one (if
-                    // any). The only solution is to clear the buffer
-                    // and potentially lose some datagrams.
-                    error!(
-                        "failed to decode datagram, clearing buffer: {:?}: {:#x?}.",
-                        e,
-                        src.as_ref()
+                let len = buf.length() as usize;
+                if len == src_len || src_len as isize - len as isize > 16 {
+                    // nothing to fix: either the frame fills the buffer,
+                    // or other frames follow it
+                    len
+                } else {
+                    // The audit messages are sometimes truncated,
+                    // because the length specified in the header,
+                    // does not take the header itself into
+                    // account. To workaround this, we tweak the
+                    // length. We've noticed two occurences of
+                    // truncated packets:
+                    //
+                    // - the length of the header is not included (see also:
+                    //   https://github.com/mozilla/libaudit-go/issues/24)
+                    // - some rule message have some padding for alignment (see
+                    //   https://github.com/linux-audit/audit-userspace/issues/78) which is not
+                    //   taken into account in the buffer length.
+                    //
+                    // How do we know that's the right length? Due to an
+                    // implementation detail and to
+                    // the fact that netlink is a datagram protocol.
+                    //
+                    // - our implementation of Stream always calls the codec
+                    //   with at most 1 message in the buffer, so we know
+                    //   the extra bytes do not belong to another message.
+                    // - because netlink is a datagram protocol, we receive
+                    //   entire messages, so we know that if those extra
+                    //   bytes do not belong to another message, they belong
+                    //   to this one.
+                    warn!("found what looks like a truncated audit packet");
+                    // also write correct length to buffer so parsing does
+                    // not fail:
+                    warn!(
+                        "setting packet length to {} instead of {}",
+                        src_len, len
                     );
-                    src.clear();
-                    return Ok(None);
+                    stats.length_corrections += 1;
+                    observer.on_length_fixup(len as u32, src_len as u32);
+                    buf.set_length(src_len as u32);
+                    src_len
                 }
-            };
+            }
+            Err(e) => {
+                // We either received a truncated packet, or the
+                // packet if malformed (invalid length field). In
+                // both case, we can't decode the datagram, and we
+                // cannot find the start of the next one (if
+                // any). The only solution is to clear the buffer
+                // and potentially lose some datagrams.
+                error!(
+                    "failed to decode datagram, clearing buffer: {:?}: {:#x?}.",
+                    e,
+                    src.as_ref()
+                );
+                stats.truncated_frames += 1;
+                stats.bytes_processed += src.len() as u64;
+                observer.on_error(src, &e);
+                src.clear();
+                return None;
+            }
+        };
 
-            let bytes = src.split_to(len);
+        let mut bytes = src.split_to(len);
+        stats.bytes_processed += len as u64;
 
-            let parsed = NetlinkMessage::<T>::deserialize(&bytes);
-            match parsed {
-                Ok(packet) => {
-                    trace!("<<< {:?}", packet);
-                    return Ok(Some(packet));
-                }
-                Err(e) => {
-                    error!("failed to decode packet {:#x?}: {}", &bytes, e);
-                    // continue looping, there may be more datagrams in the
-                    // buffer
-                }
+        if let Some(max) = config.max_frame_len {
+            if len > max {
+                warn!("skipping {len} bytes frame (maximum is {max} bytes)");
+                stats.oversized_frames += 1;
+                continue;
             }
         }
+
+        if foreign {
+            let message_type = NativeEndian::read_u16(&bytes[4..6]);
+            convert_payload(
+                message_type,
+                &mut bytes[NETLINK_HEADER_LEN..],
+                config.byte_order,
+            );
+        }
+
+        observer.on_frame(&bytes);
+        return Some(bytes);
+    }
+}
+
+/// Deserialize a frame. The `NLMSG_DONE` message the kernel sends at the
+/// end of a rule dump has an empty payload instead of the error code
+/// `netlink-packet-core` expects, so it is handled here.
+pub(crate) fn deserialize_frame<T>(
+    bytes: &[u8],
+) -> Result<NetlinkMessage<T>, DecodeError>
+where
+    T: NetlinkDeserializable,
+{
+    let buf = NetlinkBuffer::new_checked(bytes)?;
+    if buf.message_type() == NLMSG_DONE && buf.payload().is_empty() {
+        let header = NetlinkHeader::parse(&buf)?;
+        return Ok(NetlinkMessage::new(
+            header,
+            NetlinkPayload::Done(DoneMessage::default()),
+        ));
+    }
+    NetlinkMessage::deserialize(bytes)
+}
+
+/// Decode the next message from `src` (see [`next_frame`]), returning the
+/// deserialization error if the next frame is invalid.
+pub(crate) fn try_decode_message<T>(
+    src: &mut BytesMut,
+    config: &DecodeConfig,
+    stats: &mut AuditCodecStats,
+    observer: &mut dyn DecodeObserver,
+) -> Option<Result<NetlinkMessage<T>, DecodeError>>
+where
+    T: NetlinkDeserializable + Debug,
+{
+    let bytes = next_frame(src, config, stats, observer)?;
+    match deserialize_frame::<T>(&bytes) {
+        Ok(packet) => {
+            trace!("<<< {:?}", packet);
+            stats.frames_decoded += 1;
+            Some(Ok(packet))
+        }
+        Err(e) => {
+            error!("failed to decode packet {:#x?}: {}", &bytes, e);
+            stats.decode_errors += 1;
+            observer.on_error(&bytes, &e);
+            Some(Err(e))
+        }
     }
+}
 
-    fn encode<T>(msg: NetlinkMessage<T>, buf: &mut BytesMut) -> io::Result<()>
+/// Decode the next message from `src` (see [`next_frame`]). Frames that fail
+/// to deserialize are skipped.
+pub(crate) fn decode_message<T>(
+    src: &mut BytesMut,
+    config: &DecodeConfig,
+    stats: &mut AuditCodecStats,
+    observer: &mut dyn DecodeObserver,
+) -> Option<NetlinkMessage<T>>
+where
+    T: NetlinkDeserializable + Debug,
+{
+    // continue looping on errors, there may be more datagrams in the buffer
+    while let Some(parsed) = try_decode_message(src, config, stats, observer) {
+        if let Ok(packet) = parsed {
+            return Some(packet);
+        }
+    }
+    None
+}
+
+impl NetlinkAuditCodec {
+    /// Start keeping track of the statistics of the messages decoded by
+    /// the codec, which can then be retrieved with
+    /// [`NetlinkAuditCodec::stats`].
+    ///
+    /// The codec is driven by `netlink-proto` without an instance, so
+    /// these counters are global: they sum up the messages decoded by all
+    /// the connections using the codec in this process, and updating them
+    /// takes a process-wide lock for each message. Use an
+    /// [`AuditFramer`](crate::AuditFramer) to get statistics for a single
+    /// socket.
+    pub fn enable_stats() {
+        CODEC_STATS_ENABLED.store(true, Ordering::Relaxed);
+    }
+
+    /// Stop keeping track of the statistics. The counters are kept until
+    /// [`NetlinkAuditCodec::reset_stats`] is called.
+    pub fn disable_stats() {
+        CODEC_STATS_ENABLED.store(false, Ordering::Relaxed);
+    }
+
+    /// Return the statistics of the messages decoded by the codec since
+    /// they were enabled, if they are
+    pub fn stats() -> Option<AuditCodecStats> {
+        if !CODEC_STATS_ENABLED.load(Ordering::Relaxed) {
+            return None;
+        }
+        let stats = CODEC_STATS
+            .lock()
+            .map(|stats| stats.clone())
+            .unwrap_or_else(|poisoned| poisoned.into_inner().clone());
+        Some(stats)
+    }
+
+    /// Reset the counters returned by [`NetlinkAuditCodec::stats`]
+    pub fn reset_stats() {
+        let mut stats = CODEC_STATS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *stats = AuditCodecStats::new();
+    }
+
+    /// Return a builder for a configurable decoder. Since the codec itself
+    /// is stateless, the resulting decoder is an
+    /// [`AuditFramer`](crate::AuditFramer).
+    pub fn builder() -> AuditFramerBuilder {
+        AuditFramerBuilder::new()
+    }
+
+    /// Decode all the messages in `src`, typically a whole datagram read
+    /// from an audit socket. The returned iterator consumes `src` as it
+    /// goes, and yields an error for each frame that fails to deserialize.
+    pub fn decode_all<T>(src: &mut BytesMut) -> DecodeAll<'_, T>
+    where
+        T: NetlinkDeserializable + Debug,
+    {
+        DecodeAll {
+            src,
+            stats: AuditCodecStats::default(),
+            _message: PhantomData,
+        }
+    }
+}
+
+/// Iterator returned by [`NetlinkAuditCodec::decode_all`]
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct DecodeAll<'a, T> {
+    src: &'a mut BytesMut,
+    stats: AuditCodecStats,
+    _message: PhantomData<T>,
+}
+
+impl<T> DecodeAll<'_, T> {
+    /// Statistics about the messages decoded so far
+    pub fn stats(&self) -> &AuditCodecStats {
+        &self.stats
+    }
+}
+
+impl<T> Iterator for DecodeAll<'_, T>
+where
+    T: NetlinkDeserializable + Debug,
+{
+    type Item = Result<NetlinkMessage<T>, DecodeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        try_decode_message(
+            self.src,
+            &DecodeConfig::default(),
+            &mut self.stats,
+            &mut (),
+        )
+    }
+}
+
+impl NetlinkMessageCodec for NetlinkAuditCodec {
+    fn decode<T>(src: &mut BytesMut) -> io::Result<Option<NetlinkMessage<T>>>
+    where
+        T: NetlinkDeserializable + Debug,
+    {
+        debug!("NetlinkAuditCodec: decoding next message");
+        let mut stats = AuditCodecStats::new();
+        let message =
+            decode_message(src, &DecodeConfig::default(), &mut stats, &mut ());
+        if CODEC_STATS_ENABLED.load(Ordering::Relaxed) {
+            CODEC_STATS
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .add(&stats);
+        }
+        Ok(message)
+    }
+
+    fn encode<T>(
+        mut msg: NetlinkMessage<T>,
+        buf: &mut BytesMut,
+    ) -> io::Result<()>
     where
         T: Debug + NetlinkSerializable,
     {
+        // Make sure the header length and message type match the payload,
+        // so that callers don't have to remember calling `finalize()`.
+        msg.finalize();
         NetlinkCodec::encode(msg, buf)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use netlink_packet_core::{NetlinkHeader, NetlinkPayload, NLM_F_REQUEST};
+
+    use netlink_packet_utils::traits::Emitable;
+
+    use super::*;
+    use crate::{
+        feature_to_mask, AuditMessage, FeaturesMessage, MakeEquivMessage,
+        SignalInfoMessage, StatusMessage, TtyStatusMessage,
+    };
+
+    /// Held by the tests that decode with the codec, so that the global
+    /// statistics only count the messages of the test that enables them
+    static DECODE_LOCK: Mutex<()> = Mutex::new(());
+
+    fn decode_lock() -> std::sync::MutexGuard<'static, ()> {
+        DECODE_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct RawMessage(u16, Vec<u8>);
+
+    impl NetlinkDeserializable for RawMessage {
+        type Error = netlink_packet_utils::DecodeError;
+        fn deserialize(
+            header: &NetlinkHeader,
+            payload: &[u8],
+        ) -> Result<Self, Self::Error> {
+            Ok(RawMessage(header.message_type, payload.to_vec()))
+        }
+    }
+
+    #[test]
+    fn test_decode_custom_type() {
+        // header whose length does not include the header itself
+        let mut buf = BytesMut::from(
+            &[
+                0x14, 0x00, 0x00, 0x00, // length
+                0x14, 0x05, // message type
+                0x00, 0x00, // flags
+                0x00, 0x00, 0x00, 0x00, // sequence number
+                0x00, 0x00, 0x00, 0x00, // port number
+                // payload
+                0x61, 0x62, 0x63, 0x64, 0x61, 0x62, 0x63, 0x64, 0x61, 0x62,
+                0x63, 0x64, 0x61, 0x62, 0x63, 0x64, 0x61, 0x62, 0x63, 0x64,
+            ][..],
+        );
+        let _lock = decode_lock();
+        let msg = NetlinkAuditCodec::decode::<RawMessage>(&mut buf)
+            .unwrap()
+            .unwrap();
+        assert_eq!(msg.header.length, 36);
+        assert_eq!(
+            msg.payload,
+            NetlinkPayload::InnerMessage(RawMessage(
+                AUDIT_SYSCALL,
+                b"abcd".repeat(5)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let mut status = StatusMessage::new();
+        status.enabled = 1;
+        status.mask = AUDIT_STATUS_ENABLED;
+        let mut header = NetlinkHeader::default();
+        header.flags = NLM_F_REQUEST;
+        header.sequence_number = 42;
+        let msg = NetlinkMessage::new(
+            header,
+            NetlinkPayload::from(AuditMessage::SetStatus(status)),
+        );
+
+        let mut buf = BytesMut::new();
+        for _ in 0..3 {
+            NetlinkAuditCodec::encode(msg.clone(), &mut buf).unwrap();
+        }
+        assert_eq!(buf.len(), 3 * msg.buffer_len());
+
+        let _lock = decode_lock();
+        // the statistics are not collected by default
+        assert_eq!(NetlinkAuditCodec::stats(), None);
+        NetlinkAuditCodec::decode::<AuditMessage>(&mut buf).unwrap();
+        NetlinkAuditCodec::enable_stats();
+        NetlinkAuditCodec::reset_stats();
+        for _ in 0..2 {
+            let decoded = NetlinkAuditCodec::decode::<AuditMessage>(&mut buf)
+                .unwrap()
+                .unwrap();
+            assert_eq!(decoded.header.length as usize, msg.buffer_len());
+            assert_eq!(decoded.header.message_type, AUDIT_SET);
+            assert_eq!(decoded.header.sequence_number, 42);
+            assert_eq!(decoded.payload, msg.payload);
+        }
+        assert!(buf.is_empty());
+        let stats = NetlinkAuditCodec::stats().unwrap();
+        NetlinkAuditCodec::disable_stats();
+        assert_eq!(stats.frames_decoded, 2);
+        assert_eq!(stats.bytes_processed, 2 * msg.buffer_len() as u64);
+        assert_eq!(NetlinkAuditCodec::stats(), None);
+    }
+
+    #[test]
+    fn test_decode_all() {
+        let mut header = NetlinkHeader::default();
+        header.sequence_number = 1;
+        let msg = NetlinkMessage::new(
+            header,
+            NetlinkPayload::from(AuditMessage::GetStatus(None)),
+        );
+        let mut buf = BytesMut::new();
+        NetlinkAuditCodec::encode(msg.clone(), &mut buf).unwrap();
+        NetlinkAuditCodec::encode(msg.clone(), &mut buf).unwrap();
+        // a status reply that is too short to be valid
+        let mut invalid = NetlinkHeader::default();
+        invalid.length = 20;
+        invalid.message_type = AUDIT_GET;
+        let mut bytes = vec![0; 20];
+        invalid.emit(&mut bytes);
+        buf.extend_from_slice(&bytes);
+
+        let mut messages =
+            NetlinkAuditCodec::decode_all::<AuditMessage>(&mut buf);
+        assert_eq!(messages.next().unwrap().unwrap().payload, msg.payload);
+        assert_eq!(messages.next().unwrap().unwrap().payload, msg.payload);
+        assert!(messages.next().unwrap().is_err());
+        assert!(messages.next().is_none());
+        assert_eq!(messages.stats().frames_decoded, 2);
+        assert_eq!(messages.stats().decode_errors, 1);
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_control_messages() {
+        let mut features = FeaturesMessage::new();
+        features.mask = feature_to_mask(AUDIT_FEATURE_LOGINUID_IMMUTABLE);
+        let messages = vec![
+            AuditMessage::GetFeature(None),
+            AuditMessage::GetFeature(Some(features.clone())),
+            AuditMessage::SetFeature(features),
+            AuditMessage::SignalInfo(None),
+            AuditMessage::SignalInfo(Some(SignalInfoMessage::default())),
+            AuditMessage::TtyGet(None),
+            AuditMessage::TtySet(TtyStatusMessage::new(true, true)),
+            AuditMessage::Trim,
+            AuditMessage::MakeEquiv(MakeEquivMessage::new(
+                "/etc".into(),
+                "/mnt/etc".into(),
+            )),
+        ];
+        let _lock = decode_lock();
+        for message in messages {
+            let msg = NetlinkMessage::new(
+                NetlinkHeader::default(),
+                NetlinkPayload::from(message),
+            );
+            let mut buf = BytesMut::new();
+            NetlinkAuditCodec::encode(msg.clone(), &mut buf).unwrap();
+            let decoded = NetlinkAuditCodec::decode::<AuditMessage>(&mut buf)
+                .unwrap()
+                .unwrap();
+            assert_eq!(decoded.payload, msg.payload);
+        }
+    }
+}
@@ -0,0 +1,141 @@
+// SPDX-License-Identifier: MIT
+
+//! Architectures, as found in the `arch` field of the `SYSCALL` records
+//! and of the audit rules. The typed equivalent is [`AuditArch`].
+
+pub const __AUDIT_ARCH_CONVENTION_MASK: u32 = 0x3000_0000;
+pub const __AUDIT_ARCH_CONVENTION_MIPS64_N32: u32 = 0x2000_0000;
+pub const __AUDIT_ARCH_64BIT: u32 = 0x8000_0000;
+pub const __AUDIT_ARCH_LE: u32 = 0x4000_0000;
+pub const AUDIT_ARCH_AARCH64: u32 = 0xC000_00B7;
+pub const AUDIT_ARCH_ALPHA: u32 = 0xC000_9026;
+pub const AUDIT_ARCH_ARCOMPACT: u32 = 0x4000_005D;
+pub const AUDIT_ARCH_ARCOMPACTBE: u32 = 0x5D;
+pub const AUDIT_ARCH_ARCV2: u32 = 0x4000_00C3;
+pub const AUDIT_ARCH_ARCV2BE: u32 = 0xC3;
+pub const AUDIT_ARCH_ARM: u32 = 0x4000_0028;
+pub const AUDIT_ARCH_ARMEB: u32 = 0x28;
+pub const AUDIT_ARCH_C6X: u32 = 0x4000_008C;
+pub const AUDIT_ARCH_C6XBE: u32 = 0x8C;
+pub const AUDIT_ARCH_CRIS: u32 = 0x4000_004C;
+pub const AUDIT_ARCH_CSKY: u32 = 0x4000_00FC;
+pub const AUDIT_ARCH_FRV: u32 = 0x5441;
+pub const AUDIT_ARCH_H8300: u32 = 0x2E;
+pub const AUDIT_ARCH_HEXAGON: u32 = 0xA4;
+pub const AUDIT_ARCH_I386: u32 = 0x4000_0003;
+pub const AUDIT_ARCH_IA64: u32 = 0xC000_0032;
+pub const AUDIT_ARCH_LOONGARCH32: u32 = 0x4000_0102;
+pub const AUDIT_ARCH_LOONGARCH64: u32 = 0xC000_0102;
+pub const AUDIT_ARCH_M32R: u32 = 0x58;
+pub const AUDIT_ARCH_M68K: u32 = 0x04;
+pub const AUDIT_ARCH_MICROBLAZE: u32 = 0xBD;
+pub const AUDIT_ARCH_MIPS: u32 = 0x08;
+pub const AUDIT_ARCH_MIPSEL: u32 = 0x4000_0008;
+pub const AUDIT_ARCH_MIPS64: u32 = 0x8000_0008;
+pub const AUDIT_ARCH_MIPS64N32: u32 = 0xA000_0008;
+pub const AUDIT_ARCH_MIPSEL64: u32 = 0xC000_0008;
+pub const AUDIT_ARCH_MIPSEL64N32: u32 = 0xE000_0008;
+pub const AUDIT_ARCH_NDS32: u32 = 0x4000_00A7;
+pub const AUDIT_ARCH_NDS32BE: u32 = 0xA7;
+pub const AUDIT_ARCH_NIOS2: u32 = 0x4000_0071;
+pub const AUDIT_ARCH_OPENRISC: u32 = 92;
+pub const AUDIT_ARCH_PARISC: u32 = 15;
+pub const AUDIT_ARCH_PARISC64: u32 = 0x8000_000F;
+pub const AUDIT_ARCH_PPC: u32 = 20;
+pub const AUDIT_ARCH_PPC64: u32 = 0x8000_0015;
+pub const AUDIT_ARCH_PPC64LE: u32 = 0xC000_0015;
+pub const AUDIT_ARCH_RISCV32: u32 = 0x4000_00F3;
+pub const AUDIT_ARCH_RISCV64: u32 = 0xC000_00F3;
+pub const AUDIT_ARCH_S390: u32 = 22;
+pub const AUDIT_ARCH_S390X: u32 = 0x8000_0016;
+pub const AUDIT_ARCH_SH: u32 = 42;
+pub const AUDIT_ARCH_SHEL: u32 = 0x4000_002A;
+pub const AUDIT_ARCH_SH64: u32 = 0x8000_002A;
+pub const AUDIT_ARCH_SHEL64: u32 = 0xC000_002A;
+pub const AUDIT_ARCH_SPARC: u32 = 2;
+pub const AUDIT_ARCH_SPARC64: u32 = 0x8000_002B;
+pub const AUDIT_ARCH_TILEGX: u32 = 0xC000_00BF;
+pub const AUDIT_ARCH_TILEGX32: u32 = 0x4000_00BF;
+pub const AUDIT_ARCH_TILEPRO: u32 = 0x4000_00BC;
+pub const AUDIT_ARCH_UNICORE: u32 = 0x4000_006E;
+pub const AUDIT_ARCH_X86_64: u32 = 0xC000_003E;
+pub const AUDIT_ARCH_XTENSA: u32 = 0x5E;
+
+raw_enum! {
+    /// Architecture of a syscall (`AUDIT_ARCH_*` constants)
+    pub enum AuditArch: u32 {
+        Aarch64 = AUDIT_ARCH_AARCH64,
+        Alpha = AUDIT_ARCH_ALPHA,
+        Arcompact = AUDIT_ARCH_ARCOMPACT,
+        Arcompactbe = AUDIT_ARCH_ARCOMPACTBE,
+        Arcv2 = AUDIT_ARCH_ARCV2,
+        Arcv2be = AUDIT_ARCH_ARCV2BE,
+        Arm = AUDIT_ARCH_ARM,
+        Armeb = AUDIT_ARCH_ARMEB,
+        C6x = AUDIT_ARCH_C6X,
+        C6xbe = AUDIT_ARCH_C6XBE,
+        Cris = AUDIT_ARCH_CRIS,
+        Csky = AUDIT_ARCH_CSKY,
+        Frv = AUDIT_ARCH_FRV,
+        H8300 = AUDIT_ARCH_H8300,
+        Hexagon = AUDIT_ARCH_HEXAGON,
+        I386 = AUDIT_ARCH_I386,
+        Ia64 = AUDIT_ARCH_IA64,
+        Loongarch32 = AUDIT_ARCH_LOONGARCH32,
+        Loongarch64 = AUDIT_ARCH_LOONGARCH64,
+        M32r = AUDIT_ARCH_M32R,
+        M68k = AUDIT_ARCH_M68K,
+        Microblaze = AUDIT_ARCH_MICROBLAZE,
+        Mips = AUDIT_ARCH_MIPS,
+        Mipsel = AUDIT_ARCH_MIPSEL,
+        Mips64 = AUDIT_ARCH_MIPS64,
+        Mips64n32 = AUDIT_ARCH_MIPS64N32,
+        Mipsel64 = AUDIT_ARCH_MIPSEL64,
+        Mipsel64n32 = AUDIT_ARCH_MIPSEL64N32,
+        Nds32 = AUDIT_ARCH_NDS32,
+        Nds32be = AUDIT_ARCH_NDS32BE,
+        Nios2 = AUDIT_ARCH_NIOS2,
+        Openrisc = AUDIT_ARCH_OPENRISC,
+        Parisc = AUDIT_ARCH_PARISC,
+        Parisc64 = AUDIT_ARCH_PARISC64,
+        Ppc = AUDIT_ARCH_PPC,
+        Ppc64 = AUDIT_ARCH_PPC64,
+        Ppc64le = AUDIT_ARCH_PPC64LE,
+        Riscv32 = AUDIT_ARCH_RISCV32,
+        Riscv64 = AUDIT_ARCH_RISCV64,
+        S390 = AUDIT_ARCH_S390,
+        S390x = AUDIT_ARCH_S390X,
+        Sh = AUDIT_ARCH_SH,
+        Shel = AUDIT_ARCH_SHEL,
+        Sh64 = AUDIT_ARCH_SH64,
+        Shel64 = AUDIT_ARCH_SHEL64,
+        Sparc = AUDIT_ARCH_SPARC,
+        Sparc64 = AUDIT_ARCH_SPARC64,
+        Tilegx = AUDIT_ARCH_TILEGX,
+        Tilegx32 = AUDIT_ARCH_TILEGX32,
+        Tilepro = AUDIT_ARCH_TILEPRO,
+        Unicore = AUDIT_ARCH_UNICORE,
+        X86_64 = AUDIT_ARCH_X86_64,
+        Xtensa = AUDIT_ARCH_XTENSA,
+    }
+}
+
+impl AuditArch {
+    /// Return `true` for 64 bits architectures. This relies on the
+    /// `__AUDIT_ARCH_64BIT` bit, so it also works for unknown
+    /// architectures.
+    pub fn is_64bit(self) -> bool {
+        self.into_raw() & __AUDIT_ARCH_64BIT != 0
+    }
+
+    /// Return `true` for little endian architectures. This relies on the
+    /// `__AUDIT_ARCH_LE` bit, so it also works for unknown architectures.
+    pub fn is_little_endian(self) -> bool {
+        self.into_raw() & __AUDIT_ARCH_LE != 0
+    }
+
+    /// Return the ELF machine (`EM_*`) of the architecture
+    pub fn machine(self) -> u16 {
+        (self.into_raw() & 0xffff) as u16
+    }
+}
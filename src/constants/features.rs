@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: MIT
+
+//! Kernel features, reported in the status `feature_bitmap` and through
+//! `AUDIT_GET_FEATURE`. The typed equivalents are [`FeatureBitmap`] and
+//! [`AuditFeature`].
+
+use super::*;
+
+bit_flags! {
+    /// `feature_bitmap` field of the status message: the features supported
+    /// by the kernel (`AUDIT_FEATURE_BITMAP_*` constants)
+    pub struct FeatureBitmap: u32 {
+        const BACKLOG_LIMIT = AUDIT_FEATURE_BITMAP_BACKLOG_LIMIT;
+        const BACKLOG_WAIT_TIME = AUDIT_FEATURE_BITMAP_BACKLOG_WAIT_TIME;
+        const EXECUTABLE_PATH = AUDIT_FEATURE_BITMAP_EXECUTABLE_PATH;
+        const EXCLUDE_EXTEND = AUDIT_FEATURE_BITMAP_EXCLUDE_EXTEND;
+        const SESSIONID_FILTER = AUDIT_FEATURE_BITMAP_SESSIONID_FILTER;
+        const LOST_RESET = AUDIT_FEATURE_BITMAP_LOST_RESET;
+        const FILTER_FS = AUDIT_FEATURE_BITMAP_FILTER_FS;
+    }
+}
+
+raw_enum! {
+    /// Feature that can be toggled with `AUDIT_SET_FEATURE`
+    /// (`AUDIT_FEATURE_*` constants)
+    pub enum AuditFeature: u32 {
+        OnlyUnsetLoginuid = AUDIT_FEATURE_ONLY_UNSET_LOGINUID,
+        LoginuidImmutable = AUDIT_FEATURE_LOGINUID_IMMUTABLE,
+    }
+}
+
+// ============================================
+// kernel audit features (audit_status.feature_bitmap)
+// ============================================
+pub const AUDIT_FEATURE_BITMAP_BACKLOG_LIMIT: u32 = 0x0000_0001;
+pub const AUDIT_FEATURE_BITMAP_BACKLOG_WAIT_TIME: u32 = 0x0000_0002;
+pub const AUDIT_FEATURE_BITMAP_EXECUTABLE_PATH: u32 = 0x0000_0004;
+pub const AUDIT_FEATURE_BITMAP_EXCLUDE_EXTEND: u32 = 0x0000_0008;
+pub const AUDIT_FEATURE_BITMAP_SESSIONID_FILTER: u32 = 0x0000_0010;
+pub const AUDIT_FEATURE_BITMAP_LOST_RESET: u32 = 0x0000_0020;
+pub const AUDIT_FEATURE_BITMAP_FILTER_FS: u32 = 0x0000_0040;
+pub const AUDIT_FEATURE_BITMAP_ALL: u32 = AUDIT_FEATURE_BITMAP_BACKLOG_LIMIT
+    | AUDIT_FEATURE_BITMAP_BACKLOG_WAIT_TIME
+    | AUDIT_FEATURE_BITMAP_EXECUTABLE_PATH
+    | AUDIT_FEATURE_BITMAP_EXCLUDE_EXTEND
+    | AUDIT_FEATURE_BITMAP_SESSIONID_FILTER
+    | AUDIT_FEATURE_BITMAP_LOST_RESET
+    | AUDIT_FEATURE_BITMAP_FILTER_FS;
+
+pub const AUDIT_FEATURE_VERSION: u32 = 1;
+pub const AUDIT_FEATURE_ONLY_UNSET_LOGINUID: u32 = 0;
+pub const AUDIT_FEATURE_LOGINUID_IMMUTABLE: u32 = 1;
+pub const AUDIT_LAST_FEATURE: u32 = 1;
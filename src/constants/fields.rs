@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: MIT
+
+//! Rule field types, and the values of the `AUDIT_FIELD_COMPARE` field.
+//! The typed equivalents are [`RuleFieldType`] and [`FieldCompare`].
+
+raw_enum! {
+    /// Type of a rule field, without its value (`AUDIT_*` field constants)
+    pub enum RuleFieldType: u32 {
+        Pid = AUDIT_PID,
+        Uid = AUDIT_UID,
+        Euid = AUDIT_EUID,
+        Suid = AUDIT_SUID,
+        Fsuid = AUDIT_FSUID,
+        Gid = AUDIT_GID,
+        Egid = AUDIT_EGID,
+        Sgid = AUDIT_SGID,
+        Fsgid = AUDIT_FSGID,
+        Loginuid = AUDIT_LOGINUID,
+        Pers = AUDIT_PERS,
+        Arch = AUDIT_ARCH,
+        Msgtype = AUDIT_MSGTYPE,
+        SubjUser = AUDIT_SUBJ_USER,
+        SubjRole = AUDIT_SUBJ_ROLE,
+        SubjType = AUDIT_SUBJ_TYPE,
+        SubjSen = AUDIT_SUBJ_SEN,
+        SubjClr = AUDIT_SUBJ_CLR,
+        Ppid = AUDIT_PPID,
+        ObjUser = AUDIT_OBJ_USER,
+        ObjRole = AUDIT_OBJ_ROLE,
+        ObjType = AUDIT_OBJ_TYPE,
+        ObjLevLow = AUDIT_OBJ_LEV_LOW,
+        ObjLevHigh = AUDIT_OBJ_LEV_HIGH,
+        LoginuidSet = AUDIT_LOGINUID_SET,
+        Sessionid = AUDIT_SESSIONID,
+        Fstype = AUDIT_FSTYPE,
+        Devmajor = AUDIT_DEVMAJOR,
+        Devminor = AUDIT_DEVMINOR,
+        Inode = AUDIT_INODE,
+        Exit = AUDIT_EXIT,
+        Success = AUDIT_SUCCESS,
+        Watch = AUDIT_WATCH,
+        Perm = AUDIT_PERM,
+        Dir = AUDIT_DIR,
+        Filetype = AUDIT_FILETYPE,
+        ObjUid = AUDIT_OBJ_UID,
+        ObjGid = AUDIT_OBJ_GID,
+        FieldCompare = AUDIT_FIELD_COMPARE,
+        Exe = AUDIT_EXE,
+        Arg0 = AUDIT_ARG0,
+        Arg1 = AUDIT_ARG1,
+        Arg2 = AUDIT_ARG2,
+        Arg3 = AUDIT_ARG3,
+        Filterkey = AUDIT_FILTERKEY,
+    }
+}
+
+raw_enum! {
+    /// Value of the `AUDIT_FIELD_COMPARE` rule field: the two fields to
+    /// compare (`AUDIT_COMPARE_*` constants)
+    pub enum FieldCompare: u32 {
+        UidToObjUid = AUDIT_COMPARE_UID_TO_OBJ_UID,
+        GidToObjGid = AUDIT_COMPARE_GID_TO_OBJ_GID,
+        EuidToObjUid = AUDIT_COMPARE_EUID_TO_OBJ_UID,
+        EgidToObjGid = AUDIT_COMPARE_EGID_TO_OBJ_GID,
+        AuidToObjUid = AUDIT_COMPARE_AUID_TO_OBJ_UID,
+        SuidToObjUid = AUDIT_COMPARE_SUID_TO_OBJ_UID,
+        SgidToObjGid = AUDIT_COMPARE_SGID_TO_OBJ_GID,
+        FsuidToObjUid = AUDIT_COMPARE_FSUID_TO_OBJ_UID,
+        FsgidToObjGid = AUDIT_COMPARE_FSGID_TO_OBJ_GID,
+        UidToAuid = AUDIT_COMPARE_UID_TO_AUID,
+        UidToEuid = AUDIT_COMPARE_UID_TO_EUID,
+        UidToFsuid = AUDIT_COMPARE_UID_TO_FSUID,
+        UidToSuid = AUDIT_COMPARE_UID_TO_SUID,
+        AuidToFsuid = AUDIT_COMPARE_AUID_TO_FSUID,
+        AuidToSuid = AUDIT_COMPARE_AUID_TO_SUID,
+        AuidToEuid = AUDIT_COMPARE_AUID_TO_EUID,
+        EuidToSuid = AUDIT_COMPARE_EUID_TO_SUID,
+        EuidToFsuid = AUDIT_COMPARE_EUID_TO_FSUID,
+        SuidToFsuid = AUDIT_COMPARE_SUID_TO_FSUID,
+        GidToEgid = AUDIT_COMPARE_GID_TO_EGID,
+        GidToFsgid = AUDIT_COMPARE_GID_TO_FSGID,
+        GidToSgid = AUDIT_COMPARE_GID_TO_SGID,
+        EgidToFsgid = AUDIT_COMPARE_EGID_TO_FSGID,
+        EgidToSgid = AUDIT_COMPARE_EGID_TO_SGID,
+        SgidToFsgid = AUDIT_COMPARE_SGID_TO_FSGID,
+    }
+}
+
+// =======================================================================
+// rule fields
+// =======================================================================
+pub const AUDIT_PID: u32 = 0;
+pub const AUDIT_UID: u32 = 1;
+pub const AUDIT_EUID: u32 = 2;
+pub const AUDIT_SUID: u32 = 3;
+pub const AUDIT_FSUID: u32 = 4;
+pub const AUDIT_GID: u32 = 5;
+pub const AUDIT_EGID: u32 = 6;
+pub const AUDIT_SGID: u32 = 7;
+pub const AUDIT_FSGID: u32 = 8;
+pub const AUDIT_LOGINUID: u32 = 9;
+pub const AUDIT_PERS: u32 = 10;
+pub const AUDIT_ARCH: u32 = 11;
+pub const AUDIT_MSGTYPE: u32 = 12;
+pub const AUDIT_SUBJ_USER: u32 = 13;
+pub const AUDIT_SUBJ_ROLE: u32 = 14;
+pub const AUDIT_SUBJ_TYPE: u32 = 15;
+pub const AUDIT_SUBJ_SEN: u32 = 16;
+pub const AUDIT_SUBJ_CLR: u32 = 17;
+pub const AUDIT_PPID: u32 = 18;
+pub const AUDIT_OBJ_USER: u32 = 19;
+pub const AUDIT_OBJ_ROLE: u32 = 20;
+pub const AUDIT_OBJ_TYPE: u32 = 21;
+pub const AUDIT_OBJ_LEV_LOW: u32 = 22;
+pub const AUDIT_OBJ_LEV_HIGH: u32 = 23;
+pub const AUDIT_LOGINUID_SET: u32 = 24;
+pub const AUDIT_SESSIONID: u32 = 25;
+pub const AUDIT_FSTYPE: u32 = 26;
+pub const AUDIT_DEVMAJOR: u32 = 100;
+pub const AUDIT_DEVMINOR: u32 = 101;
+pub const AUDIT_INODE: u32 = 102;
+pub const AUDIT_EXIT: u32 = 103;
+pub const AUDIT_SUCCESS: u32 = 104;
+pub const AUDIT_WATCH: u32 = 105;
+pub const AUDIT_PERM: u32 = 106;
+pub const AUDIT_DIR: u32 = 107;
+pub const AUDIT_FILETYPE: u32 = 108;
+pub const AUDIT_OBJ_UID: u32 = 109;
+pub const AUDIT_OBJ_GID: u32 = 110;
+pub const AUDIT_FIELD_COMPARE: u32 = 111;
+pub const AUDIT_EXE: u32 = 112;
+pub const AUDIT_ARG0: u32 = 200;
+pub const AUDIT_ARG1: u32 = 201;
+pub const AUDIT_ARG2: u32 = 202;
+pub const AUDIT_ARG3: u32 = 203;
+pub const AUDIT_FILTERKEY: u32 = 210;
+
+// Field Comparing Constants
+pub const AUDIT_COMPARE_UID_TO_OBJ_UID: u32 = 1;
+pub const AUDIT_COMPARE_GID_TO_OBJ_GID: u32 = 2;
+pub const AUDIT_COMPARE_EUID_TO_OBJ_UID: u32 = 3;
+pub const AUDIT_COMPARE_EGID_TO_OBJ_GID: u32 = 4;
+pub const AUDIT_COMPARE_AUID_TO_OBJ_UID: u32 = 5;
+pub const AUDIT_COMPARE_SUID_TO_OBJ_UID: u32 = 6;
+pub const AUDIT_COMPARE_SGID_TO_OBJ_GID: u32 = 7;
+pub const AUDIT_COMPARE_FSUID_TO_OBJ_UID: u32 = 8;
+pub const AUDIT_COMPARE_FSGID_TO_OBJ_GID: u32 = 9;
+pub const AUDIT_COMPARE_UID_TO_AUID: u32 = 10;
+pub const AUDIT_COMPARE_UID_TO_EUID: u32 = 11;
+pub const AUDIT_COMPARE_UID_TO_FSUID: u32 = 12;
+pub const AUDIT_COMPARE_UID_TO_SUID: u32 = 13;
+pub const AUDIT_COMPARE_AUID_TO_FSUID: u32 = 14;
+pub const AUDIT_COMPARE_AUID_TO_SUID: u32 = 15;
+pub const AUDIT_COMPARE_AUID_TO_EUID: u32 = 16;
+pub const AUDIT_COMPARE_EUID_TO_SUID: u32 = 17;
+pub const AUDIT_COMPARE_EUID_TO_FSUID: u32 = 18;
+pub const AUDIT_COMPARE_SUID_TO_FSUID: u32 = 19;
+pub const AUDIT_COMPARE_GID_TO_EGID: u32 = 20;
+pub const AUDIT_COMPARE_GID_TO_FSGID: u32 = 21;
+pub const AUDIT_COMPARE_GID_TO_SGID: u32 = 22;
+pub const AUDIT_COMPARE_EGID_TO_FSGID: u32 = 23;
+pub const AUDIT_COMPARE_EGID_TO_SGID: u32 = 24;
+pub const AUDIT_COMPARE_SGID_TO_FSGID: u32 = 25;
+pub const AUDIT_MAX_FIELD_COMPARE: u32 = 25;
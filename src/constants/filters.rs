@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: MIT
+
+//! Filter lists and actions of the audit rules. The typed equivalents are
+//! [`RuleFlags`] and [`RuleAction`].
+
+pub use crate::rules::{RuleAction, RuleFlags};
+
+// rule flags
+
+/// Apply rule to user-generated messages
+pub const AUDIT_FILTER_USER: u32 = 0;
+/// Apply rule at task creation (not syscall)
+pub const AUDIT_FILTER_TASK: u32 = 1;
+/// Apply rule at syscall entry
+pub const AUDIT_FILTER_ENTRY: u32 = 2;
+/// Apply rule to file system watches
+pub const AUDIT_FILTER_WATCH: u32 = 3;
+/// Apply rule at syscall exit
+pub const AUDIT_FILTER_EXIT: u32 = 4;
+/// Apply rule at audit_log_start
+pub const AUDIT_FILTER_TYPE: u32 = 5;
+/// Apply rule at `audit_log_start`. Alias of `AUDIT_FILTER_TYPE`.
+pub const AUDIT_FILTER_EXCLUDE: u32 = AUDIT_FILTER_TYPE;
+/// Apply rule at `__audit_inode_child`
+pub const AUDIT_FILTER_FS: u32 = 6;
+/// Apply rule at io_uring op exit (Linux 5.16+)
+pub const AUDIT_FILTER_URING_EXIT: u32 = 7;
+
+/// Mask to get actual filter
+pub const AUDIT_NR_FILTERS: u32 = 8;
+pub const AUDIT_FILTER_PREPEND: u32 = 16;
+/// Filter is unset
+pub const AUDIT_FILTER_UNSET: u32 = 128;
+
+// Rule actions
+
+/// Do not build context if rule matches
+pub const AUDIT_NEVER: u32 = 0;
+/// Build context if rule matches
+pub const AUDIT_POSSIBLE: u32 = 1;
+/// Generate audit record if rule matches
+pub const AUDIT_ALWAYS: u32 = 2;
@@ -0,0 +1,154 @@
+// SPDX-License-Identifier: MIT
+
+//! File system magic numbers (from `linux/magic.h`), used as the value of
+//! the `AUDIT_FSTYPE` rule field.
+//!
+//! `auditctl` only knows `debugfs` and `tracefs` by name, but the kernel
+//! accepts any magic number.
+
+pub const ADFS_SUPER_MAGIC: u32 = 0xadf5;
+pub const AFFS_SUPER_MAGIC: u32 = 0xadff;
+pub const AFS_SUPER_MAGIC: u32 = 0x5346_414f;
+pub const ANON_INODE_FS_MAGIC: u32 = 0x0904_1934;
+pub const AUTOFS_SUPER_MAGIC: u32 = 0x0187;
+pub const BINFMTFS_MAGIC: u32 = 0x4249_4e4d;
+pub const BPF_FS_MAGIC: u32 = 0xcafe_4a11;
+pub const BTRFS_SUPER_MAGIC: u32 = 0x9123_683e;
+pub const CEPH_SUPER_MAGIC: u32 = 0x00c3_6400;
+pub const CGROUP_SUPER_MAGIC: u32 = 0x0027_e0eb;
+pub const CGROUP2_SUPER_MAGIC: u32 = 0x6367_7270;
+pub const CODA_SUPER_MAGIC: u32 = 0x7375_7245;
+pub const CONFIGFS_MAGIC: u32 = 0x6265_6570;
+pub const CRAMFS_MAGIC: u32 = 0x28cd_3d45;
+pub const DEBUGFS_MAGIC: u32 = 0x6462_6720;
+pub const DEVPTS_SUPER_MAGIC: u32 = 0x1cd1;
+pub const ECRYPTFS_SUPER_MAGIC: u32 = 0xf15f;
+pub const EFIVARFS_MAGIC: u32 = 0xde5e_81e4;
+pub const EROFS_SUPER_MAGIC_V1: u32 = 0xe0f5_e1e2;
+/// Shared by ext2, ext3 and ext4
+pub const EXT4_SUPER_MAGIC: u32 = 0xef53;
+pub const F2FS_SUPER_MAGIC: u32 = 0xf2f5_2010;
+pub const FUSE_SUPER_MAGIC: u32 = 0x6573_5546;
+pub const HOSTFS_SUPER_MAGIC: u32 = 0x00c0_ffee;
+pub const HPFS_SUPER_MAGIC: u32 = 0xf995_e849;
+pub const HUGETLBFS_MAGIC: u32 = 0x9584_58f6;
+pub const ISOFS_SUPER_MAGIC: u32 = 0x9660;
+pub const JFFS2_SUPER_MAGIC: u32 = 0x72b6;
+pub const MINIX_SUPER_MAGIC: u32 = 0x137f;
+pub const MQUEUE_MAGIC: u32 = 0x1980_0202;
+pub const MSDOS_SUPER_MAGIC: u32 = 0x4d44;
+pub const NFS_SUPER_MAGIC: u32 = 0x6969;
+pub const NILFS_SUPER_MAGIC: u32 = 0x3434;
+pub const NSFS_MAGIC: u32 = 0x6e73_6673;
+pub const OCFS2_SUPER_MAGIC: u32 = 0x7461_636f;
+pub const OPENPROM_SUPER_MAGIC: u32 = 0x9fa1;
+pub const OVERLAYFS_SUPER_MAGIC: u32 = 0x794c_7630;
+pub const PIPEFS_MAGIC: u32 = 0x5049_5045;
+pub const PROC_SUPER_MAGIC: u32 = 0x9fa0;
+pub const PSTOREFS_MAGIC: u32 = 0x6165_676c;
+pub const QNX4_SUPER_MAGIC: u32 = 0x002f;
+pub const RAMFS_MAGIC: u32 = 0x8584_58f6;
+pub const REISERFS_SUPER_MAGIC: u32 = 0x5265_4973;
+pub const SECURITYFS_MAGIC: u32 = 0x7363_6673;
+pub const SELINUX_MAGIC: u32 = 0xf97c_ff8c;
+pub const SMACK_MAGIC: u32 = 0x4341_5d53;
+pub const SMB_SUPER_MAGIC: u32 = 0x517b;
+pub const SOCKFS_MAGIC: u32 = 0x534f_434b;
+pub const SQUASHFS_MAGIC: u32 = 0x7371_7368;
+pub const SYSFS_MAGIC: u32 = 0x6265_6572;
+pub const TMPFS_MAGIC: u32 = 0x0102_1994;
+pub const TRACEFS_MAGIC: u32 = 0x7472_6163;
+pub const XFS_SUPER_MAGIC: u32 = 0x5846_5342;
+pub const ZONEFS_MAGIC: u32 = 0x5a4f_4653;
+
+/// Names of the file systems, as found in `/proc/filesystems`
+const NAMES: &[(&str, u32)] = &[
+    ("adfs", ADFS_SUPER_MAGIC),
+    ("affs", AFFS_SUPER_MAGIC),
+    ("afs", AFS_SUPER_MAGIC),
+    ("anon_inodefs", ANON_INODE_FS_MAGIC),
+    ("autofs", AUTOFS_SUPER_MAGIC),
+    ("binfmt_misc", BINFMTFS_MAGIC),
+    ("bpf", BPF_FS_MAGIC),
+    ("btrfs", BTRFS_SUPER_MAGIC),
+    ("ceph", CEPH_SUPER_MAGIC),
+    ("cgroup", CGROUP_SUPER_MAGIC),
+    ("cgroup2", CGROUP2_SUPER_MAGIC),
+    ("coda", CODA_SUPER_MAGIC),
+    ("configfs", CONFIGFS_MAGIC),
+    ("cramfs", CRAMFS_MAGIC),
+    ("debugfs", DEBUGFS_MAGIC),
+    ("devpts", DEVPTS_SUPER_MAGIC),
+    ("ecryptfs", ECRYPTFS_SUPER_MAGIC),
+    ("efivarfs", EFIVARFS_MAGIC),
+    ("erofs", EROFS_SUPER_MAGIC_V1),
+    ("ext4", EXT4_SUPER_MAGIC),
+    ("ext3", EXT4_SUPER_MAGIC),
+    ("ext2", EXT4_SUPER_MAGIC),
+    ("f2fs", F2FS_SUPER_MAGIC),
+    ("fuse", FUSE_SUPER_MAGIC),
+    ("hostfs", HOSTFS_SUPER_MAGIC),
+    ("hpfs", HPFS_SUPER_MAGIC),
+    ("hugetlbfs", HUGETLBFS_MAGIC),
+    ("iso9660", ISOFS_SUPER_MAGIC),
+    ("jffs2", JFFS2_SUPER_MAGIC),
+    ("minix", MINIX_SUPER_MAGIC),
+    ("mqueue", MQUEUE_MAGIC),
+    ("msdos", MSDOS_SUPER_MAGIC),
+    ("nfs", NFS_SUPER_MAGIC),
+    ("nilfs2", NILFS_SUPER_MAGIC),
+    ("nsfs", NSFS_MAGIC),
+    ("ocfs2", OCFS2_SUPER_MAGIC),
+    ("openpromfs", OPENPROM_SUPER_MAGIC),
+    ("overlay", OVERLAYFS_SUPER_MAGIC),
+    ("pipefs", PIPEFS_MAGIC),
+    ("proc", PROC_SUPER_MAGIC),
+    ("pstore", PSTOREFS_MAGIC),
+    ("qnx4", QNX4_SUPER_MAGIC),
+    ("ramfs", RAMFS_MAGIC),
+    ("reiserfs", REISERFS_SUPER_MAGIC),
+    ("securityfs", SECURITYFS_MAGIC),
+    ("selinuxfs", SELINUX_MAGIC),
+    ("smackfs", SMACK_MAGIC),
+    ("smb", SMB_SUPER_MAGIC),
+    ("sockfs", SOCKFS_MAGIC),
+    ("squashfs", SQUASHFS_MAGIC),
+    ("sysfs", SYSFS_MAGIC),
+    ("tmpfs", TMPFS_MAGIC),
+    ("tracefs", TRACEFS_MAGIC),
+    ("xfs", XFS_SUPER_MAGIC),
+    ("zonefs", ZONEFS_MAGIC),
+];
+
+/// Return the name of the file system with the given magic number. ext2,
+/// ext3 and ext4 share the same magic number, reported as `ext4`.
+pub fn name(magic: u32) -> Option<&'static str> {
+    NAMES
+        .iter()
+        .find(|(_, value)| *value == magic)
+        .map(|(name, _)| *name)
+}
+
+/// Return the magic number of the file system with the given name
+pub fn from_name(name: &str) -> Option<u32> {
+    NAMES
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, value)| *value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(from_name("tracefs"), Some(TRACEFS_MAGIC));
+        assert_eq!(from_name("debugfs"), Some(0x6462_6720));
+        assert_eq!(from_name("ext3"), Some(EXT4_SUPER_MAGIC));
+        assert_eq!(from_name("nope"), None);
+        assert_eq!(name(PROC_SUPER_MAGIC), Some("proc"));
+        assert_eq!(name(EXT4_SUPER_MAGIC), Some("ext4"));
+        assert_eq!(name(0x1234_5678), None);
+    }
+}
@@ -0,0 +1,289 @@
+// SPDX-License-Identifier: MIT
+
+//! Constants from `linux/audit.h` and libaudit.
+//!
+//! The constants are grouped in submodules, each of which also provides
+//! the typed equivalent of its constants. Everything is re-exported here
+//! (and at the root of the crate) as well, except for [`fs_magic`].
+
+use std::ops::{BitAnd, BitOr, BitOrAssign};
+
+/// Define an enum for a group of constants, with an `Unknown` variant for
+/// the values this crate does not know about
+macro_rules! raw_enum {
+    (
+        $(#[$attr:meta])*
+        pub enum $type:ident: $raw:ty {
+            $($variant:ident = $constant:ident,)*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+        #[non_exhaustive]
+        pub enum $type {
+            $(
+                #[doc = concat!("`", stringify!($constant), "`")]
+                $variant,
+            )*
+            /// A value this crate does not know about
+            Unknown($raw),
+        }
+
+        impl $type {
+            pub fn from_raw(value: $raw) -> Self {
+                match value {
+                    $($constant => $type::$variant,)*
+                    other => $type::Unknown(other),
+                }
+            }
+
+            pub fn into_raw(self) -> $raw {
+                match self {
+                    $($type::$variant => $constant,)*
+                    $type::Unknown(other) => other,
+                }
+            }
+        }
+
+        impl From<$raw> for $type {
+            fn from(value: $raw) -> Self {
+                $type::from_raw(value)
+            }
+        }
+
+        impl From<$type> for $raw {
+            fn from(value: $type) -> Self {
+                value.into_raw()
+            }
+        }
+    };
+}
+
+/// Define a bit mask type for a group of constants. Bits this crate does
+/// not know about are preserved.
+macro_rules! bit_flags {
+    (
+        $(#[$attr:meta])*
+        pub struct $type:ident: $raw:ty {
+            $(const $flag:ident = $constant:ident;)*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+        pub struct $type($raw);
+
+        impl $type {
+            $(
+                #[doc = concat!("`", stringify!($constant), "`")]
+                pub const $flag: $type = $type($constant);
+            )*
+
+            pub const fn empty() -> Self {
+                $type(0)
+            }
+
+            /// Return the mask with all the bits known to this crate set
+            pub const fn all() -> Self {
+                $type(0 $(| $constant)*)
+            }
+
+            pub const fn from_raw(value: $raw) -> Self {
+                $type(value)
+            }
+
+            pub const fn into_raw(self) -> $raw {
+                self.0
+            }
+
+            pub const fn is_empty(self) -> bool {
+                self.0 == 0
+            }
+
+            /// Return `true` if all the bits of `other` are set
+            pub const fn contains(self, other: Self) -> bool {
+                self.0 & other.0 == other.0
+            }
+
+            pub fn insert(&mut self, other: Self) {
+                self.0 |= other.0
+            }
+
+            pub fn remove(&mut self, other: Self) {
+                self.0 &= !other.0
+            }
+        }
+
+        impl BitOr for $type {
+            type Output = Self;
+
+            fn bitor(self, other: Self) -> Self {
+                $type(self.0 | other.0)
+            }
+        }
+
+        impl BitOrAssign for $type {
+            fn bitor_assign(&mut self, other: Self) {
+                self.0 |= other.0
+            }
+        }
+
+        impl BitAnd for $type {
+            type Output = Self;
+
+            fn bitand(self, other: Self) -> Self {
+                $type(self.0 & other.0)
+            }
+        }
+
+        impl From<$raw> for $type {
+            fn from(value: $raw) -> Self {
+                $type(value)
+            }
+        }
+
+        impl From<$type> for $raw {
+            fn from(value: $type) -> Self {
+                value.0
+            }
+        }
+    };
+}
+
+pub mod arch;
+pub use self::arch::*;
+
+pub mod features;
+pub use self::features::*;
+
+pub mod fields;
+pub use self::fields::*;
+
+pub mod filters;
+pub use self::filters::*;
+
+pub mod fs_magic;
+
+pub mod operators;
+pub use self::operators::*;
+
+pub mod record_types;
+pub use self::record_types::*;
+
+pub mod status;
+pub use self::status::*;
+
+pub const AUDIT_MAX_FIELDS: usize = 64;
+pub const AUDIT_MAX_KEY_LEN: usize = 256;
+pub const AUDIT_BITMASK_SIZE: usize = 64;
+
+pub const AUDIT_SYSCALL_CLASSES: u32 = 16;
+pub const AUDIT_CLASS_DIR_WRITE: u32 = 0;
+pub const AUDIT_CLASS_DIR_WRITE_32: u32 = 1;
+pub const AUDIT_CLASS_CHATTR: u32 = 2;
+pub const AUDIT_CLASS_CHATTR_32: u32 = 3;
+pub const AUDIT_CLASS_READ: u32 = 4;
+pub const AUDIT_CLASS_READ_32: u32 = 5;
+pub const AUDIT_CLASS_WRITE: u32 = 6;
+pub const AUDIT_CLASS_WRITE_32: u32 = 7;
+pub const AUDIT_CLASS_SIGNAL: u32 = 8;
+pub const AUDIT_CLASS_SIGNAL_32: u32 = 9;
+pub const AUDIT_UNUSED_BITS: u32 = 134216704;
+
+pub const AUDIT_PERM_EXEC: u32 = 1;
+pub const AUDIT_PERM_WRITE: u32 = 2;
+pub const AUDIT_PERM_READ: u32 = 4;
+pub const AUDIT_PERM_ATTR: u32 = 8;
+pub const AUDIT_MESSAGE_TEXT_MAX: u32 = 8560;
+
+/// Unused multicast group for audit
+pub const AUDIT_NLGRP_NONE: u32 = 0;
+/// Multicast group to listen for audit events
+pub const AUDIT_NLGRP_READLOG: u32 = 1;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_raw_conversions() {
+        assert_eq!(RuleFieldType::from_raw(AUDIT_EXE), RuleFieldType::Exe);
+        assert_eq!(RuleFieldType::from_raw(42), RuleFieldType::Unknown(42));
+        assert_eq!(RuleFieldType::Filterkey.into_raw(), AUDIT_FILTERKEY);
+        assert_eq!(FieldCompare::from_raw(10), FieldCompare::UidToAuid);
+        assert_eq!(
+            FieldCompare::SgidToFsgid.into_raw(),
+            AUDIT_MAX_FIELD_COMPARE
+        );
+        assert_eq!(FieldCompare::from_raw(26), FieldCompare::Unknown(26));
+        assert_eq!(FailureMode::from_raw(AUDIT_FAIL_PANIC), FailureMode::Panic);
+        assert_eq!(AuditFeature::LoginuidImmutable.into_raw(), 1);
+        assert_eq!(RuleFlags::from_raw(AUDIT_FILTER_FS), RuleFlags::FilterFs);
+        assert_eq!(RuleFlags::from_raw(7), RuleFlags::FilterUringExit);
+        assert_eq!(RuleFlags::NrFilters.into_raw(), 8);
+        assert_eq!(
+            AuditMessageType::from_raw(AUDIT_SYSCALL).into_raw(),
+            AUDIT_SYSCALL
+        );
+
+        let mut mask = StatusMask::PID | StatusMask::LOST;
+        assert!(mask.contains(StatusMask::LOST));
+        assert!(!mask.contains(StatusMask::LOST | StatusMask::ENABLED));
+        mask.remove(StatusMask::PID);
+        assert_eq!(mask.into_raw(), AUDIT_STATUS_LOST);
+
+        let bitmap = FeatureBitmap::from_raw(0x8000_0001);
+        assert!(bitmap.contains(FeatureBitmap::BACKLOG_LIMIT));
+        assert_eq!(bitmap.into_raw(), 0x8000_0001);
+        assert_eq!(bitmap & FeatureBitmap::all(), FeatureBitmap::BACKLOG_LIMIT);
+        assert_eq!(FeatureBitmap::all().into_raw(), AUDIT_FEATURE_BITMAP_ALL);
+    }
+
+    #[test]
+    fn test_operator_symbols() {
+        assert_eq!(operator_symbol(AUDIT_NOT_EQUAL), Some("!="));
+        assert_eq!(operator_symbol(AUDIT_BIT_TEST), Some("&="));
+        assert_eq!(operator_symbol(0), None);
+        assert_eq!(
+            RuleFieldFlags::from_symbol(">="),
+            Some(RuleFieldFlags::GreaterThanOrEqual)
+        );
+        assert_eq!(RuleFieldFlags::BitMask.symbol(), Some("&"));
+        for (op, symbol) in operators::OPERATOR_SYMBOLS {
+            assert_eq!(operator_from_symbol(symbol), Some(*op));
+        }
+
+        assert_eq!(
+            split_field_expression("uid>=1000"),
+            Some(("uid", AUDIT_GREATER_THAN_OR_EQUAL, "1000"))
+        );
+        assert_eq!(
+            split_field_expression("perm&=wa"),
+            Some(("perm", AUDIT_BIT_TEST, "wa"))
+        );
+        assert_eq!(
+            split_field_expression("key=a=b"),
+            Some(("key", AUDIT_EQUAL, "a=b"))
+        );
+        assert_eq!(split_field_expression("uid"), None);
+        assert_eq!(split_field_expression("uid!0"), None);
+    }
+
+    #[test]
+    fn test_arch() {
+        let arch = AuditArch::from_raw(0xc000_003e);
+        assert_eq!(arch, AuditArch::X86_64);
+        assert!(arch.is_64bit() && arch.is_little_endian());
+        assert_eq!(arch.machine(), 62);
+
+        assert!(!AuditArch::I386.is_64bit());
+        assert!(AuditArch::Riscv64.is_64bit());
+        assert!(AuditArch::Loongarch64.is_little_endian());
+        assert!(AuditArch::S390x.is_64bit());
+        assert!(!AuditArch::S390x.is_little_endian());
+        assert!(!AuditArch::Armeb.is_little_endian());
+
+        let unknown = AuditArch::from_raw(0x8000_1234);
+        assert_eq!(unknown, AuditArch::Unknown(0x8000_1234));
+        assert!(unknown.is_64bit() && !unknown.is_little_endian());
+    }
+}
@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: MIT
+
+//! Comparison operators of the rule fields. The typed equivalent is
+//! [`RuleFieldFlags`]. [`operator_symbol`] and [`operator_from_symbol`]
+//! convert them from and to the symbols `auditctl` uses (`-F uid!=0`).
+
+pub use crate::rules::RuleFieldFlags;
+
+pub const AUDIT_BIT_MASK: u32 = 0x0800_0000;
+pub const AUDIT_LESS_THAN: u32 = 0x1000_0000;
+pub const AUDIT_GREATER_THAN: u32 = 0x2000_0000;
+pub const AUDIT_NOT_EQUAL: u32 = 0x3000_0000;
+pub const AUDIT_EQUAL: u32 = 0x4000_0000;
+pub const AUDIT_BIT_TEST: u32 = AUDIT_BIT_MASK | AUDIT_EQUAL;
+pub const AUDIT_LESS_THAN_OR_EQUAL: u32 = AUDIT_LESS_THAN | AUDIT_EQUAL;
+pub const AUDIT_GREATER_THAN_OR_EQUAL: u32 = AUDIT_GREATER_THAN | AUDIT_EQUAL;
+pub const AUDIT_OPERATORS: u32 = AUDIT_EQUAL | AUDIT_NOT_EQUAL | AUDIT_BIT_MASK;
+
+/// Operators and their `auditctl` symbols. Longer symbols come first, so
+/// that a rule field can be split on the first symbol that matches.
+pub(crate) const OPERATOR_SYMBOLS: &[(u32, &str)] = &[
+    (AUDIT_NOT_EQUAL, "!="),
+    (AUDIT_LESS_THAN_OR_EQUAL, "<="),
+    (AUDIT_GREATER_THAN_OR_EQUAL, ">="),
+    (AUDIT_BIT_TEST, "&="),
+    (AUDIT_EQUAL, "="),
+    (AUDIT_LESS_THAN, "<"),
+    (AUDIT_GREATER_THAN, ">"),
+    (AUDIT_BIT_MASK, "&"),
+];
+
+/// Return the `auditctl` symbol of an `AUDIT_*` operator
+pub fn operator_symbol(operator: u32) -> Option<&'static str> {
+    OPERATOR_SYMBOLS
+        .iter()
+        .find(|(op, _)| *op == operator)
+        .map(|(_, symbol)| *symbol)
+}
+
+/// Return the `AUDIT_*` operator of an `auditctl` symbol
+pub fn operator_from_symbol(symbol: &str) -> Option<u32> {
+    OPERATOR_SYMBOLS
+        .iter()
+        .find(|(_, s)| *s == symbol)
+        .map(|(op, _)| *op)
+}
+
+/// Split an `auditctl` field expression such as `uid>=1000` into the field
+/// name, the `AUDIT_*` operator and the value
+pub fn split_field_expression(expr: &str) -> Option<(&str, u32, &str)> {
+    let start = expr.find(['!', '<', '>', '&', '='])?;
+    let (name, rest) = expr.split_at(start);
+    OPERATOR_SYMBOLS
+        .iter()
+        .find(|(_, symbol)| rest.starts_with(symbol))
+        .map(|(op, symbol)| (name, *op, &rest[symbol.len()..]))
+}
+
+impl RuleFieldFlags {
+    /// Return the `auditctl` symbol of the operator
+    pub fn symbol(&self) -> Option<&'static str> {
+        operator_symbol((*self).into())
+    }
+
+    /// Return the operator of an `auditctl` symbol
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        operator_from_symbol(symbol).map(Self::from)
+    }
+}
@@ -0,0 +1,480 @@
+// SPDX-License-Identifier: MIT
+
+//! Netlink message and audit record types (`AUDIT_*` in `linux/audit.h`
+//! and libaudit). The typed equivalent is [`AuditMessageType`].
+
+pub use crate::{AuditMessageType, UserMessageKind};
+
+// ==========================================
+// 1000 - 1099 are for commanding the audit system
+// ==========================================
+
+/// Get status
+pub const AUDIT_GET: u16 = 1000;
+/// Set status (enable/disable/auditd)
+pub const AUDIT_SET: u16 = 1001;
+/// List syscall rules -- deprecated
+pub const AUDIT_LIST: u16 = 1002;
+/// Add syscall rule -- deprecated
+pub const AUDIT_ADD: u16 = 1003;
+/// Delete syscall rule -- deprecated
+pub const AUDIT_DEL: u16 = 1004;
+/// Message from userspace -- deprecated
+pub const AUDIT_USER: u16 = 1005;
+/// Define the login id and information
+pub const AUDIT_LOGIN: u16 = 1006;
+/// Insert file/dir watch entry
+pub const AUDIT_WATCH_INS: u16 = 1007;
+/// Remove file/dir watch entry
+pub const AUDIT_WATCH_REM: u16 = 1008;
+/// List all file/dir watches
+pub const AUDIT_WATCH_LIST: u16 = 1009;
+/// Get info about sender of signal to auditd
+pub const AUDIT_SIGNAL_INFO: u16 = 1010;
+/// Add syscall filtering rule
+pub const AUDIT_ADD_RULE: u16 = 1011;
+/// Delete syscall filtering rule
+pub const AUDIT_DEL_RULE: u16 = 1012;
+/// List syscall filtering rules
+pub const AUDIT_LIST_RULES: u16 = 1013;
+/// Trim junk from watched tree
+pub const AUDIT_TRIM: u16 = 1014;
+/// Append to watched tree
+pub const AUDIT_MAKE_EQUIV: u16 = 1015;
+/// Get TTY auditing status
+pub const AUDIT_TTY_GET: u16 = 1016;
+/// Set TTY auditing status
+pub const AUDIT_TTY_SET: u16 = 1017;
+/// Turn an audit feature on or off
+pub const AUDIT_SET_FEATURE: u16 = 1018;
+/// Get which features are enabled
+pub const AUDIT_GET_FEATURE: u16 = 1019;
+
+// ==========================================
+// 1100 - 1199 user space trusted application messages
+// ==========================================
+
+/// Userspace messages mostly uninteresting to kernel
+pub const AUDIT_FIRST_USER_MSG: u16 = 1100;
+// The following message types are not defined by the kernel, but by
+// libaudit. The kernel forwards them to the audit daemon as they are.
+/// User system access authentication
+pub const AUDIT_USER_AUTH: u16 = 1100;
+/// User system access authorization
+pub const AUDIT_USER_ACCT: u16 = 1101;
+/// User acct attribute change
+pub const AUDIT_USER_MGMT: u16 = 1102;
+/// User credential acquired
+pub const AUDIT_CRED_ACQ: u16 = 1103;
+/// User credential disposed
+pub const AUDIT_CRED_DISP: u16 = 1104;
+/// User session start
+pub const AUDIT_USER_START: u16 = 1105;
+/// User session end
+pub const AUDIT_USER_END: u16 = 1106;
+/// We filter this differently
+pub const AUDIT_USER_AVC: u16 = 1107;
+/// User acct password or pin changed
+pub const AUDIT_USER_CHAUTHTOK: u16 = 1108;
+/// User acct state error
+pub const AUDIT_USER_ERR: u16 = 1109;
+/// User credential refreshed
+pub const AUDIT_CRED_REFR: u16 = 1110;
+/// User space system config change
+pub const AUDIT_USYS_CONFIG: u16 = 1111;
+/// User has logged in
+pub const AUDIT_USER_LOGIN: u16 = 1112;
+/// User has logged out
+pub const AUDIT_USER_LOGOUT: u16 = 1113;
+/// User account added
+pub const AUDIT_ADD_USER: u16 = 1114;
+/// User account deleted
+pub const AUDIT_DEL_USER: u16 = 1115;
+/// Group account added
+pub const AUDIT_ADD_GROUP: u16 = 1116;
+/// Group account deleted
+pub const AUDIT_DEL_GROUP: u16 = 1117;
+/// User space DAC check results
+pub const AUDIT_DAC_CHECK: u16 = 1118;
+/// User space group ID changed
+pub const AUDIT_CHGRP_ID: u16 = 1119;
+/// Used for test success messages
+pub const AUDIT_TEST: u16 = 1120;
+/// Trusted app msg - freestyle text
+pub const AUDIT_TRUSTED_APP: u16 = 1121;
+/// SE Linux user space error
+pub const AUDIT_USER_SELINUX_ERR: u16 = 1122;
+/// User shell command and args
+pub const AUDIT_USER_CMD: u16 = 1123;
+/// Non-ICANON TTY input meaning
+pub const AUDIT_USER_TTY: u16 = 1124;
+/// Changed user ID supplemental data
+pub const AUDIT_CHUSER_ID: u16 = 1125;
+/// Authentication for group password
+pub const AUDIT_GRP_AUTH: u16 = 1126;
+/// System boot
+pub const AUDIT_SYSTEM_BOOT: u16 = 1127;
+/// System shutdown
+pub const AUDIT_SYSTEM_SHUTDOWN: u16 = 1128;
+/// System runlevel change
+pub const AUDIT_SYSTEM_RUNLEVEL: u16 = 1129;
+/// Service (daemon) start
+pub const AUDIT_SERVICE_START: u16 = 1130;
+/// Service (daemon) stop
+pub const AUDIT_SERVICE_STOP: u16 = 1131;
+/// Group account attr was modified
+pub const AUDIT_GRP_MGMT: u16 = 1132;
+/// Group acct password or pin changed
+pub const AUDIT_GRP_CHAUTHTOK: u16 = 1133;
+/// User space MAC decision results
+pub const AUDIT_MAC_CHECK: u16 = 1134;
+/// User's account locked by admin
+pub const AUDIT_ACCT_LOCK: u16 = 1135;
+/// User's account unlocked by admin
+pub const AUDIT_ACCT_UNLOCK: u16 = 1136;
+/// User space hotplug device changes
+pub const AUDIT_USER_DEVICE: u16 = 1137;
+/// Software update event
+pub const AUDIT_SOFTWARE_UPDATE: u16 = 1138;
+pub const AUDIT_LAST_USER_MSG: u16 = 1199;
+
+/// More user space messages;
+pub const AUDIT_FIRST_USER_MSG2: u16 = 2100;
+pub const AUDIT_LAST_USER_MSG2: u16 = 2999;
+// The following message types are defined by libaudit as well.
+/// Userspace anomaly messages
+pub const AUDIT_FIRST_ANOM_MSG: u16 = 2100;
+pub const AUDIT_LAST_ANOM_MSG: u16 = 2199;
+/// Responses to anomalies
+pub const AUDIT_FIRST_ANOM_RESP: u16 = 2200;
+pub const AUDIT_LAST_ANOM_RESP: u16 = 2299;
+/// Failed login limit reached
+pub const AUDIT_ANOM_LOGIN_FAILURES: u16 = 2100;
+/// Login attempted at bad time
+pub const AUDIT_ANOM_LOGIN_TIME: u16 = 2101;
+/// Max concurrent sessions reached
+pub const AUDIT_ANOM_LOGIN_SESSIONS: u16 = 2102;
+/// Login attempted to watched acct
+pub const AUDIT_ANOM_LOGIN_ACCT: u16 = 2103;
+/// Login from forbidden location
+pub const AUDIT_ANOM_LOGIN_LOCATION: u16 = 2104;
+/// Max DAC failures reached
+pub const AUDIT_ANOM_MAX_DAC: u16 = 2105;
+/// Max MAC failures reached
+pub const AUDIT_ANOM_MAX_MAC: u16 = 2106;
+/// AMTU failure
+pub const AUDIT_ANOM_AMTU_FAIL: u16 = 2107;
+/// RBAC self test failure
+pub const AUDIT_ANOM_RBAC_FAIL: u16 = 2108;
+/// RBAC file integrity failure
+pub const AUDIT_ANOM_RBAC_INTEGRITY_FAIL: u16 = 2109;
+/// Crypto system test failure
+pub const AUDIT_ANOM_CRYPTO_FAIL: u16 = 2110;
+/// Access of file or dir
+pub const AUDIT_ANOM_ACCESS_FS: u16 = 2111;
+/// Execution of file
+pub const AUDIT_ANOM_EXEC: u16 = 2112;
+/// Make an executable
+pub const AUDIT_ANOM_MK_EXEC: u16 = 2113;
+/// Adding an acct
+pub const AUDIT_ANOM_ADD_ACCT: u16 = 2114;
+/// Deleting an acct
+pub const AUDIT_ANOM_DEL_ACCT: u16 = 2115;
+/// Changing an acct
+pub const AUDIT_ANOM_MOD_ACCT: u16 = 2116;
+/// User became root
+pub const AUDIT_ANOM_ROOT_TRANS: u16 = 2117;
+/// Service acct attempted login
+pub const AUDIT_ANOM_LOGIN_SERVICE: u16 = 2118;
+/// Root login attempted
+pub const AUDIT_ANOM_LOGIN_ROOT: u16 = 2119;
+/// Origin has too many failed login
+pub const AUDIT_ANOM_ORIGIN_FAILURES: u16 = 2120;
+/// The user session is bad
+pub const AUDIT_ANOM_SESSION: u16 = 2121;
+/// Anomaly not reacted to
+pub const AUDIT_RESP_ANOMALY: u16 = 2200;
+/// Alert email was sent
+pub const AUDIT_RESP_ALERT: u16 = 2201;
+/// Kill program
+pub const AUDIT_RESP_KILL_PROC: u16 = 2202;
+/// Terminate session
+pub const AUDIT_RESP_TERM_ACCESS: u16 = 2203;
+/// Acct locked from remote access
+pub const AUDIT_RESP_ACCT_REMOTE: u16 = 2204;
+/// User acct locked for time
+pub const AUDIT_RESP_ACCT_LOCK_TIMED: u16 = 2205;
+/// User acct unlocked from time
+pub const AUDIT_RESP_ACCT_UNLOCK_TIMED: u16 = 2206;
+/// User acct was locked
+pub const AUDIT_RESP_ACCT_LOCK: u16 = 2207;
+/// Terminal was locked
+pub const AUDIT_RESP_TERM_LOCK: u16 = 2208;
+/// Set an SE Linux boolean
+pub const AUDIT_RESP_SEBOOL: u16 = 2209;
+/// Execute a script
+pub const AUDIT_RESP_EXEC: u16 = 2210;
+/// Go to single user mode
+pub const AUDIT_RESP_SINGLE: u16 = 2211;
+/// Take the system down
+pub const AUDIT_RESP_HALT: u16 = 2212;
+/// Address blocked by iptables
+pub const AUDIT_RESP_ORIGIN_BLOCK: u16 = 2213;
+/// Address blocked for time
+pub const AUDIT_RESP_ORIGIN_BLOCK_TIMED: u16 = 2214;
+/// User changed to a new role
+pub const AUDIT_USER_ROLE_CHANGE: u16 = 2300;
+/// Admin assigned user to role
+pub const AUDIT_ROLE_ASSIGN: u16 = 2301;
+/// Admin removed user from role
+pub const AUDIT_ROLE_REMOVE: u16 = 2302;
+/// Admin is overriding a label
+pub const AUDIT_LABEL_OVERRIDE: u16 = 2303;
+/// Object's level was changed
+pub const AUDIT_LABEL_LEVEL_CHANGE: u16 = 2304;
+/// Object exported with label
+pub const AUDIT_USER_LABELED_EXPORT: u16 = 2305;
+/// Object exported without label
+pub const AUDIT_USER_UNLABELED_EXPORT: u16 = 2306;
+/// Device was allocated
+pub const AUDIT_DEV_ALLOC: u16 = 2307;
+/// Device was deallocated
+pub const AUDIT_DEV_DEALLOC: u16 = 2308;
+/// Filesystem relabeled
+pub const AUDIT_FS_RELABEL: u16 = 2309;
+/// Userspc daemon loaded policy
+pub const AUDIT_USER_MAC_POLICY_LOAD: u16 = 2310;
+/// Admin modified a role
+pub const AUDIT_ROLE_MODIFY: u16 = 2311;
+/// Change made to MAC policy
+pub const AUDIT_USER_MAC_CONFIG_CHANGE: u16 = 2312;
+/// Userspc daemon enforcing change
+pub const AUDIT_USER_MAC_STATUS: u16 = 2313;
+/// Crypto test results
+pub const AUDIT_CRYPTO_TEST_USER: u16 = 2400;
+/// Crypto attribute change
+pub const AUDIT_CRYPTO_PARAM_CHANGE_USER: u16 = 2401;
+/// Logged in as crypto officer
+pub const AUDIT_CRYPTO_LOGIN: u16 = 2402;
+/// Logged out from crypto
+pub const AUDIT_CRYPTO_LOGOUT: u16 = 2403;
+/// Create,delete,negotiate
+pub const AUDIT_CRYPTO_KEY_USER: u16 = 2404;
+/// Fail decrypt,encrypt,randomiz
+pub const AUDIT_CRYPTO_FAILURE_USER: u16 = 2405;
+/// Crypto replay detected
+pub const AUDIT_CRYPTO_REPLAY_USER: u16 = 2406;
+/// Record parameters set during TLS session establishment
+pub const AUDIT_CRYPTO_SESSION: u16 = 2407;
+/// Record parameters related to IKE SA
+pub const AUDIT_CRYPTO_IKE_SA: u16 = 2408;
+/// Record parameters related to IPSEC SA
+pub const AUDIT_CRYPTO_IPSEC_SA: u16 = 2409;
+/// Start, Pause, Stop VM
+pub const AUDIT_VIRT_CONTROL: u16 = 2500;
+/// Resource assignment
+pub const AUDIT_VIRT_RESOURCE: u16 = 2501;
+/// Binding of label to VM
+pub const AUDIT_VIRT_MACHINE_ID: u16 = 2502;
+/// Guest integrity results
+pub const AUDIT_VIRT_INTEGRITY_CHECK: u16 = 2503;
+/// Creation of guest image
+pub const AUDIT_VIRT_CREATE: u16 = 2504;
+/// Destruction of guest image
+pub const AUDIT_VIRT_DESTROY: u16 = 2505;
+/// Inbound guest migration info
+pub const AUDIT_VIRT_MIGRATE_IN: u16 = 2506;
+/// Outbound guest migration info
+pub const AUDIT_VIRT_MIGRATE_OUT: u16 = 2507;
+
+// ==========================================
+// 1200 - 1299 messages internal to the audit daemon
+// ==========================================
+
+/// Daemon startup record
+pub const AUDIT_DAEMON_START: u16 = 1200;
+/// Daemon normal stop record
+pub const AUDIT_DAEMON_END: u16 = 1201;
+/// Daemon error stop record
+pub const AUDIT_DAEMON_ABORT: u16 = 1202;
+/// Daemon config change
+pub const AUDIT_DAEMON_CONFIG: u16 = 1203;
+// The following message types are defined by libaudit, the kernel never
+// sends them.
+/// Auditd should reconfigure
+pub const AUDIT_DAEMON_RECONFIG: u16 = 1204;
+/// Auditd should rotate logs
+pub const AUDIT_DAEMON_ROTATE: u16 = 1205;
+/// Auditd should resume logging
+pub const AUDIT_DAEMON_RESUME: u16 = 1206;
+/// Auditd accepted remote connection
+pub const AUDIT_DAEMON_ACCEPT: u16 = 1207;
+/// Auditd closed remote connection
+pub const AUDIT_DAEMON_CLOSE: u16 = 1208;
+/// Auditd internal error
+pub const AUDIT_DAEMON_ERR: u16 = 1209;
+
+// ==========================================
+// 1300 - 1399 audit event messages
+// ==========================================
+
+pub const AUDIT_EVENT_MESSAGE_MIN: u16 = 1300;
+pub const AUDIT_EVENT_MESSAGE_MAX: u16 = 1399;
+/// Syscall event
+pub const AUDIT_SYSCALL: u16 = 1300;
+/// Filename path information
+pub const AUDIT_PATH: u16 = 1302;
+/// IPC record
+pub const AUDIT_IPC: u16 = 1303;
+/// sys_socketcall arguments
+pub const AUDIT_SOCKETCALL: u16 = 1304;
+/// Audit system configuration change
+pub const AUDIT_CONFIG_CHANGE: u16 = 1305;
+/// sockaddr copied as syscall arg
+pub const AUDIT_SOCKADDR: u16 = 1306;
+/// Current working directory
+pub const AUDIT_CWD: u16 = 1307;
+/// execve arguments
+pub const AUDIT_EXECVE: u16 = 1309;
+/// IPC new permissions record type
+pub const AUDIT_IPC_SET_PERM: u16 = 1311;
+/// POSIX MQ open record type
+pub const AUDIT_MQ_OPEN: u16 = 1312;
+/// POSIX MQ send/receive record type
+pub const AUDIT_MQ_SENDRECV: u16 = 1313;
+/// POSIX MQ notify record type
+pub const AUDIT_MQ_NOTIFY: u16 = 1314;
+/// POSIX MQ get/set attribute record type
+pub const AUDIT_MQ_GETSETATTR: u16 = 1315;
+/// For use by 3rd party modules
+pub const AUDIT_KERNEL_OTHER: u16 = 1316;
+/// audit record for pipe/socketpair
+pub const AUDIT_FD_PAIR: u16 = 1317;
+/// ptrace target
+pub const AUDIT_OBJ_PID: u16 = 1318;
+/// Input on an administrative TTY
+pub const AUDIT_TTY: u16 = 1319;
+/// End of multi-record event
+pub const AUDIT_EOE: u16 = 1320;
+/// Information about fcaps increasing perms
+pub const AUDIT_BPRM_FCAPS: u16 = 1321;
+/// Record showing argument to sys_capset
+pub const AUDIT_CAPSET: u16 = 1322;
+/// Record showing descriptor and flags in mmap
+pub const AUDIT_MMAP: u16 = 1323;
+/// Packets traversing netfilter chains
+pub const AUDIT_NETFILTER_PKT: u16 = 1324;
+/// Netfilter chain modifications
+pub const AUDIT_NETFILTER_CFG: u16 = 1325;
+/// Secure Computing event
+pub const AUDIT_SECCOMP: u16 = 1326;
+/// Proctitle emit event
+pub const AUDIT_PROCTITLE: u16 = 1327;
+/// audit log listing feature changes
+pub const AUDIT_FEATURE_CHANGE: u16 = 1328;
+/// Replace auditd if this packet unanswerd
+pub const AUDIT_REPLACE: u16 = 1329;
+/// Kernel Module events
+pub const AUDIT_KERN_MODULE: u16 = 1330;
+/// Fanotify access decision
+pub const AUDIT_FANOTIFY: u16 = 1331;
+/// Timekeeping offset injected
+pub const AUDIT_TIME_INJOFFSET: u16 = 1332;
+/// NTP value adjustment
+pub const AUDIT_TIME_ADJNTPVAL: u16 = 1333;
+/// BPF subsystem
+pub const AUDIT_BPF: u16 = 1334;
+/// Task joined multicast read socket
+pub const AUDIT_EVENT_LISTENER: u16 = 1335;
+/// io_uring operation
+pub const AUDIT_URINGOP: u16 = 1336;
+/// Record showing openat2 how args
+pub const AUDIT_OPENAT2: u16 = 1337;
+/// Device Mapper target control
+pub const AUDIT_DM_CTRL: u16 = 1338;
+/// Device Mapper events
+pub const AUDIT_DM_EVENT: u16 = 1339;
+
+// ==========================================
+// 1400 - 1499 SE Linux use
+// ==========================================
+
+/// SE Linux avc denial or grant
+pub const AUDIT_AVC: u16 = 1400;
+/// Internal SE Linux Errors
+pub const AUDIT_SELINUX_ERR: u16 = 1401;
+/// dentry, vfsmount pair from avc
+pub const AUDIT_AVC_PATH: u16 = 1402;
+/// Policy file load
+pub const AUDIT_MAC_POLICY_LOAD: u16 = 1403;
+/// Changed enforcing,permissive,off
+pub const AUDIT_MAC_STATUS: u16 = 1404;
+/// Changes to booleans
+pub const AUDIT_MAC_CONFIG_CHANGE: u16 = 1405;
+/// NetLabel: allow unlabeled traffic
+pub const AUDIT_MAC_UNLBL_ALLOW: u16 = 1406;
+/// NetLabel: add CIPSOv4 DOI entry
+pub const AUDIT_MAC_CIPSOV4_ADD: u16 = 1407;
+/// NetLabel: del CIPSOv4 DOI entry
+pub const AUDIT_MAC_CIPSOV4_DEL: u16 = 1408;
+/// NetLabel: add LSM domain mapping
+pub const AUDIT_MAC_MAP_ADD: u16 = 1409;
+/// NetLabel: del LSM domain mapping
+pub const AUDIT_MAC_MAP_DEL: u16 = 1410;
+/// Not used
+pub const AUDIT_MAC_IPSEC_ADDSA: u16 = 1411;
+/// Not used
+pub const AUDIT_MAC_IPSEC_DELSA: u16 = 1412;
+/// Not used
+pub const AUDIT_MAC_IPSEC_ADDSPD: u16 = 1413;
+/// Not used
+pub const AUDIT_MAC_IPSEC_DELSPD: u16 = 1414;
+/// Audit an IPSec event
+pub const AUDIT_MAC_IPSEC_EVENT: u16 = 1415;
+/// NetLabel: add a static label
+pub const AUDIT_MAC_UNLBL_STCADD: u16 = 1416;
+/// NetLabel: del a static label
+pub const AUDIT_MAC_UNLBL_STCDEL: u16 = 1417;
+/// NetLabel: add CALIPSO DOI entry
+pub const AUDIT_MAC_CALIPSO_ADD: u16 = 1418;
+/// NetLabel: del CALIPSO DOI entry
+pub const AUDIT_MAC_CALIPSO_DEL: u16 = 1419;
+
+// ==========================================
+// 1700 - 1799 kernel anomaly records
+// ==========================================
+
+pub const AUDIT_FIRST_KERN_ANOM_MSG: u16 = 1700;
+pub const AUDIT_LAST_KERN_ANOM_MSG: u16 = 1799;
+/// Device changed promiscuous mode
+pub const AUDIT_ANOM_PROMISCUOUS: u16 = 1700;
+/// Process ended abnormally
+pub const AUDIT_ANOM_ABEND: u16 = 1701;
+/// Suspicious use of file links
+pub const AUDIT_ANOM_LINK: u16 = 1702;
+/// Suspicious file creation
+pub const AUDIT_ANOM_CREAT: u16 = 1703;
+
+// ==========================================
+// 1800 - 1899 kernel integrity events
+// ==========================================
+
+/// Data integrity verification
+pub const AUDIT_INTEGRITY_DATA: u16 = 1800;
+/// Metadata integrity verification
+pub const AUDIT_INTEGRITY_METADATA: u16 = 1801;
+/// Integrity enable status
+pub const AUDIT_INTEGRITY_STATUS: u16 = 1802;
+/// Integrity HASH type
+pub const AUDIT_INTEGRITY_HASH: u16 = 1803;
+/// PCR invalidation msgs
+pub const AUDIT_INTEGRITY_PCR: u16 = 1804;
+/// policy rule
+pub const AUDIT_INTEGRITY_RULE: u16 = 1805;
+/// New EVM-covered xattr
+pub const AUDIT_INTEGRITY_EVM_XATTR: u16 = 1806;
+/// IMA policy rules
+pub const AUDIT_INTEGRITY_POLICY_RULE: u16 = 1807;
+
+// 2000 is for otherwise unclassified kernel audit messages (legacy)
+pub const AUDIT_KERNEL: u16 = 2000;
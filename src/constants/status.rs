@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: MIT
+
+//! Constants of the `AUDIT_GET` and `AUDIT_SET` status messages. The
+//! typed equivalents are [`StatusMask`] and [`FailureMode`].
+
+use super::*;
+
+bit_flags! {
+    /// `mask` field of the status message: the fields to change in an
+    /// `AUDIT_SET` request (`AUDIT_STATUS_*` constants)
+    pub struct StatusMask: u32 {
+        const ENABLED = AUDIT_STATUS_ENABLED;
+        const FAILURE = AUDIT_STATUS_FAILURE;
+        const PID = AUDIT_STATUS_PID;
+        const RATE_LIMIT = AUDIT_STATUS_RATE_LIMIT;
+        const BACKLOG_LIMIT = AUDIT_STATUS_BACKLOG_LIMIT;
+        const BACKLOG_WAIT_TIME = AUDIT_STATUS_BACKLOG_WAIT_TIME;
+        const LOST = AUDIT_STATUS_LOST;
+        const BACKLOG_WAIT_TIME_ACTUAL = AUDIT_STATUS_BACKLOG_WAIT_TIME_ACTUAL;
+    }
+}
+
+raw_enum! {
+    /// What the kernel does when it cannot log an event (`AUDIT_FAIL_*`
+    /// constants), as found in the `failure` field of the status message
+    pub enum FailureMode: u32 {
+        Silent = AUDIT_FAIL_SILENT,
+        Printk = AUDIT_FAIL_PRINTK,
+        Panic = AUDIT_FAIL_PANIC,
+    }
+}
+
+// ============================================
+// audit_status.mask bits
+// ============================================
+pub const AUDIT_STATUS_ENABLED: u32 = 0x0001;
+pub const AUDIT_STATUS_FAILURE: u32 = 0x0002;
+pub const AUDIT_STATUS_PID: u32 = 0x0004;
+pub const AUDIT_STATUS_RATE_LIMIT: u32 = 0x0008;
+pub const AUDIT_STATUS_BACKLOG_LIMIT: u32 = 0x0010;
+pub const AUDIT_STATUS_BACKLOG_WAIT_TIME: u32 = 0x0020;
+pub const AUDIT_STATUS_LOST: u32 = 0x0040;
+pub const AUDIT_STATUS_BACKLOG_WAIT_TIME_ACTUAL: u32 = 0x0080;
+
+/// Value of the `enabled` status field when auditing is enabled and its
+/// configuration cannot be changed until the next reboot (`AUDIT_LOCKED`
+/// in the kernel's private `kernel/audit.h`)
+pub const AUDIT_LOCKED: u32 = 2;
+
+// ============================================
+// failure to log actions
+// ============================================
+pub const AUDIT_FAIL_SILENT: u32 = 0;
+pub const AUDIT_FAIL_PRINTK: u32 = 1;
+pub const AUDIT_FAIL_PANIC: u32 = 2;
@@ -0,0 +1,193 @@
+// SPDX-License-Identifier: MIT
+
+//! Recording and replay of the raw audit netlink traffic.
+//!
+//! A dump is a sequence of datagrams, each of them prefixed with its length
+//! as a little-endian `u32`. Since the datagrams are recorded as the kernel
+//! sent them, replaying a dump goes through the same decoding path as live
+//! traffic, which makes dumps suitable as regression fixtures.
+//!
+//! ```
+//! use netlink_packet_audit::{
+//!     dump::{DumpReader, DumpWriter},
+//!     AuditMessage,
+//! };
+//!
+//! let mut writer = DumpWriter::new(vec![]);
+//! # let datagram: &[u8] = &[];
+//! writer.record(datagram)?;
+//! let dump = writer.into_inner();
+//!
+//! let mut reader = DumpReader::new(&dump[..]);
+//! while let Some(msg) = reader.next_message::<AuditMessage>()? {
+//!     println!("{msg:?}");
+//! }
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::{
+    convert::TryFrom,
+    fmt::Debug,
+    io::{self, Read, Write},
+};
+
+use byteorder::{ByteOrder, LittleEndian};
+use netlink_packet_core::{NetlinkDeserializable, NetlinkMessage};
+
+use crate::AuditFramer;
+
+/// Larger datagrams are considered corrupted
+const MAX_DATAGRAM_LEN: usize = 1024 * 1024;
+
+/// Record datagrams into a dump
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct DumpWriter<W> {
+    writer: W,
+}
+
+impl<W: Write> DumpWriter<W> {
+    pub fn new(writer: W) -> Self {
+        DumpWriter { writer }
+    }
+
+    /// Append a datagram, as read from the audit socket, to the dump
+    pub fn record(&mut self, datagram: &[u8]) -> io::Result<()> {
+        let len = u32::try_from(datagram.len()).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("cannot record a {} bytes datagram", datagram.len()),
+            )
+        })?;
+        let mut prefix = [0; 4];
+        LittleEndian::write_u32(&mut prefix, len);
+        self.writer.write_all(&prefix)?;
+        self.writer.write_all(datagram)
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Replay the datagrams recorded in a dump
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct DumpReader<R> {
+    reader: R,
+    framer: AuditFramer,
+}
+
+impl<R: Read> DumpReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_framer(reader, AuditFramer::new())
+    }
+
+    /// Replay the dump through the given framer, so that it can be
+    /// configured like the one used with live traffic
+    pub fn with_framer(reader: R, framer: AuditFramer) -> Self {
+        DumpReader { reader, framer }
+    }
+
+    pub fn framer(&self) -> &AuditFramer {
+        &self.framer
+    }
+
+    pub fn framer_mut(&mut self) -> &mut AuditFramer {
+        &mut self.framer
+    }
+
+    /// Read the next datagram, or return `None` at the end of the dump
+    pub fn next_datagram(&mut self) -> io::Result<Option<Vec<u8>>> {
+        let mut prefix = [0; 4];
+        match self.reader.read_exact(&mut prefix) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                return Ok(None)
+            }
+            Err(e) => return Err(e),
+        }
+        let len = LittleEndian::read_u32(&prefix) as usize;
+        if len > MAX_DATAGRAM_LEN {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "datagram length {len} exceeds the maximum of \
+                    {MAX_DATAGRAM_LEN}"
+                ),
+            ));
+        }
+        let mut datagram = vec![0; len];
+        self.reader.read_exact(&mut datagram)?;
+        Ok(Some(datagram))
+    }
+
+    /// Decode the next message, or return `None` at the end of the dump
+    pub fn next_message<T>(&mut self) -> io::Result<Option<NetlinkMessage<T>>>
+    where
+        T: NetlinkDeserializable + Debug,
+    {
+        loop {
+            if let Some(msg) = self.framer.next_message() {
+                return Ok(Some(msg));
+            }
+            match self.next_datagram()? {
+                Some(datagram) => self.framer.push(&datagram)?,
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use netlink_packet_core::{NetlinkHeader, NetlinkPayload};
+    use netlink_packet_utils::traits::Emitable;
+
+    use super::*;
+    use crate::{constants::*, AuditMessage, EventPayload};
+
+    #[test]
+    fn test_record_replay() {
+        let mut writer = DumpWriter::new(vec![]);
+        for serial in 1..=3 {
+            let data = format!("audit(1592992200.383:{serial}): pid=1");
+            let mut header = NetlinkHeader::default();
+            header.message_type = AUDIT_SYSCALL;
+            // the kernel does not count the header
+            header.length = data.len() as u32;
+            let mut datagram = vec![0; header.buffer_len()];
+            header.emit(&mut datagram);
+            datagram.extend_from_slice(data.as_bytes());
+            writer.record(&datagram).unwrap();
+        }
+        let dump = writer.into_inner();
+
+        let mut reader = DumpReader::new(&dump[..]);
+        reader.framer_mut().enable_stats();
+        for serial in 1..=3 {
+            let msg = reader.next_message::<AuditMessage>().unwrap().unwrap();
+            assert_eq!(
+                msg.payload,
+                NetlinkPayload::InnerMessage(AuditMessage::Event(
+                    EventPayload::new(
+                        AUDIT_SYSCALL.into(),
+                        format!("audit(1592992200.383:{serial}): pid=1").into()
+                    )
+                ))
+            );
+        }
+        assert!(reader.next_message::<AuditMessage>().unwrap().is_none());
+        assert_eq!(reader.framer().stats().unwrap().length_corrections, 3);
+
+        let mut reader = DumpReader::new(&dump[..10]);
+        assert_eq!(
+            reader.next_datagram().unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+}
@@ -0,0 +1,461 @@
+// SPDX-License-Identifier: MIT
+
+use std::{fmt, io};
+
+use netlink_packet_core::{
+    ErrorMessage, NetlinkBuffer, NetlinkHeader, NetlinkMessage, NetlinkPayload,
+    NETLINK_HEADER_LEN,
+};
+use netlink_packet_utils::DecodeError;
+
+use crate::{AuditMessage, RuleFieldType, RuleFlags};
+
+/// Error reported by the kernel in reply to a request, through a
+/// `NLMSG_ERROR` message with a negative error code.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[non_exhaustive]
+pub struct AuditNetlinkError {
+    /// Positive errno value (`EPERM`, `EINVAL`, ...)
+    pub errno: i32,
+    /// Sequence number of the error message
+    pub sequence_number: u32,
+    /// Header of the request that failed, when the kernel included it
+    pub request: Option<NetlinkHeader>,
+}
+
+impl AuditNetlinkError {
+    /// Build the error from a `NLMSG_ERROR` message carrying a negative
+    /// error code. Zero and positive codes are acknowledgements.
+    pub fn new(header: &NetlinkHeader, error: &ErrorMessage) -> Self {
+        // The kernel may truncate the original request (NLM_F_CAPPED), so
+        // only the header fields themselves are read here.
+        let request = (error.header.len() >= NETLINK_HEADER_LEN).then(|| {
+            let buf = NetlinkBuffer::new(&error.header[..]);
+            let mut header = NetlinkHeader::default();
+            header.length = buf.length();
+            header.message_type = buf.message_type();
+            header.flags = buf.flags();
+            header.sequence_number = buf.sequence_number();
+            header.port_number = buf.port_number();
+            header
+        });
+        AuditNetlinkError {
+            errno: error.raw_code().saturating_neg(),
+            sequence_number: header.sequence_number,
+            request,
+        }
+    }
+
+    pub fn to_io(&self) -> io::Error {
+        io::Error::from_raw_os_error(self.errno)
+    }
+}
+
+impl fmt::Display for AuditNetlinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.request {
+            Some(ref request) => write!(
+                f,
+                "request of type {} (sequence number {}) failed: {}",
+                request.message_type,
+                request.sequence_number,
+                self.to_io()
+            ),
+            None => write!(
+                f,
+                "request with sequence number {} failed: {}",
+                self.sequence_number,
+                self.to_io()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AuditNetlinkError {}
+
+impl From<AuditNetlinkError> for io::Error {
+    fn from(e: AuditNetlinkError) -> io::Error {
+        e.to_io()
+    }
+}
+
+/// Reason why an audit message payload could not be parsed. It converts
+/// into [`DecodeError`], which is what the `Parseable` implementations
+/// return.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[non_exhaustive]
+pub enum AuditDecodeError {
+    /// The `audit_status` payload is shorter than the original layout
+    TruncatedStatus { needed: usize, got: usize },
+    /// The `audit_status` payload length does not match any known layout
+    UnknownStatusLayout { got: usize },
+    /// The `audit_features` payload is too short
+    TruncatedFeatures { needed: usize, got: usize },
+    /// The `audit_sig_info` payload is too short
+    TruncatedSignalInfo { needed: usize, got: usize },
+    /// The `audit_tty_status` payload is too short
+    TruncatedTtyStatus { needed: usize, got: usize },
+    /// The `AUDIT_MAKE_EQUIV` payload is shorter than its header, or than
+    /// the paths lengths it announces
+    TruncatedMakeEquiv { needed: usize, got: usize },
+    /// The `audit_rule_data` payload is shorter than its header, or than
+    /// the string buffer length it announces
+    InvalidRuleBuffer { needed: usize, got: usize },
+    /// The syscall bit mask of a rule does not have the expected size
+    InvalidSyscallMask { needed: usize, got: usize },
+    /// The rule announces more fields than the kernel supports
+    TooManyRuleFields { count: usize, max: usize },
+    /// The string value of a rule field goes past the end of the rule
+    /// string buffer
+    InvalidRuleField { field: u32 },
+    /// The rule field type is unknown
+    UnknownRuleField { field: u32 },
+    /// The payload is not valid UTF-8, and `Utf8Policy::Strict` is in use
+    NonUtf8Payload { valid_up_to: usize },
+}
+
+impl fmt::Display for AuditDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::AuditDecodeError::*;
+
+        let (name, needed, got) = match *self {
+            TruncatedStatus { needed, got } => ("audit_status", needed, got),
+            TruncatedFeatures { needed, got } => {
+                ("audit_features", needed, got)
+            }
+            TruncatedSignalInfo { needed, got } => {
+                ("audit_sig_info", needed, got)
+            }
+            TruncatedTtyStatus { needed, got } => {
+                ("audit_tty_status", needed, got)
+            }
+            TruncatedMakeEquiv { needed, got } => {
+                ("AUDIT_MAKE_EQUIV", needed, got)
+            }
+            InvalidRuleBuffer { needed, got } => {
+                ("audit_rule_data", needed, got)
+            }
+            InvalidSyscallMask { needed, got } => {
+                ("syscall bit mask", needed, got)
+            }
+            UnknownStatusLayout { got } => {
+                return write!(
+                    f,
+                    "audit_status payload length is {got}, which does not \
+                    match any known layout"
+                )
+            }
+            InvalidRuleField { field } => {
+                return write!(
+                    f,
+                    "failed to decode rule field {field} (value should be a \
+                    string?)"
+                )
+            }
+            TooManyRuleFields { count, max } => {
+                return write!(
+                    f,
+                    "rule has {count} fields, but at most {max} are supported"
+                )
+            }
+            UnknownRuleField { field } => {
+                return write!(f, "unknown rule field type {field}")
+            }
+            NonUtf8Payload { valid_up_to } => {
+                return write!(
+                    f,
+                    "payload is not valid UTF-8 (invalid byte at offset \
+                    {valid_up_to})"
+                )
+            }
+        };
+        write!(f, "invalid {name} payload: need {needed} bytes, got {got}")
+    }
+}
+
+impl std::error::Error for AuditDecodeError {}
+
+impl From<AuditDecodeError> for DecodeError {
+    fn from(e: AuditDecodeError) -> DecodeError {
+        anyhow::Error::new(e).into()
+    }
+}
+
+/// Reason why a message cannot be serialized, returned by
+/// [`AuditMessage::try_emit`],
+/// [`RuleMessage::try_emit`](crate::rules::RuleMessage::try_emit),
+/// [`StatusMessage::try_emit`](crate::StatusMessage::try_emit) and
+/// [`try_serialize`](crate::try_serialize)
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[non_exhaustive]
+pub enum AuditEmitError {
+    /// The buffer is too small for the message
+    BufferTooShort { needed: usize, got: usize },
+    /// The `length` field of the netlink header does not match the length
+    /// of the serialized message. `NetlinkMessage::finalize()` sets it.
+    LengthMismatch { header: u32, actual: usize },
+    /// A rule has more fields than the kernel accepts
+    TooManyRuleFields { count: usize, max: usize },
+    /// A rule syscall bit mask does not have the expected size
+    InvalidSyscallMask { needed: usize, got: usize },
+    /// A rule field cannot be used on the rule's filter list
+    FieldNotAllowed {
+        field: RuleFieldType,
+        filter: RuleFlags,
+    },
+    /// The string value of a rule field is longer than the kernel accepts
+    StringTooLong {
+        field: RuleFieldType,
+        len: usize,
+        max: usize,
+    },
+}
+
+impl AuditEmitError {
+    /// Check that `buffer` can hold `needed` bytes
+    pub(crate) fn check_buffer(
+        needed: usize,
+        buffer: &[u8],
+    ) -> Result<(), AuditEmitError> {
+        if buffer.len() < needed {
+            return Err(AuditEmitError::BufferTooShort {
+                needed,
+                got: buffer.len(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for AuditEmitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::AuditEmitError::*;
+
+        match *self {
+            BufferTooShort { needed, got } => write!(
+                f,
+                "buffer is {got} bytes long, but the message needs {needed}"
+            ),
+            LengthMismatch { header, actual } => write!(
+                f,
+                "header length is {header}, but the message is {actual} \
+                bytes long"
+            ),
+            TooManyRuleFields { count, max } => write!(
+                f,
+                "rule has {count} fields, but at most {max} are supported"
+            ),
+            InvalidSyscallMask { needed, got } => write!(
+                f,
+                "syscall bit mask has {got} words instead of {needed}"
+            ),
+            FieldNotAllowed { field, filter } => write!(
+                f,
+                "field {field:?} is not allowed on the {filter:?} filter list"
+            ),
+            StringTooLong { field, len, max } => write!(
+                f,
+                "field {field:?} is {len} bytes long, but at most {max} are \
+                supported"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AuditEmitError {}
+
+/// Return `true` if the `NLMSG_ERROR` message reports an error.
+///
+/// Only negative codes are errors. Zero is a plain acknowledgement, and
+/// some requests are acknowledged with a positive value instead: the
+/// previous lost events counter for a `StatusMessage::reset_lost`
+/// request, for instance.
+pub(crate) fn is_error(message: &ErrorMessage) -> bool {
+    message.raw_code() < 0
+}
+
+/// Convert a netlink message into either the audit message it carries, or
+/// the error the kernel reported.
+///
+/// Returns `None` for the control messages consumers are usually not
+/// interested in: acknowledgements (`NLMSG_ERROR` with a zero or
+/// positive code), `NLMSG_DONE`, `NLMSG_NOOP` and `NLMSG_OVERRUN`.
+pub fn into_audit_result(
+    message: NetlinkMessage<AuditMessage>,
+) -> Option<Result<AuditMessage, AuditNetlinkError>> {
+    let (header, payload) = message.into_parts();
+    match payload {
+        NetlinkPayload::InnerMessage(msg) => Some(Ok(msg)),
+        NetlinkPayload::Error(ref e) if is_error(e) => {
+            Some(Err(AuditNetlinkError::new(&header, e)))
+        }
+        _ => None,
+    }
+}
+
+/// Answer of the kernel to a request
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum AuditReply {
+    /// The request was acknowledged (`NLMSG_ERROR` with a zero or
+    /// positive code)
+    Ack,
+    /// The kernel replied with a message, for instance the status in reply
+    /// to `AUDIT_GET`
+    Message(AuditMessage),
+}
+
+/// Return the answer to the request with the given sequence number: the
+/// first acknowledgement, error or reply carrying that sequence number.
+/// The messages with another sequence number (events, answers to other
+/// requests) and the netlink control messages are skipped.
+///
+/// Returns `None` if `messages` ends before the request is answered.
+///
+/// ```
+/// use netlink_packet_audit::prelude::*;
+/// use netlink_packet_core::ErrorMessage;
+///
+/// let mut header = NetlinkHeader::default();
+/// header.sequence_number = 3;
+/// let ack = NetlinkMessage::new(
+///     header,
+///     NetlinkPayload::Error(ErrorMessage::default()),
+/// );
+/// assert_eq!(resolve_reply(vec![ack], 3), Some(Ok(AuditReply::Ack)));
+/// ```
+pub fn resolve_reply<I>(
+    messages: I,
+    sequence_number: u32,
+) -> Option<Result<AuditReply, AuditNetlinkError>>
+where
+    I: IntoIterator<Item = NetlinkMessage<AuditMessage>>,
+{
+    messages
+        .into_iter()
+        .filter(|msg| msg.header.sequence_number == sequence_number)
+        .find_map(|msg| {
+            let (header, payload) = msg.into_parts();
+            match payload {
+                NetlinkPayload::InnerMessage(msg) => {
+                    Some(Ok(AuditReply::Message(msg)))
+                }
+                NetlinkPayload::Error(ref e) if is_error(e) => {
+                    Some(Err(AuditNetlinkError::new(&header, e)))
+                }
+                NetlinkPayload::Error(_) => Some(Ok(AuditReply::Ack)),
+                _ => None,
+            }
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use netlink_packet_core::DoneMessage;
+    use netlink_packet_utils::traits::Emitable;
+
+    use super::*;
+    use crate::{constants::*, EventPayload, Utf8Policy};
+
+    #[test]
+    fn test_parse_payload_error() {
+        assert_eq!(
+            AuditMessage::parse_payload(&[0; 8], AUDIT_SET, Utf8Policy::Raw),
+            Err(AuditDecodeError::TruncatedStatus { needed: 32, got: 8 })
+        );
+        assert_eq!(
+            AuditMessage::parse_payload(
+                b"audit(1.000:1): \xff",
+                AUDIT_SYSCALL,
+                Utf8Policy::Strict
+            ),
+            Err(AuditDecodeError::NonUtf8Payload { valid_up_to: 16 })
+        );
+        let err = DecodeError::from(AuditDecodeError::TruncatedFeatures {
+            needed: 16,
+            got: 4,
+        });
+        assert!(err
+            .to_string()
+            .contains("invalid audit_features payload: need 16 bytes, got 4"));
+    }
+
+    #[test]
+    fn test_into_audit_result() {
+        let mut request = NetlinkHeader::default();
+        request.message_type = AUDIT_SET;
+        request.sequence_number = 7;
+        let mut error = ErrorMessage::default();
+        error.code = std::num::NonZeroI32::new(-1);
+        error.header = vec![0; request.buffer_len()];
+        request.emit(&mut error.header);
+
+        let mut header = NetlinkHeader::default();
+        header.sequence_number = 7;
+        let msg = NetlinkMessage::new(header, NetlinkPayload::Error(error));
+        let err = into_audit_result(msg).unwrap().unwrap_err();
+        assert_eq!(err.errno, 1);
+        assert_eq!(err.request, Some(request));
+        assert_eq!(err.to_io().kind(), io::ErrorKind::PermissionDenied);
+
+        let ack = NetlinkMessage::new(
+            header,
+            NetlinkPayload::Error(ErrorMessage::default()),
+        );
+        assert!(into_audit_result(ack).is_none());
+        let mut lost = ErrorMessage::default();
+        lost.code = std::num::NonZeroI32::new(42);
+        let ack = NetlinkMessage::new(header, NetlinkPayload::Error(lost));
+        assert!(into_audit_result(ack).is_none());
+        let done = NetlinkMessage::new(
+            header,
+            NetlinkPayload::Done(DoneMessage::default()),
+        );
+        assert!(into_audit_result(done).is_none());
+
+        let event =
+            AuditMessage::Event(EventPayload::new(AUDIT_EOE.into(), vec![]));
+        let msg = NetlinkMessage::new(
+            header,
+            NetlinkPayload::InnerMessage(event.clone()),
+        );
+        assert_eq!(into_audit_result(msg), Some(Ok(event)));
+    }
+
+    #[test]
+    fn test_resolve_reply() {
+        let message = |sequence_number, payload| {
+            let mut header = NetlinkHeader::default();
+            header.sequence_number = sequence_number;
+            NetlinkMessage::new(header, payload)
+        };
+        let event =
+            AuditMessage::Event(EventPayload::new(AUDIT_EOE.into(), vec![]));
+        let status = AuditMessage::GetStatus(Some(Default::default()));
+        let mut error = ErrorMessage::default();
+        error.code = std::num::NonZeroI32::new(-17);
+        let mut lost = ErrorMessage::default();
+        lost.code = std::num::NonZeroI32::new(42);
+        let messages = vec![
+            message(0, NetlinkPayload::InnerMessage(event)),
+            message(1, NetlinkPayload::Error(ErrorMessage::default())),
+            message(2, NetlinkPayload::Done(DoneMessage::default())),
+            message(2, NetlinkPayload::InnerMessage(status.clone())),
+            message(3, NetlinkPayload::Error(error)),
+            message(5, NetlinkPayload::Error(lost)),
+        ];
+
+        assert_eq!(
+            resolve_reply(messages.clone(), 1),
+            Some(Ok(AuditReply::Ack))
+        );
+        assert_eq!(
+            resolve_reply(messages.clone(), 2),
+            Some(Ok(AuditReply::Message(status)))
+        );
+        let err = resolve_reply(messages.clone(), 3).unwrap().unwrap_err();
+        assert_eq!(err.to_io().kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(resolve_reply(messages.clone(), 4), None);
+        assert_eq!(resolve_reply(messages, 5), Some(Ok(AuditReply::Ack)));
+    }
+}
@@ -0,0 +1,158 @@
+// SPDX-License-Identifier: MIT
+
+use byteorder::{ByteOrder, NativeEndian};
+
+use netlink_packet_utils::{
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use crate::{constants::*, AuditDecodeError, Field};
+
+const VERSION: Field = 0..4;
+const MASK: Field = 4..8;
+const FEATURES: Field = 8..12;
+const LOCK: Field = 12..16;
+pub const FEATURES_MESSAGE_LEN: usize = LOCK.end;
+
+/// Payload of the `AUDIT_GET_FEATURE` and `AUDIT_SET_FEATURE` messages
+/// (`struct audit_features`)
+#[derive(Debug, PartialEq, Eq, Clone, Default, Hash)]
+#[non_exhaustive]
+pub struct FeaturesMessage {
+    /// Version of the structure, should be `AUDIT_FEATURE_VERSION`
+    pub version: u32,
+    /// Bit mask of the features being changed
+    pub mask: u32,
+    /// Bit mask of the enabled features
+    pub features: u32,
+    /// Bit mask of the features whose state is locked
+    pub lock: u32,
+}
+
+impl FeaturesMessage {
+    pub fn new() -> Self {
+        FeaturesMessage {
+            version: AUDIT_FEATURE_VERSION,
+            ..Default::default()
+        }
+    }
+
+    /// Return `true` if the given `AUDIT_FEATURE_*` feature is enabled
+    pub fn is_enabled(&self, feature: u32) -> bool {
+        self.features & feature_to_mask(feature) != 0
+    }
+
+    /// Return `true` if the given `AUDIT_FEATURE_*` feature is locked
+    pub fn is_locked(&self, feature: u32) -> bool {
+        self.lock & feature_to_mask(feature) != 0
+    }
+}
+
+/// Equivalent of the kernel's `AUDIT_FEATURE_TO_MASK()` macro
+pub fn feature_to_mask(feature: u32) -> u32 {
+    1 << (feature & 31)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct FeaturesMessageBuffer<T> {
+    buffer: T,
+}
+
+impl<T: AsRef<[u8]>> FeaturesMessageBuffer<T> {
+    pub fn new(buffer: T) -> FeaturesMessageBuffer<T> {
+        FeaturesMessageBuffer { buffer }
+    }
+
+    pub fn new_checked(
+        buffer: T,
+    ) -> Result<FeaturesMessageBuffer<T>, DecodeError> {
+        let buf = Self::new(buffer);
+        buf.check_buffer_length()?;
+        Ok(buf)
+    }
+
+    pub fn check_buffer_length(&self) -> Result<(), AuditDecodeError> {
+        let len = self.buffer.as_ref().len();
+        if len < FEATURES_MESSAGE_LEN {
+            return Err(AuditDecodeError::TruncatedFeatures {
+                needed: FEATURES_MESSAGE_LEN,
+                got: len,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> T {
+        self.buffer
+    }
+
+    pub fn version(&self) -> u32 {
+        NativeEndian::read_u32(&self.buffer.as_ref()[VERSION])
+    }
+
+    pub fn mask(&self) -> u32 {
+        NativeEndian::read_u32(&self.buffer.as_ref()[MASK])
+    }
+
+    pub fn features(&self) -> u32 {
+        NativeEndian::read_u32(&self.buffer.as_ref()[FEATURES])
+    }
+
+    pub fn lock(&self) -> u32 {
+        NativeEndian::read_u32(&self.buffer.as_ref()[LOCK])
+    }
+}
+
+impl<T: AsRef<[u8]> + AsMut<[u8]>> FeaturesMessageBuffer<T> {
+    pub fn set_version(&mut self, value: u32) {
+        NativeEndian::write_u32(&mut self.buffer.as_mut()[VERSION], value)
+    }
+
+    pub fn set_mask(&mut self, value: u32) {
+        NativeEndian::write_u32(&mut self.buffer.as_mut()[MASK], value)
+    }
+
+    pub fn set_features(&mut self, value: u32) {
+        NativeEndian::write_u32(&mut self.buffer.as_mut()[FEATURES], value)
+    }
+
+    pub fn set_lock(&mut self, value: u32) {
+        NativeEndian::write_u32(&mut self.buffer.as_mut()[LOCK], value)
+    }
+}
+
+impl<T: AsRef<[u8]>> Parseable<FeaturesMessageBuffer<T>> for FeaturesMessage {
+    fn parse(buf: &FeaturesMessageBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self::try_parse(buf)?)
+    }
+}
+
+impl FeaturesMessage {
+    pub(crate) fn try_parse<T: AsRef<[u8]>>(
+        buf: &FeaturesMessageBuffer<T>,
+    ) -> Result<Self, AuditDecodeError> {
+        buf.check_buffer_length()?;
+        Ok(FeaturesMessage {
+            version: buf.version(),
+            mask: buf.mask(),
+            features: buf.features(),
+            lock: buf.lock(),
+        })
+    }
+}
+
+impl Emitable for FeaturesMessage {
+    fn buffer_len(&self) -> usize {
+        FEATURES_MESSAGE_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = FeaturesMessageBuffer::new(buffer);
+        buffer.set_version(self.version);
+        buffer.set_mask(self.mask);
+        buffer.set_features(self.features);
+        buffer.set_lock(self.lock);
+    }
+}
@@ -0,0 +1,243 @@
+// SPDX-License-Identifier: MIT
+
+//! `#[repr(C)]` mirrors of the structures used by libaudit, with
+//! conversions to and from the types of this crate, for programs that call
+//! into libaudit and want to use this crate for part of their work.
+#![allow(non_camel_case_types)]
+
+use std::{mem, slice};
+
+use netlink_packet_utils::traits::Emitable;
+
+use crate::{
+    constants::*,
+    rules::{RuleBuffer, RuleMessage},
+    AuditDecodeError, FeaturesMessage, StatusMessage, TtyStatusMessage,
+};
+
+/// `struct audit_status`
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct audit_status {
+    pub mask: u32,
+    pub enabled: u32,
+    pub failure: u32,
+    pub pid: u32,
+    pub rate_limit: u32,
+    pub backlog_limit: u32,
+    pub lost: u32,
+    pub backlog: u32,
+    /// Also known as `version`
+    pub feature_bitmap: u32,
+    pub backlog_wait_time: u32,
+    pub backlog_wait_time_actual: u32,
+}
+
+impl From<&StatusMessage> for audit_status {
+    fn from(status: &StatusMessage) -> Self {
+        audit_status {
+            mask: status.mask,
+            enabled: status.enabled,
+            failure: status.failure,
+            pid: status.pid,
+            rate_limit: status.rate_limiting,
+            backlog_limit: status.backlog_limit,
+            lost: status.lost,
+            backlog: status.backlog,
+            feature_bitmap: status.feature_bitmap,
+            backlog_wait_time: status.backlog_wait_time,
+            backlog_wait_time_actual: status.backlog_wait_time_actual,
+        }
+    }
+}
+
+impl From<&audit_status> for StatusMessage {
+    fn from(status: &audit_status) -> Self {
+        StatusMessage {
+            mask: status.mask,
+            enabled: status.enabled,
+            failure: status.failure,
+            pid: status.pid,
+            rate_limiting: status.rate_limit,
+            backlog_limit: status.backlog_limit,
+            lost: status.lost,
+            backlog: status.backlog,
+            feature_bitmap: status.feature_bitmap,
+            backlog_wait_time: status.backlog_wait_time,
+            backlog_wait_time_actual: status.backlog_wait_time_actual,
+        }
+    }
+}
+
+/// `struct audit_features`
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct audit_features {
+    pub vers: u32,
+    pub mask: u32,
+    pub features: u32,
+    pub lock: u32,
+}
+
+impl From<&FeaturesMessage> for audit_features {
+    fn from(features: &FeaturesMessage) -> Self {
+        audit_features {
+            vers: features.version,
+            mask: features.mask,
+            features: features.features,
+            lock: features.lock,
+        }
+    }
+}
+
+impl From<&audit_features> for FeaturesMessage {
+    fn from(features: &audit_features) -> Self {
+        FeaturesMessage {
+            version: features.vers,
+            mask: features.mask,
+            features: features.features,
+            lock: features.lock,
+        }
+    }
+}
+
+/// `struct audit_tty_status`
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct audit_tty_status {
+    pub enabled: u32,
+    pub log_passwd: u32,
+}
+
+impl From<&TtyStatusMessage> for audit_tty_status {
+    fn from(status: &TtyStatusMessage) -> Self {
+        audit_tty_status {
+            enabled: status.enabled,
+            log_passwd: status.log_passwd,
+        }
+    }
+}
+
+impl From<&audit_tty_status> for TtyStatusMessage {
+    fn from(status: &audit_tty_status) -> Self {
+        TtyStatusMessage {
+            enabled: status.enabled,
+            log_passwd: status.log_passwd,
+        }
+    }
+}
+
+/// `struct audit_rule_data`. It is followed by `buflen` bytes holding the
+/// string values of the fields.
+#[repr(C)]
+#[derive(Debug)]
+pub struct audit_rule_data {
+    pub flags: u32,
+    pub action: u32,
+    pub field_count: u32,
+    pub mask: [u32; AUDIT_BITMASK_SIZE],
+    pub fields: [u32; AUDIT_MAX_FIELDS],
+    pub values: [u32; AUDIT_MAX_FIELDS],
+    pub fieldflags: [u32; AUDIT_MAX_FIELDS],
+    pub buflen: u32,
+    pub buf: [u8; 0],
+}
+
+impl audit_rule_data {
+    /// Parse the rule, including the string values that follow the
+    /// structure.
+    ///
+    /// # Safety
+    ///
+    /// `self` must be followed by `self.buflen` readable bytes, as is the
+    /// case for the rules allocated by libaudit.
+    pub unsafe fn to_rule(&self) -> Result<RuleMessage, AuditDecodeError> {
+        let len = mem::size_of::<Self>() + self.buflen as usize;
+        let bytes =
+            slice::from_raw_parts(self as *const Self as *const u8, len);
+        RuleMessage::try_parse(&RuleBuffer::new(bytes))
+    }
+}
+
+/// Owned `struct audit_rule_data`, followed by its string values, built
+/// from a [`RuleMessage`]
+#[derive(Debug, Clone)]
+pub struct AuditRuleDataBuf {
+    // u32 words, so that the structure is properly aligned
+    words: Vec<u32>,
+}
+
+impl AuditRuleDataBuf {
+    pub fn as_ptr(&self) -> *const audit_rule_data {
+        self.words.as_ptr() as *const audit_rule_data
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut audit_rule_data {
+        self.words.as_mut_ptr() as *mut audit_rule_data
+    }
+
+    /// Size of the structure and of its string values, in bytes
+    pub fn byte_len(&self) -> usize {
+        mem::size_of::<audit_rule_data>() + self.rule().buflen as usize
+    }
+
+    pub fn rule(&self) -> &audit_rule_data {
+        // SAFETY: the buffer is aligned and at least as large as the
+        // structure
+        unsafe { &*self.as_ptr() }
+    }
+}
+
+impl From<&RuleMessage> for AuditRuleDataBuf {
+    fn from(rule: &RuleMessage) -> Self {
+        let len = rule.buffer_len();
+        let mut words = vec![0u32; len.div_ceil(4)];
+        // SAFETY: the vector holds at least `len` bytes
+        let bytes = unsafe {
+            slice::from_raw_parts_mut(words.as_mut_ptr() as *mut u8, len)
+        };
+        rule.emit(bytes);
+        AuditRuleDataBuf { words }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        rules::{RuleAction, RuleField, RuleFieldFlags, RuleFlags},
+        FEATURES_MESSAGE_LEN, STATUS_MESSAGE_LEN, TTY_STATUS_MESSAGE_LEN,
+    };
+
+    #[test]
+    fn test_layouts() {
+        assert_eq!(mem::size_of::<audit_status>(), STATUS_MESSAGE_LEN);
+        assert_eq!(mem::size_of::<audit_features>(), FEATURES_MESSAGE_LEN);
+        assert_eq!(mem::size_of::<audit_tty_status>(), TTY_STATUS_MESSAGE_LEN);
+        assert_eq!(
+            mem::size_of::<audit_rule_data>(),
+            crate::rules::RULE_BUF_MIN_LEN
+        );
+    }
+
+    #[test]
+    fn test_rule_roundtrip() {
+        let mut rule = RuleMessage::new();
+        rule.flags = RuleFlags::FilterExit;
+        rule.action = RuleAction::Always;
+        rule.fields
+            .push((RuleField::Filterkey("key".into()), RuleFieldFlags::Equal));
+        let buf = AuditRuleDataBuf::from(&rule);
+        assert_eq!(buf.rule().field_count, 1);
+        assert_eq!(buf.rule().buflen, 3);
+        assert_eq!(buf.byte_len(), rule.buffer_len());
+        assert_eq!(unsafe { buf.rule().to_rule() }, Ok(rule));
+
+        let status = StatusMessage {
+            enabled: 1,
+            pid: 42,
+            ..Default::default()
+        };
+        assert_eq!(StatusMessage::from(&audit_status::from(&status)), status);
+    }
+}
@@ -0,0 +1,323 @@
+// SPDX-License-Identifier: MIT
+
+//! Filtering of the records received from the kernel, before they are
+//! processed further.
+//!
+//! ```
+//! use netlink_packet_audit::{constants::*, filter::Filter};
+//!
+//! let mut filter = Filter::new()
+//!     .message_types([AUDIT_SYSCALL, AUDIT_PATH, AUDIT_EOE])
+//!     .uid_range("auid", 1000..=59999)
+//!     .key("exec")
+//!     .exe("/usr/bin/*");
+//! # let records: Vec<netlink_packet_audit::AuditMessage> = vec![];
+//! for record in records.iter().filter(|record| filter.accept(record)) {
+//!     println!("{record}");
+//! }
+//! ```
+
+use std::{borrow::Cow, collections::HashSet, ops::RangeInclusive};
+
+use crate::{AuditMessage, EventId, RecordField};
+
+/// Set of criteria records must meet.
+///
+/// All the configured criteria must be met, and each criterion is met if
+/// any of its values matches. Field criteria only apply to the records
+/// that have the field: `exe` or `key` only appear in the `SYSCALL`
+/// record of an event, not in its `PATH` or `CWD` records. Hex encoded
+/// values (such as the `exe` of a path containing spaces, or multiple
+/// keys) are decoded before being compared, quoted values are compared as
+/// is.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct Filter {
+    message_types: Option<HashSet<u16>>,
+    uid_ranges: Vec<(String, RangeInclusive<u32>)>,
+    keys: Vec<String>,
+    exes: Vec<String>,
+    syscalls: Option<HashSet<u32>>,
+    rejected: Option<EventId>,
+}
+
+impl Filter {
+    /// Return a filter that accepts every record
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Only accept the records of the given types
+    pub fn message_types<I>(mut self, message_types: I) -> Self
+    where
+        I: IntoIterator<Item = u16>,
+    {
+        self.message_types
+            .get_or_insert_with(HashSet::new)
+            .extend(message_types);
+        self
+    }
+
+    /// Only accept the records whose `field` (such as `uid` or `auid`) is
+    /// in `range`. Ranges given for the same field are alternatives.
+    pub fn uid_range(
+        mut self,
+        field: &str,
+        range: RangeInclusive<u32>,
+    ) -> Self {
+        self.uid_ranges.push((field.to_string(), range));
+        self
+    }
+
+    /// Only accept the records tagged with the given rule key
+    pub fn key(mut self, key: &str) -> Self {
+        self.keys.push(key.to_string());
+        self
+    }
+
+    /// Only accept the records whose `exe` matches the given pattern, in
+    /// which `*` matches any sequence of characters and `?` any single
+    /// character
+    pub fn exe(mut self, pattern: &str) -> Self {
+        self.exes.push(pattern.to_string());
+        self
+    }
+
+    /// Only accept the records of the given syscall numbers. The numbers
+    /// depend on the architecture (the `arch` field of the record), and
+    /// are compared as is whatever the `arch` of the record.
+    ///
+    /// Syscall names are not supported: the kernel only logs numbers, and
+    /// this crate has no per-architecture syscall table to resolve names
+    /// against. Callers that filter by name must resolve the names to the
+    /// numbers of the architecture they monitor, e.g. with `ausyscall`.
+    /// When several architectures are monitored, a number may match
+    /// different syscalls on each of them.
+    pub fn syscalls<I>(mut self, syscalls: I) -> Self
+    where
+        I: IntoIterator<Item = u32>,
+    {
+        self.syscalls
+            .get_or_insert_with(HashSet::new)
+            .extend(syscalls);
+        self
+    }
+
+    /// Return `true` if the record meets the criteria
+    pub fn matches(&self, message: &AuditMessage) -> bool {
+        if let Some(ref types) = self.message_types {
+            if !types.contains(&message.message_type().into()) {
+                return false;
+            }
+        }
+        self.matches_fields(message)
+    }
+
+    /// Return `true` if the record meets the criteria, and if it does not
+    /// belong to an event whose previous records were rejected for their
+    /// fields. Since the kernel sends the `SYSCALL` record of an event
+    /// first, this drops the other records of the events it rejects.
+    ///
+    /// The records must be passed in the order they were received.
+    pub fn accept(&mut self, message: &AuditMessage) -> bool {
+        let id = message.event_id();
+        if id.is_some() && id == self.rejected {
+            return false;
+        }
+        if !self.matches_fields(message) {
+            self.rejected = id;
+            return false;
+        }
+        self.matches(message)
+    }
+
+    /// Check the field criteria, in a single pass over the fields of the
+    /// record
+    fn matches_fields(&self, message: &AuditMessage) -> bool {
+        if self.uid_ranges.is_empty()
+            && self.keys.is_empty()
+            && self.exes.is_empty()
+            && self.syscalls.is_none()
+        {
+            return true;
+        }
+        let mut fields = match message.fields() {
+            Some(fields) => fields,
+            None => return true,
+        };
+        fields.all(|field| self.matches_field(&field))
+    }
+
+    /// Return `false` if the given field does not meet its criterion
+    fn matches_field(&self, field: &RecordField<'_>) -> bool {
+        let RecordField { name, value, .. } = *field;
+        match name {
+            "key" if !self.keys.is_empty() => field
+                .decoded()
+                .split('\x01')
+                .any(|key| self.keys.iter().any(|k| k == key)),
+            "exe" if !self.exes.is_empty() => {
+                let exe = field.decoded();
+                self.exes.iter().any(|pattern| glob_match(pattern, &exe))
+            }
+            "syscall" => self.syscalls.as_ref().is_none_or(|syscalls| {
+                value.parse().is_ok_and(|nr| syscalls.contains(&nr))
+            }),
+            _ => {
+                let mut ranges = self
+                    .uid_ranges
+                    .iter()
+                    .filter(|(n, _)| n == name)
+                    .peekable();
+                if ranges.peek().is_none() {
+                    return true;
+                }
+                let value = value.parse::<u32>().ok();
+                ranges
+                    .any(|(_, range)| value.is_some_and(|v| range.contains(&v)))
+            }
+        }
+    }
+}
+
+/// Decode the values the kernel logs as upper case hexadecimal. Other
+/// values are returned as is, without being copied.
+pub(crate) fn decoded(value: &str) -> Cow<'_, str> {
+    let hex = value.as_bytes();
+    if hex.is_empty() || !hex.len().is_multiple_of(2) {
+        return Cow::Borrowed(value);
+    }
+    let mut bytes = Vec::with_capacity(hex.len() / 2);
+    for pair in hex.chunks_exact(2) {
+        match (nibble(pair[0]), nibble(pair[1])) {
+            (Some(high), Some(low)) => bytes.push(high << 4 | low),
+            _ => return Cow::Borrowed(value),
+        }
+    }
+    match String::from_utf8(bytes) {
+        Ok(decoded) => Cow::Owned(decoded),
+        Err(e) => {
+            Cow::Owned(String::from_utf8_lossy(e.as_bytes()).into_owned())
+        }
+    }
+}
+
+/// Return the value of an upper case hexadecimal digit
+fn nibble(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Match `text` against a pattern in which `*` matches any sequence of
+/// characters, and `?` any single character
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    let (mut p, mut t) = (0, 0);
+    // position of the last `*`, and of the text it was matched against
+    let mut backtrack = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{constants::*, EventPayload};
+
+    fn record(message_type: u16, data: &str) -> AuditMessage {
+        AuditMessage::Event(EventPayload::new(
+            message_type.into(),
+            data.as_bytes().to_vec(),
+        ))
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("/usr/bin/*", "/usr/bin/ls"));
+        assert!(glob_match("*sh", "/bin/bash"));
+        assert!(glob_match("/bin/?ash", "/bin/bash"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("/usr/bin/*", "/bin/ls"));
+        assert!(!glob_match("/bin/?", "/bin/ls"));
+    }
+
+    #[test]
+    fn test_decoded() {
+        assert_eq!(decoded("2F62696E2F6C73"), "/bin/ls");
+        assert_eq!(decoded("6C73FF"), "ls\u{fffd}");
+        assert!(matches!(decoded("/bin/ls"), Cow::Borrowed("/bin/ls")));
+        assert!(matches!(decoded("2f62"), Cow::Borrowed(_)));
+        assert!(matches!(decoded("2F6"), Cow::Borrowed(_)));
+        assert!(matches!(decoded(""), Cow::Borrowed("")));
+    }
+
+    #[test]
+    fn test_filter() {
+        let syscall = |serial, auid, exe| {
+            record(
+                AUDIT_SYSCALL,
+                &format!(
+                    "audit(1.000:{serial}): arch=c000003e syscall=59 \
+                    auid={auid} exe={exe} key=6578656301726F6F74"
+                ),
+            )
+        };
+        let path = |serial| {
+            record(AUDIT_PATH, &format!("audit(1.000:{serial}): item=0"))
+        };
+        let mut filter = Filter::new()
+            .message_types([AUDIT_SYSCALL, AUDIT_PATH])
+            .uid_range("auid", 1000..=1999)
+            .uid_range("auid", 3000..=3999)
+            .key("root")
+            .exe("/usr/bin/*")
+            .syscalls([59]);
+
+        assert!(filter.accept(&syscall(1, 1000, "\"/usr/bin/ls\"")));
+        assert!(filter.accept(&path(1)));
+        assert!(!filter.accept(&record(AUDIT_CWD, "audit(1.000:1): cwd=\"/\"")));
+        // hex encoded exe
+        assert!(filter.accept(&syscall(2, 3500, "2F7573722F62696E2F612062")));
+        assert!(!filter.accept(&syscall(3, 2000, "\"/usr/bin/ls\"")));
+        assert!(!filter.accept(&path(3)));
+        assert!(!filter.accept(&syscall(4, 1000, "\"/bin/ls\"")));
+        assert!(filter.matches(&path(4)));
+        assert!(!filter.accept(&path(4)));
+
+        // quoted values are not decoded
+        let filter = Filter::new().key("CAFE");
+        let key =
+            |key| record(AUDIT_SYSCALL, &format!("audit(1.000:5): key={key}"));
+        assert!(filter.matches(&key("\"CAFE\"")));
+        assert!(filter.matches(&key("43414645")));
+        assert!(!filter.matches(&key("\"43414645\"")));
+
+        let filter = Filter::new().key("other").syscalls([2]);
+        assert!(!filter.matches(&syscall(1, 0, "\"/bin/ls\"")));
+        assert!(filter.matches(&AuditMessage::GetStatus(None)));
+        assert!(Filter::new().matches(&syscall(1, 0, "\"/bin/ls\"")));
+    }
+}
@@ -0,0 +1,230 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    fmt::Debug,
+    io,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::{Buf, BytesMut};
+use futures::{
+    io::{AsyncRead, AsyncWrite},
+    ready,
+    sink::Sink,
+    stream::Stream,
+};
+use netlink_packet_core::{
+    NetlinkDeserializable, NetlinkMessage, NetlinkSerializable,
+};
+
+use crate::{codec::NetlinkMessageCodec, AuditFramer, NetlinkAuditCodec};
+
+const READ_BUFFER_LEN: usize = 64 * 1024;
+/// Above this amount of buffered bytes, the pending messages are written
+/// before accepting new ones
+const WRITE_BUFFER_HIGH_WATER_MARK: usize = 64 * 1024;
+
+/// [`Stream`] and [`Sink`] of audit netlink messages over any
+/// [`AsyncRead`]/[`AsyncWrite`] I/O object, regardless of the async
+/// runtime.
+///
+/// Decoding is done by an [`AuditFramer`], which can be configured with
+/// [`AuditFramed::framer_mut`]. Each read is expected to return whole
+/// datagrams; when reading from a stream instead, partial frames should be
+/// retained with [`AuditFramer::set_retain_partial_frames`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct AuditFramed<S, T> {
+    io: S,
+    framer: AuditFramer,
+    read_buf: Vec<u8>,
+    write_buf: BytesMut,
+    eof: bool,
+    _message: PhantomData<fn() -> T>,
+}
+
+impl<S, T> AuditFramed<S, T> {
+    pub fn new(io: S) -> Self {
+        Self::with_framer(io, AuditFramer::new())
+    }
+
+    pub fn with_framer(io: S, framer: AuditFramer) -> Self {
+        AuditFramed {
+            io,
+            framer,
+            read_buf: vec![0; READ_BUFFER_LEN],
+            write_buf: BytesMut::new(),
+            eof: false,
+            _message: PhantomData,
+        }
+    }
+
+    pub fn framer(&self) -> &AuditFramer {
+        &self.framer
+    }
+
+    pub fn framer_mut(&mut self) -> &mut AuditFramer {
+        &mut self.framer
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.io
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.io
+    }
+
+    pub fn into_inner(self) -> S {
+        self.io
+    }
+}
+
+impl<S, T> Stream for AuditFramed<S, T>
+where
+    S: AsyncRead + Unpin,
+    T: NetlinkDeserializable + Debug,
+{
+    type Item = io::Result<NetlinkMessage<T>>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(msg) = this.framer.next_message() {
+                return Poll::Ready(Some(Ok(msg)));
+            }
+            if this.eof {
+                return Poll::Ready(None);
+            }
+            let n = ready!(
+                Pin::new(&mut this.io).poll_read(cx, &mut this.read_buf)
+            )?;
+            if n == 0 {
+                this.eof = true;
+                if let Err(e) = this.framer.finish() {
+                    return Poll::Ready(Some(Err(e)));
+                }
+            } else {
+                this.framer.push(&this.read_buf[..n])?;
+            }
+        }
+    }
+}
+
+impl<S, T> AuditFramed<S, T>
+where
+    S: AsyncWrite + Unpin,
+{
+    fn poll_write_buf(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while !self.write_buf.is_empty() {
+            let n =
+                ready!(Pin::new(&mut self.io).poll_write(cx, &self.write_buf))?;
+            if n == 0 {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write the buffered messages",
+                )));
+            }
+            self.write_buf.advance(n);
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<S, T> Sink<NetlinkMessage<T>> for AuditFramed<S, T>
+where
+    S: AsyncWrite + Unpin,
+    T: NetlinkSerializable + Debug,
+{
+    type Error = io::Error;
+
+    fn poll_ready(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if this.write_buf.len() >= WRITE_BUFFER_HIGH_WATER_MARK {
+            ready!(this.poll_write_buf(cx))?;
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(
+        self: Pin<&mut Self>,
+        item: NetlinkMessage<T>,
+    ) -> io::Result<()> {
+        NetlinkAuditCodec::encode(item, &mut self.get_mut().write_buf)
+    }
+
+    fn poll_flush(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_buf(cx))?;
+        Pin::new(&mut this.io).poll_flush(cx)
+    }
+
+    fn poll_close(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_write_buf(cx))?;
+        Pin::new(&mut this.io).poll_close(cx)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use futures::{executor::block_on, io::Cursor, SinkExt, StreamExt};
+    use netlink_packet_core::{NetlinkHeader, NetlinkPayload};
+
+    use super::*;
+    use crate::{constants::*, AuditMessage};
+
+    #[test]
+    fn test_sink_and_stream() {
+        let mut header = NetlinkHeader::default();
+        header.sequence_number = 1;
+        let msg = NetlinkMessage::new(
+            header,
+            NetlinkPayload::from(AuditMessage::ListRules(None)),
+        );
+
+        let mut framed = AuditFramed::new(Cursor::new(vec![]));
+        block_on(async {
+            framed.send(msg.clone()).await.unwrap();
+            framed.send(msg.clone()).await.unwrap();
+        });
+        let bytes = framed.into_inner().into_inner();
+        assert_eq!(bytes.len(), 32);
+
+        // each read returns a single datagram
+        let mut framed =
+            AuditFramed::<_, AuditMessage>::new(Cursor::new(bytes));
+        framed.read_buf.truncate(16);
+        let messages: Vec<_> = block_on(framed.collect());
+        assert_eq!(messages.len(), 2);
+        for decoded in messages {
+            let decoded = decoded.unwrap();
+            assert_eq!(decoded.header.message_type, AUDIT_LIST_RULES);
+            assert_eq!(decoded.payload, msg.payload);
+        }
+    }
+
+    #[test]
+    fn test_truncated_stream() {
+        let mut framed =
+            AuditFramed::<_, AuditMessage>::new(Cursor::new(vec![0x20, 0, 0]));
+        framed.framer_mut().set_retain_partial_frames(true);
+        let err = block_on(framed.next()).unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        assert!(block_on(framed.next()).is_none());
+    }
+}
@@ -0,0 +1,960 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    fmt::{self, Debug},
+    io,
+};
+
+use bytes::{Bytes, BytesMut};
+use netlink_packet_core::{
+    NetlinkBuffer, NetlinkDeserializable, NetlinkHeader, NetlinkMessage,
+    NETLINK_HEADER_LEN, NLMSG_OVERRUN,
+};
+use netlink_packet_utils::{
+    traits::{Parseable, ParseableParametrized},
+    DecodeError,
+};
+
+use crate::{
+    codec::{
+        deserialize_frame, is_partial_frame, next_frame, AuditCodecStats,
+        DecodeConfig, DecodeObserver, LengthPolicy,
+    },
+    into_audit_result, AuditBuffer, AuditMessage, AuditNetlinkError,
+    Endianness, SequenceGap, SequenceTracker, Utf8Policy,
+};
+
+/// A raw netlink frame, whose payload is not deserialized.
+///
+/// The payload is a view into the buffer of the [`AuditFramer`] it comes
+/// from, so obtaining a frame does not copy nor allocate.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct AuditFrame {
+    /// Netlink header, with the corrected length
+    pub header: NetlinkHeader,
+    /// Raw payload
+    pub payload: Bytes,
+    // whole frame, header included
+    frame: Bytes,
+}
+
+impl AuditFrame {
+    fn new(frame: BytesMut) -> Result<Self, (DecodeError, BytesMut)> {
+        let header = match NetlinkHeader::parse(&NetlinkBuffer::new(&frame)) {
+            Ok(header) => header,
+            Err(e) => return Err((e, frame)),
+        };
+        let frame = frame.freeze();
+        Ok(AuditFrame {
+            header,
+            payload: frame.slice(NETLINK_HEADER_LEN..),
+            frame,
+        })
+    }
+
+    /// Return the whole frame, header included
+    pub fn as_bytes(&self) -> &Bytes {
+        &self.frame
+    }
+
+    /// Deserialize the frame
+    pub fn parse<T>(&self) -> Result<NetlinkMessage<T>, DecodeError>
+    where
+        T: NetlinkDeserializable,
+    {
+        deserialize_frame(&self.frame)
+    }
+}
+
+/// Sans-IO decoder for audit netlink messages.
+///
+/// Unlike [`NetlinkAuditCodec`](crate::NetlinkAuditCodec), which is driven
+/// by `netlink-proto`, the framer is fed with the bytes read from the
+/// socket by the caller, and hands out the messages it decoded from them.
+/// It does not make any assumption about how the bytes are read, so it can
+/// be used with blocking sockets, `mio`, `io_uring` or any async runtime.
+///
+/// The same workarounds for the kernel audit length bugs as in
+/// `NetlinkAuditCodec` are applied, which requires each datagram to be
+/// drained with [`AuditFramer::next_message`] before the next one is
+/// pushed.
+///
+/// ```
+/// use netlink_packet_audit::{AuditFramer, AuditMessage};
+///
+/// let mut framer = AuditFramer::new();
+/// # let datagram: &[u8] = &[];
+/// framer.push(datagram).unwrap();
+/// while let Some(msg) = framer.next_message::<AuditMessage>() {
+///     println!("{msg:?}");
+/// }
+/// ```
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct AuditFramer {
+    buffer: BytesMut,
+    config: DecodeConfig,
+    max_buffer_len: Option<usize>,
+    buffer_capacity: usize,
+    utf8_policy: Utf8Policy,
+    stats: Option<AuditCodecStats>,
+    gap_handler: Option<GapHandler>,
+    malformed_handler: Option<Callback<MalformedFrame>>,
+    observer: Observer,
+}
+
+struct Observer(Box<dyn DecodeObserver + Send>);
+
+impl Default for Observer {
+    fn default() -> Self {
+        Observer(Box::new(()))
+    }
+}
+
+impl Debug for Observer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Observer")
+    }
+}
+
+/// Frame that could not be deserialized
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct MalformedFrame {
+    /// The raw frame
+    pub frame: AuditFrame,
+    /// Why it could not be deserialized
+    pub error: DecodeError,
+}
+
+struct Callback<T>(Box<dyn FnMut(T) + Send>);
+
+impl<T> Debug for Callback<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Callback")
+    }
+}
+
+#[derive(Debug)]
+struct GapHandler {
+    tracker: SequenceTracker,
+    callback: Callback<SequenceGap>,
+}
+
+impl AuditFramer {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn builder() -> AuditFramerBuilder {
+        AuditFramerBuilder::new()
+    }
+
+    /// Set how the length of the frames is handled
+    pub fn set_length_policy(&mut self, policy: LengthPolicy) {
+        self.config.length_policy = policy;
+    }
+
+    /// Skip the frames whose length exceed `len` bytes, instead of
+    /// deserializing them.
+    pub fn set_max_frame_len(&mut self, len: usize) {
+        self.config.max_frame_len = Some(len);
+    }
+
+    /// Limit the number of bytes that can be buffered. Pushing bytes that
+    /// would make the buffer grow beyond `len` fails.
+    pub fn set_max_buffer_len(&mut self, len: usize) {
+        self.max_buffer_len = Some(len);
+    }
+
+    /// Keep the incomplete frames in the internal buffer until the rest of
+    /// their bytes is pushed, instead of discarding them. This is needed
+    /// when the bytes do not come from a netlink socket, but from a stream
+    /// (a pipe, a file, ...), where a read may end in the middle of a frame.
+    ///
+    /// Because the kernel does not always count the header in the frame
+    /// length, a frame followed by less than 16 bytes cannot be delimited:
+    /// it is only decoded once more bytes are pushed, or once
+    /// [`AuditFramer::finish`] is called.
+    pub fn set_retain_partial_frames(&mut self, retain: bool) {
+        self.config.retain_partial_frames = retain;
+    }
+
+    /// Set the byte order of the frames, for instance to decode the traffic
+    /// captured on a machine of a different byte order. The frames are
+    /// converted to the native byte order before they are handed out: the
+    /// netlink headers, and the integer fields of the control messages
+    /// (status, rules, features, ...).
+    pub fn set_byte_order(&mut self, order: Endianness) {
+        self.config.byte_order = order;
+    }
+
+    /// Return the number of bytes in the internal buffer
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Signal the end of the stream. The partial frames are not retained
+    /// anymore, so the remaining frames can be drained.
+    ///
+    /// This fails with [`io::ErrorKind::UnexpectedEof`] if the buffer
+    /// starts with an incomplete frame, in which case the buffer is
+    /// cleared.
+    pub fn finish(&mut self) -> io::Result<()> {
+        self.config.retain_partial_frames = false;
+        if self.buffer.is_empty()
+            || !is_partial_frame(&self.buffer, self.config.byte_order)
+        {
+            return Ok(());
+        }
+        let len = self.buffer.len();
+        self.buffer.clear();
+        if let Some(ref mut stats) = self.stats {
+            stats.truncated_frames += 1;
+            stats.bytes_processed += len as u64;
+        }
+        Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            format!("stream ended in the middle of a {len} bytes frame"),
+        ))
+    }
+
+    /// Allocate the internal buffer by chunks of at least `capacity` bytes.
+    ///
+    /// Frames and payloads are views into these chunks. Once all the frames
+    /// taken from a chunk have been dropped, the chunk is recycled instead
+    /// of allocating a new one, so with a capacity large enough to hold a
+    /// few datagrams, a steady stream of messages can be decoded without
+    /// allocating a buffer for each of them.
+    pub fn set_buffer_capacity(&mut self, capacity: usize) {
+        self.buffer_capacity = capacity;
+    }
+
+    /// Set how [`AuditFramer::next_audit_message`] handles event payloads
+    /// that are not valid UTF-8. By default, payloads are kept as is.
+    pub fn set_utf8_policy(&mut self, policy: Utf8Policy) {
+        self.utf8_policy = policy;
+    }
+
+    /// Track the serial numbers of the events decoded by
+    /// [`AuditFramer::next_audit_message`], and call `callback` whenever a
+    /// gap is detected (see [`SequenceTracker`]). Gaps are reported once
+    /// they fall out of the [`DEFAULT_REORDER_WINDOW`].
+    ///
+    /// [`DEFAULT_REORDER_WINDOW`]: crate::DEFAULT_REORDER_WINDOW
+    pub fn on_sequence_gap<F>(&mut self, callback: F)
+    where
+        F: FnMut(SequenceGap) + Send + 'static,
+    {
+        self.gap_handler = Some(GapHandler {
+            tracker: SequenceTracker::new(),
+            callback: Callback(Box::new(callback)),
+        });
+    }
+
+    /// Set the observer notified of the decoder events, replacing the
+    /// previous one
+    pub fn set_observer<O>(&mut self, observer: O)
+    where
+        O: DecodeObserver + Send + 'static,
+    {
+        self.observer = Observer(Box::new(observer));
+    }
+
+    /// Call `callback` with the frames that cannot be deserialized. These
+    /// frames are skipped, and decoding goes on with the next frame.
+    pub fn on_malformed<F>(&mut self, callback: F)
+    where
+        F: FnMut(MalformedFrame) + Send + 'static,
+    {
+        self.malformed_handler = Some(Callback(Box::new(callback)));
+    }
+
+    /// Start keeping track of the decoder statistics, which can then be
+    /// retrieved with [`AuditFramer::stats`].
+    pub fn enable_stats(&mut self) {
+        self.stats.get_or_insert_with(Default::default);
+    }
+
+    /// Return the decoder statistics, if they are enabled
+    pub fn stats(&self) -> Option<&AuditCodecStats> {
+        self.stats.as_ref()
+    }
+
+    /// Append bytes read from the socket to the internal buffer.
+    ///
+    /// This fails with [`io::ErrorKind::OutOfMemory`] if the maximum
+    /// buffer length would be exceeded, in which case `bytes` is
+    /// discarded.
+    pub fn push(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if let Some(max) = self.max_buffer_len {
+            if self.buffer.len() + bytes.len() > max {
+                if let Some(ref mut stats) = self.stats {
+                    stats.rejected_bytes += bytes.len() as u64;
+                }
+                return Err(io::Error::new(
+                    io::ErrorKind::OutOfMemory,
+                    format!(
+                        "cannot buffer {} more bytes: {} bytes are already \
+                        buffered and the maximum is {max}",
+                        bytes.len(),
+                        self.buffer.len()
+                    ),
+                ));
+            }
+        }
+        if self.buffer.capacity() - self.buffer.len() < bytes.len() {
+            // `reserve` reclaims the current chunk if no frame refers to it
+            // anymore
+            self.buffer.reserve(bytes.len().max(self.buffer_capacity));
+        }
+        self.buffer.extend_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Return the next frame from the internal buffer without
+    /// deserializing its payload, or `None` if it does not hold any valid
+    /// frame anymore. Since the frame is not deserialized, it is not
+    /// counted in [`AuditCodecStats::frames_decoded`].
+    pub fn next_frame(&mut self) -> Option<AuditFrame> {
+        let mut scratch = AuditCodecStats::default();
+        let stats = self.stats.as_mut().unwrap_or(&mut scratch);
+        pop_frame(
+            &mut self.buffer,
+            &self.config,
+            stats,
+            self.observer.0.as_mut(),
+        )
+    }
+
+    /// Decode the next message from the internal buffer, or return `None`
+    /// if it does not hold any valid message anymore.
+    pub fn next_message<T>(&mut self) -> Option<NetlinkMessage<T>>
+    where
+        T: NetlinkDeserializable + Debug,
+    {
+        let mut scratch = AuditCodecStats::default();
+        let stats = self.stats.as_mut().unwrap_or(&mut scratch);
+        while let Some(frame) = pop_frame(
+            &mut self.buffer,
+            &self.config,
+            stats,
+            self.observer.0.as_mut(),
+        ) {
+            match frame.parse::<T>() {
+                Ok(packet) => {
+                    trace!("<<< {:?}", packet);
+                    stats.frames_decoded += 1;
+                    return Some(packet);
+                }
+                Err(error) => {
+                    stats.decode_errors += 1;
+                    self.observer.0.on_error(frame.as_bytes(), &error);
+                    report_malformed(&mut self.malformed_handler, frame, error);
+                }
+            }
+        }
+        None
+    }
+
+    /// Decode the next audit message, or the next error reported by the
+    /// kernel. Acknowledgements and the other netlink control messages are
+    /// skipped (see [`into_audit_result`]), as well as the messages that
+    /// cannot be parsed.
+    pub fn next_audit_message(
+        &mut self,
+    ) -> Option<Result<AuditMessage, AuditNetlinkError>> {
+        let mut scratch = AuditCodecStats::default();
+        let stats = self.stats.as_mut().unwrap_or(&mut scratch);
+        while let Some(frame) = pop_frame(
+            &mut self.buffer,
+            &self.config,
+            stats,
+            self.observer.0.as_mut(),
+        ) {
+            let message_type = frame.header.message_type;
+            let parsed = if message_type <= NLMSG_OVERRUN {
+                frame.parse::<AuditMessage>().map(into_audit_result)
+            } else {
+                AuditMessage::parse_with_param(
+                    &AuditBuffer::new(&frame.payload[..]),
+                    (message_type, self.utf8_policy),
+                )
+                .map(|msg| Some(Ok(msg)))
+            };
+            match parsed {
+                Ok(result) => {
+                    stats.frames_decoded += 1;
+                    if let (Some(Ok(msg)), Some(handler)) =
+                        (&result, self.gap_handler.as_mut())
+                    {
+                        handler.observe(msg);
+                    }
+                    if result.is_some() {
+                        return result;
+                    }
+                }
+                Err(error) => {
+                    stats.decode_errors += 1;
+                    self.observer.0.on_error(frame.as_bytes(), &error);
+                    report_malformed(&mut self.malformed_handler, frame, error);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl GapHandler {
+    fn observe(&mut self, message: &AuditMessage) {
+        if !message.is_event() {
+            return;
+        }
+        if let Some(id) = message.event_id() {
+            for gap in self.tracker.observe(id.serial) {
+                warn!(
+                    "{} events lost before serial {}",
+                    gap.count(),
+                    gap.received
+                );
+                (self.callback.0)(gap);
+            }
+        }
+    }
+}
+
+/// Builder for [`AuditFramer`]
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct AuditFramerBuilder {
+    framer: AuditFramer,
+    initial_capacity: usize,
+}
+
+impl AuditFramerBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Allocate `capacity` bytes for the internal buffer upfront. The
+    /// buffer then grows by chunks of at least this size (see
+    /// [`AuditFramer::set_buffer_capacity`]).
+    pub fn initial_capacity(mut self, capacity: usize) -> Self {
+        self.initial_capacity = capacity;
+        self
+    }
+
+    /// See [`AuditFramer::set_max_frame_len`]
+    pub fn max_frame_len(mut self, len: usize) -> Self {
+        self.framer.set_max_frame_len(len);
+        self
+    }
+
+    /// See [`AuditFramer::set_max_buffer_len`]
+    pub fn max_buffer_len(mut self, len: usize) -> Self {
+        self.framer.set_max_buffer_len(len);
+        self
+    }
+
+    /// See [`AuditFramer::set_length_policy`]
+    pub fn length_policy(mut self, policy: LengthPolicy) -> Self {
+        self.framer.set_length_policy(policy);
+        self
+    }
+
+    /// See [`AuditFramer::set_utf8_policy`]
+    pub fn utf8_policy(mut self, policy: Utf8Policy) -> Self {
+        self.framer.set_utf8_policy(policy);
+        self
+    }
+
+    /// See [`AuditFramer::set_retain_partial_frames`]
+    pub fn retain_partial_frames(mut self, retain: bool) -> Self {
+        self.framer.set_retain_partial_frames(retain);
+        self
+    }
+
+    /// See [`AuditFramer::set_byte_order`]
+    pub fn byte_order(mut self, order: Endianness) -> Self {
+        self.framer.set_byte_order(order);
+        self
+    }
+
+    /// Keep track of the decoder statistics (see
+    /// [`AuditFramer::enable_stats`])
+    pub fn stats(mut self, enable: bool) -> Self {
+        self.framer.stats = enable.then(Default::default);
+        self
+    }
+
+    pub fn build(self) -> AuditFramer {
+        let mut framer = self.framer;
+        if self.initial_capacity > 0 {
+            framer.set_buffer_capacity(self.initial_capacity);
+            framer.buffer.reserve(self.initial_capacity);
+        }
+        framer
+    }
+}
+
+fn report_malformed(
+    handler: &mut Option<Callback<MalformedFrame>>,
+    frame: AuditFrame,
+    error: DecodeError,
+) {
+    error!("failed to decode packet {:#x?}: {error}", frame);
+    if let Some(handler) = handler {
+        (handler.0)(MalformedFrame { frame, error });
+    }
+}
+
+/// Split the next frame from `buffer` and parse its header
+fn pop_frame(
+    buffer: &mut BytesMut,
+    config: &DecodeConfig,
+    stats: &mut AuditCodecStats,
+    observer: &mut dyn DecodeObserver,
+) -> Option<AuditFrame> {
+    while let Some(bytes) = next_frame(buffer, config, stats, observer) {
+        match AuditFrame::new(bytes) {
+            Ok(frame) => return Some(frame),
+            Err((e, bytes)) => {
+                error!("failed to decode netlink header: {e}");
+                stats.decode_errors += 1;
+                observer.on_error(&bytes, &e);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use netlink_packet_core::{
+        NetlinkHeader, NetlinkPayload, NLMSG_DONE, NLM_F_MULTIPART,
+    };
+    use netlink_packet_utils::traits::Emitable;
+
+    use super::*;
+    use crate::{
+        constants::*, AuditMessage, EventPayload, NetlinkAuditCodec,
+        StatusMessage,
+    };
+
+    #[test]
+    fn test_header_length_not_included() {
+        let data = "audit(1592992200.383:66): pid=1";
+        let mut header = NetlinkHeader::default();
+        header.message_type = AUDIT_SYSCALL;
+        // the kernel does not count the header
+        header.length = data.len() as u32;
+        let mut datagram = vec![0; header.buffer_len()];
+        header.emit(&mut datagram);
+        datagram.extend_from_slice(data.as_bytes());
+
+        let mut framer = AuditFramer::new();
+        framer.enable_stats();
+        framer.push(&datagram).unwrap();
+        let msg = framer.next_message::<AuditMessage>().unwrap();
+        assert_eq!(
+            msg.payload,
+            NetlinkPayload::InnerMessage(AuditMessage::Event(
+                EventPayload::new(AUDIT_SYSCALL.into(), data.into())
+            ))
+        );
+        assert!(framer.next_message::<AuditMessage>().is_none());
+
+        let stats = framer.stats().unwrap();
+        assert_eq!(stats.frames_decoded, 1);
+        assert_eq!(stats.bytes_processed, datagram.len() as u64);
+        assert_eq!(stats.length_corrections, 1);
+        assert_eq!(stats.truncated_frames, 0);
+        assert_eq!(stats.decode_errors, 0);
+    }
+
+    #[test]
+    fn test_next_frame() {
+        let data = "audit(1592992200.383:66): pid=1";
+        let mut header = NetlinkHeader::default();
+        header.message_type = AUDIT_SYSCALL;
+        header.length = (header.buffer_len() + data.len()) as u32;
+        let mut datagram = vec![0; header.buffer_len()];
+        header.emit(&mut datagram);
+        datagram.extend_from_slice(data.as_bytes());
+
+        let mut framer = AuditFramer::new();
+        framer.enable_stats();
+        framer.push(&datagram).unwrap();
+        let frame = framer.next_frame().unwrap();
+        assert!(framer.next_frame().is_none());
+        // the frame was not deserialized
+        let stats = framer.stats().unwrap();
+        assert_eq!(stats.frames_decoded, 0);
+        assert_eq!(stats.bytes_processed, datagram.len() as u64);
+        assert_eq!(frame.header, header);
+        assert_eq!(frame.payload, data.as_bytes());
+        assert_eq!(frame.as_bytes(), &datagram[..]);
+        assert_eq!(
+            frame.parse::<AuditMessage>().unwrap().payload,
+            NetlinkPayload::InnerMessage(AuditMessage::Event(
+                EventPayload::new(AUDIT_SYSCALL.into(), data.into())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_empty_done() {
+        // sent by the kernel after the last rule of an AUDIT_LIST_RULES
+        // dump
+        let mut header = NetlinkHeader::default();
+        header.message_type = NLMSG_DONE;
+        header.flags = NLM_F_MULTIPART;
+        header.sequence_number = 3;
+        header.length = header.buffer_len() as u32;
+        let mut datagram = vec![0; header.buffer_len()];
+        header.emit(&mut datagram);
+
+        let mut framer = AuditFramer::new();
+        framer.push(&datagram).unwrap();
+        let msg = framer.next_message::<AuditMessage>().unwrap();
+        assert_eq!(msg.header, header);
+        assert!(matches!(msg.payload, NetlinkPayload::Done(_)));
+    }
+
+    #[test]
+    fn test_size_limits() {
+        let mut header = NetlinkHeader::default();
+        header.message_type = AUDIT_SYSCALL;
+        header.length = 64;
+        let mut datagram = vec![0; 64];
+        header.emit(&mut datagram);
+
+        let mut framer = AuditFramer::new();
+        framer.enable_stats();
+        framer.set_max_frame_len(32);
+        framer.set_max_buffer_len(100);
+        framer.push(&datagram).unwrap();
+        assert_eq!(
+            framer.push(&datagram).unwrap_err().kind(),
+            io::ErrorKind::OutOfMemory
+        );
+        assert!(framer.next_message::<AuditMessage>().is_none());
+
+        let stats = framer.stats().unwrap();
+        assert_eq!(stats.oversized_frames, 1);
+        assert_eq!(stats.rejected_bytes, 64);
+        assert_eq!(stats.frames_decoded, 0);
+    }
+
+    #[test]
+    fn test_buffer_recycling() {
+        let mut header = NetlinkHeader::default();
+        header.message_type = AUDIT_EOE;
+        header.length = 32;
+        let mut datagram = vec![0; 32];
+        header.emit(&mut datagram);
+
+        let mut framer = AuditFramer::new();
+        framer.set_buffer_capacity(4096);
+        framer.push(&datagram).unwrap();
+        let frame = framer.next_frame().unwrap();
+        let ptr = frame.as_bytes().as_ptr();
+        framer.push(&datagram).unwrap();
+        let frame2 = framer.next_frame().unwrap();
+        // the chunk is not full yet
+        assert_eq!(frame2.as_bytes().as_ptr(), ptr.wrapping_add(32));
+        drop((frame, frame2));
+
+        for _ in 0..1000 {
+            framer.push(&datagram).unwrap();
+            let frame = framer.next_frame().unwrap();
+            assert_eq!(frame.as_bytes(), &datagram[..]);
+        }
+        // the first chunk has been reused
+        framer.push(&datagram).unwrap();
+        let frame = framer.next_frame().unwrap();
+        assert!(
+            frame.as_bytes().as_ptr() >= ptr
+                && frame.as_bytes().as_ptr() < ptr.wrapping_add(4096)
+        );
+    }
+
+    #[test]
+    fn test_utf8_policy() {
+        let data = b"audit(1592992200.383:66): data=\xff";
+        let mut header = NetlinkHeader::default();
+        header.message_type = AUDIT_TTY;
+        header.length = (header.buffer_len() + data.len()) as u32;
+        let mut datagram = vec![0; header.buffer_len()];
+        header.emit(&mut datagram);
+        datagram.extend_from_slice(data);
+
+        let mut framer = AuditFramer::new();
+        framer.enable_stats();
+        framer.push(&datagram).unwrap();
+        let msg = framer.next_audit_message().unwrap().unwrap();
+        assert_eq!(
+            msg,
+            AuditMessage::Event(EventPayload::new(
+                AUDIT_TTY.into(),
+                data.to_vec()
+            ))
+        );
+        assert_eq!(
+            msg.data_lossy().unwrap(),
+            "audit(1592992200.383:66): data=\u{fffd}"
+        );
+
+        framer.set_utf8_policy(Utf8Policy::Strict);
+        framer.push(&datagram).unwrap();
+        assert!(framer.next_audit_message().is_none());
+        assert_eq!(framer.stats().unwrap().decode_errors, 1);
+        assert_eq!(framer.stats().unwrap().frames_decoded, 1);
+
+        framer.set_utf8_policy(Utf8Policy::Lossy);
+        framer.push(&datagram).unwrap();
+        assert_eq!(
+            framer.next_audit_message(),
+            Some(Ok(AuditMessage::Event(EventPayload::new(
+                AUDIT_TTY.into(),
+                "audit(1592992200.383:66): data=\u{fffd}".into()
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_sequence_gap() {
+        use std::sync::{Arc, Mutex};
+
+        let gaps = Arc::new(Mutex::new(vec![]));
+        let mut framer = AuditFramer::new();
+        let gaps2 = gaps.clone();
+        framer.on_sequence_gap(move |gap| gaps2.lock().unwrap().push(gap));
+
+        for serial in [1, 1, 2, 5, 4, 6, 8, 40] {
+            let data = format!("audit(1592992200.383:{serial}): pid=1");
+            let mut header = NetlinkHeader::default();
+            header.message_type = AUDIT_SYSCALL;
+            header.length = (header.buffer_len() + data.len()) as u32;
+            let mut datagram = vec![0; header.buffer_len()];
+            header.emit(&mut datagram);
+            datagram.extend_from_slice(data.as_bytes());
+            framer.push(&datagram).unwrap();
+            assert!(framer.next_audit_message().is_some());
+        }
+
+        let gaps = gaps.lock().unwrap();
+        assert_eq!(gaps.len(), 2);
+        assert_eq!(gaps[0].expected, 3);
+        assert_eq!(gaps[0].count(), 1);
+        assert_eq!(gaps[1].expected, 7);
+        assert_eq!(gaps[1].count(), 1);
+    }
+
+    #[test]
+    fn test_partial_frames() {
+        let data = "audit(1592992200.383:66): pid=1";
+        let mut header = NetlinkHeader::default();
+        header.message_type = AUDIT_SYSCALL;
+        header.length = (header.buffer_len() + data.len()) as u32;
+        let mut datagram = vec![0; header.buffer_len()];
+        header.emit(&mut datagram);
+        datagram.extend_from_slice(data.as_bytes());
+
+        let mut framer = AuditFramer::new();
+        framer.set_retain_partial_frames(true);
+        framer.push(&datagram[..10]).unwrap();
+        assert!(framer.next_frame().is_none());
+        framer.push(&datagram[10..30]).unwrap();
+        assert!(framer.next_frame().is_none());
+        assert_eq!(framer.buffered_len(), 30);
+        framer.push(&datagram[30..]).unwrap();
+        framer.push(&datagram[..20]).unwrap();
+        assert_eq!(framer.next_frame().unwrap().as_bytes(), &datagram[..]);
+        assert!(framer.next_frame().is_none());
+        assert_eq!(framer.buffered_len(), 20);
+
+        assert_eq!(
+            framer.finish().unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        assert_eq!(framer.buffered_len(), 0);
+        framer.finish().unwrap();
+    }
+
+    #[test]
+    fn test_partial_frames_header_length_not_included() {
+        let data = "audit(1592992200.383:66): pid=1";
+        let mut header = NetlinkHeader::default();
+        header.message_type = AUDIT_SYSCALL;
+        header.length = data.len() as u32;
+        let mut datagram = vec![0; header.buffer_len()];
+        header.emit(&mut datagram);
+        datagram.extend_from_slice(data.as_bytes());
+
+        let mut framer = AuditFramer::new();
+        framer.set_retain_partial_frames(true);
+        framer.push(&datagram).unwrap();
+        assert!(framer.next_frame().is_none());
+        framer.finish().unwrap();
+        let frame = framer.next_frame().unwrap();
+        assert_eq!(frame.payload, data.as_bytes());
+        assert_eq!(framer.buffered_len(), 0);
+    }
+
+    #[test]
+    fn test_malformed_frames() {
+        use std::sync::{Arc, Mutex};
+
+        // AUDIT_GET reply that is too short
+        let mut header = NetlinkHeader::default();
+        header.message_type = AUDIT_GET;
+        header.length = 20;
+        let mut invalid = vec![0; 20];
+        header.emit(&mut invalid);
+        let data = "audit(1592992200.383:66): pid=1";
+        header.message_type = AUDIT_SYSCALL;
+        header.length = (header.buffer_len() + data.len()) as u32;
+        let mut valid = vec![0; header.buffer_len()];
+        header.emit(&mut valid);
+        valid.extend_from_slice(data.as_bytes());
+
+        let malformed = Arc::new(Mutex::new(vec![]));
+        let malformed2 = malformed.clone();
+        let mut framer = AuditFramer::new();
+        framer.on_malformed(move |m| malformed2.lock().unwrap().push(m));
+        framer.push(&invalid).unwrap();
+        assert!(framer.next_message::<AuditMessage>().is_none());
+        framer.push(&invalid).unwrap();
+        framer.push(&valid).unwrap();
+        assert_eq!(
+            framer.next_audit_message(),
+            Some(Ok(AuditMessage::Event(EventPayload::new(
+                AUDIT_SYSCALL.into(),
+                data.into()
+            ))))
+        );
+
+        let malformed = malformed.lock().unwrap();
+        assert_eq!(malformed.len(), 2);
+        assert_eq!(malformed[0].frame.as_bytes(), &invalid[..]);
+        assert_eq!(malformed[1].frame.header.message_type, AUDIT_GET);
+    }
+
+    #[test]
+    fn test_observer() {
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Default)]
+        struct Events {
+            frames: Vec<Vec<u8>>,
+            fixups: Vec<(u32, u32)>,
+            errors: usize,
+        }
+
+        struct Recorder(Arc<Mutex<Events>>);
+
+        impl DecodeObserver for Recorder {
+            fn on_frame(&mut self, frame: &[u8]) {
+                self.0.lock().unwrap().frames.push(frame.to_vec());
+            }
+
+            fn on_length_fixup(&mut self, original: u32, corrected: u32) {
+                self.0.lock().unwrap().fixups.push((original, corrected));
+            }
+
+            fn on_error(&mut self, _bytes: &[u8], _error: &DecodeError) {
+                self.0.lock().unwrap().errors += 1;
+            }
+        }
+
+        let data = "audit(1592992200.383:66): pid=1";
+        let mut header = NetlinkHeader::default();
+        header.message_type = AUDIT_SYSCALL;
+        header.length = data.len() as u32;
+        let mut datagram = vec![0; header.buffer_len()];
+        header.emit(&mut datagram);
+        datagram.extend_from_slice(data.as_bytes());
+
+        let events = Arc::new(Mutex::new(Events::default()));
+        let mut framer = AuditFramer::new();
+        framer.set_observer(Recorder(events.clone()));
+        framer.push(&datagram).unwrap();
+        assert!(framer.next_audit_message().is_some());
+        framer.push(&[0x10, 0x00, 0x00]).unwrap();
+        assert!(framer.next_audit_message().is_none());
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.frames.len(), 1);
+        assert_eq!(events.frames[0].len(), datagram.len());
+        assert_eq!(events.fixups, vec![(31, 47)]);
+        assert_eq!(events.errors, 1);
+    }
+
+    #[test]
+    fn test_builder() {
+        let data = "audit(1592992200.383:66): pid=1";
+        let mut header = NetlinkHeader::default();
+        header.message_type = AUDIT_SYSCALL;
+        header.length = 16 + 20;
+        let mut datagram = vec![0; header.buffer_len()];
+        header.emit(&mut datagram);
+        datagram.extend_from_slice(data.as_bytes());
+
+        let mut framer = NetlinkAuditCodec::builder()
+            .initial_capacity(1024)
+            .length_policy(LengthPolicy::Strict)
+            .utf8_policy(Utf8Policy::Lossy)
+            .stats(true)
+            .build();
+        assert!(framer.buffer.capacity() >= 1024);
+        framer.push(&datagram).unwrap();
+        // the length is trusted, even though it looks wrong
+        let frame = framer.next_frame().unwrap();
+        assert_eq!(frame.payload, data.as_bytes()[..20]);
+        assert_eq!(framer.stats().unwrap().length_corrections, 0);
+
+        let framer = AuditFramer::builder().stats(false).build();
+        assert!(framer.stats().is_none());
+    }
+
+    #[test]
+    fn test_byte_order() {
+        let status = AuditMessage::GetStatus(Some(StatusMessage {
+            pid: 42,
+            ..Default::default()
+        }));
+        let mut msg = NetlinkMessage::from(status.clone());
+        msg.finalize();
+        let mut native = vec![0; msg.buffer_len()];
+        msg.serialize(&mut native);
+
+        for order in [Endianness::Little, Endianness::Big] {
+            // the header and the status only have 32 bits words, except
+            // the message type and flags
+            let mut datagram = native.clone();
+            for word in datagram.chunks_exact_mut(4) {
+                let value = Endianness::native().read_u32(word);
+                order.write_u32(word, value);
+            }
+            if !order.is_native() {
+                datagram[4..8].rotate_left(2);
+            }
+            let mut framer = AuditFramer::builder().byte_order(order).build();
+            framer.push(&datagram).unwrap();
+            assert_eq!(framer.next_audit_message(), Some(Ok(status.clone())));
+        }
+    }
+
+    #[test]
+    fn test_truncated_datagram() {
+        let mut framer = AuditFramer::new();
+        framer.enable_stats();
+        framer.push(&[0x10, 0x00, 0x00]).unwrap();
+        assert!(framer.next_message::<AuditMessage>().is_none());
+        let stats = framer.stats().unwrap();
+        assert_eq!(stats.truncated_frames, 1);
+        assert_eq!(stats.bytes_processed, 3);
+    }
+}
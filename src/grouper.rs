@@ -0,0 +1,319 @@
+// SPDX-License-Identifier: MIT
+
+use std::{collections::VecDeque, io};
+
+use crate::{constants::*, AuditMessage, EventId};
+
+/// Limits on the records the event writers ([`AusearchWriter`],
+/// [`SiemWriter`], and `JsonWriter`) buffer while waiting for the end of
+/// the events.
+///
+/// When a limit is reached, events are written before their last record is
+/// received: the records received afterwards make up another event with
+/// the same serial number.
+///
+/// [`AusearchWriter`]: crate::AusearchWriter
+/// [`SiemWriter`]: crate::SiemWriter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct EventLimits {
+    max_pending_events: usize,
+    max_records_per_event: usize,
+    max_buffered_bytes: usize,
+}
+
+impl Default for EventLimits {
+    fn default() -> Self {
+        EventLimits {
+            max_pending_events: 32,
+            max_records_per_event: 1024,
+            max_buffered_bytes: 1 << 20,
+        }
+    }
+}
+
+impl EventLimits {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Maximum number of events waiting for their last record (32 by
+    /// default). When a record starts another one, the oldest pending
+    /// event is written.
+    pub fn max_pending_events(mut self, max: usize) -> Self {
+        self.max_pending_events = max;
+        self
+    }
+
+    /// Maximum number of records of an event (1024 by default, an
+    /// `EXECVE` record is logged for each argument of a long command
+    /// line). The event is written when it reaches this size.
+    pub fn max_records_per_event(mut self, max: usize) -> Self {
+        self.max_records_per_event = max;
+        self
+    }
+
+    /// Maximum size of the payloads of all the buffered records (1 MiB by
+    /// default). The oldest pending events are written until the records
+    /// fit.
+    pub fn max_buffered_bytes(mut self, max: usize) -> Self {
+        self.max_buffered_bytes = max;
+        self
+    }
+}
+
+/// Group the records of each event, for the writers that output complete
+/// events.
+///
+/// The records of concurrent events may be interleaved, so the records of
+/// each event are buffered separately until its last record is received
+/// (see [`ends_event`]), until an [`EventLimits`] is reached, or until
+/// [`EventGrouper::flush`] is called. `EOE` records are dropped. Messages
+/// that do not belong to an event, such as the replies to control
+/// requests, are passed on their own.
+#[derive(Debug, Default)]
+pub(crate) struct EventGrouper {
+    limits: EventLimits,
+    /// Events waiting for their last record, oldest first
+    pending: VecDeque<(EventId, Vec<AuditMessage>)>,
+    buffered_bytes: usize,
+    forced_flushes: u64,
+}
+
+impl EventGrouper {
+    pub(crate) fn set_limits(&mut self, limits: EventLimits) {
+        self.limits = limits;
+    }
+
+    /// Number of events written before their last record was received,
+    /// because of the limits
+    pub(crate) fn forced_flushes(&self) -> u64 {
+        self.forced_flushes
+    }
+
+    /// Add a message, and call `write` with the records of the events it
+    /// completes, if any
+    pub(crate) fn push<F>(
+        &mut self,
+        message: &AuditMessage,
+        mut write: F,
+    ) -> io::Result<()>
+    where
+        F: FnMut(Option<EventId>, &[AuditMessage]) -> io::Result<()>,
+    {
+        let id = match message.event_id() {
+            Some(id) => id,
+            None => return write(None, std::slice::from_ref(message)),
+        };
+        let message_type = message.message_type().into();
+        let position = self.pending.iter().position(|(i, _)| *i == id);
+        let index = match position {
+            Some(index) => index,
+            None if message_type == AUDIT_EOE => return Ok(()),
+            None => {
+                if self.pending.len() >= self.limits.max_pending_events {
+                    self.force_flush(0, &mut write)?;
+                }
+                self.pending.push_back((id, Vec::new()));
+                self.pending.len() - 1
+            }
+        };
+        if message_type != AUDIT_EOE {
+            self.buffered_bytes += record_size(message);
+            self.pending[index].1.push(message.clone());
+        }
+        if ends_event(message_type) {
+            if let Some((id, records)) = self.take(index) {
+                if !records.is_empty() {
+                    write(Some(id), &records)?;
+                }
+            }
+        } else if self.pending[index].1.len()
+            >= self.limits.max_records_per_event
+        {
+            self.force_flush(index, &mut write)?;
+        }
+        while self.buffered_bytes > self.limits.max_buffered_bytes
+            && !self.pending.is_empty()
+        {
+            self.force_flush(0, &mut write)?;
+        }
+        Ok(())
+    }
+
+    /// Call `write` with the records of each pending event, oldest first
+    pub(crate) fn flush<F>(&mut self, mut write: F) -> io::Result<()>
+    where
+        F: FnMut(Option<EventId>, &[AuditMessage]) -> io::Result<()>,
+    {
+        while let Some((id, records)) = self.take(0) {
+            write(Some(id), &records)?;
+        }
+        Ok(())
+    }
+
+    /// Write the pending event at `index` before its last record
+    fn force_flush<F>(&mut self, index: usize, write: F) -> io::Result<()>
+    where
+        F: FnOnce(Option<EventId>, &[AuditMessage]) -> io::Result<()>,
+    {
+        match self.take(index) {
+            Some((id, records)) => {
+                self.forced_flushes += 1;
+                write(Some(id), &records)
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn take(&mut self, index: usize) -> Option<(EventId, Vec<AuditMessage>)> {
+        let (id, records) = self.pending.remove(index)?;
+        self.buffered_bytes -= records.iter().map(record_size).sum::<usize>();
+        Some((id, records))
+    }
+}
+
+fn record_size(message: &AuditMessage) -> usize {
+    message.data().map_or(0, <[u8]>::len)
+}
+
+/// Return `true` if a record of the given type is the last one of its
+/// event. As with auparse, these are the `EOE` and `PROCTITLE` records
+/// that end the syscall events, and the types of the events made of a
+/// single record (userspace, daemon and anomaly messages...).
+pub(crate) fn ends_event(message_type: u16) -> bool {
+    matches!(
+        message_type,
+        AUDIT_EOE
+            | AUDIT_PROCTITLE
+            | AUDIT_USER
+            | AUDIT_KERNEL
+            | AUDIT_MAC_UNLBL_ALLOW..=AUDIT_MAC_CALIPSO_DEL
+            | AUDIT_FIRST_ANOM_MSG..
+    ) || (AUDIT_LOGIN + 1..AUDIT_EVENT_MESSAGE_MIN).contains(&message_type)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::EventPayload;
+
+    fn record(message_type: u16, serial: u32) -> AuditMessage {
+        AuditMessage::Event(EventPayload::new(
+            message_type.into(),
+            format!("audit(1.000:{serial}): x=1").into_bytes(),
+        ))
+    }
+
+    fn push_all(
+        grouper: &mut EventGrouper,
+        messages: &[AuditMessage],
+    ) -> Vec<(Option<u32>, Vec<u16>)> {
+        let mut events = vec![];
+        let mut write = |id: Option<EventId>, records: &[AuditMessage]| {
+            events.push((
+                id.map(|id| id.serial),
+                records.iter().map(|r| r.message_type().into()).collect(),
+            ));
+            Ok(())
+        };
+        for message in messages {
+            grouper.push(message, &mut write).unwrap();
+        }
+        grouper.flush(&mut write).unwrap();
+        events
+    }
+
+    #[test]
+    fn test_interleaved() {
+        let mut grouper = EventGrouper::default();
+        let events = push_all(
+            &mut grouper,
+            &[
+                record(AUDIT_SYSCALL, 1),
+                record(AUDIT_SYSCALL, 2),
+                record(AUDIT_CWD, 1),
+                record(AUDIT_USER_LOGIN, 3),
+                record(AUDIT_PATH, 2),
+                AuditMessage::GetStatus(None),
+                record(AUDIT_EOE, 2),
+                record(AUDIT_PROCTITLE, 1),
+                record(AUDIT_EOE, 1),
+                record(AUDIT_SYSCALL, 4),
+            ],
+        );
+        assert_eq!(
+            events,
+            vec![
+                (Some(3), vec![AUDIT_USER_LOGIN]),
+                (None, vec![AUDIT_GET]),
+                (Some(2), vec![AUDIT_SYSCALL, AUDIT_PATH]),
+                (Some(1), vec![AUDIT_SYSCALL, AUDIT_CWD, AUDIT_PROCTITLE]),
+                (Some(4), vec![AUDIT_SYSCALL]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_max_pending() {
+        const MAX_PENDING_EVENTS: usize = 32;
+        let mut grouper = EventGrouper::default();
+        let messages: Vec<_> = (0..=MAX_PENDING_EVENTS as u32)
+            .map(|serial| record(AUDIT_SYSCALL, serial))
+            .chain([record(AUDIT_CWD, 0), record(AUDIT_EOE, 0)])
+            .collect();
+        let events = push_all(&mut grouper, &messages);
+        assert_eq!(events.len(), MAX_PENDING_EVENTS + 2);
+        // the oldest event was written when the last one started, and its
+        // late record started a new event, evicting the next oldest one
+        assert_eq!(events[0], (Some(0), vec![AUDIT_SYSCALL]));
+        assert_eq!(events[1], (Some(1), vec![AUDIT_SYSCALL]));
+        assert_eq!(events[2], (Some(0), vec![AUDIT_CWD]));
+        assert_eq!(events[3], (Some(2), vec![AUDIT_SYSCALL]));
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut grouper = EventGrouper::default();
+        grouper.set_limits(EventLimits::new().max_records_per_event(2));
+        let events = push_all(
+            &mut grouper,
+            &[
+                record(AUDIT_SYSCALL, 1),
+                record(AUDIT_EXECVE, 1),
+                record(AUDIT_PATH, 1),
+                record(AUDIT_EOE, 1),
+            ],
+        );
+        assert_eq!(
+            events,
+            vec![
+                (Some(1), vec![AUDIT_SYSCALL, AUDIT_EXECVE]),
+                (Some(1), vec![AUDIT_PATH]),
+            ]
+        );
+        assert_eq!(grouper.forced_flushes(), 1);
+
+        // each record is 19 bytes long
+        let mut grouper = EventGrouper::default();
+        grouper.set_limits(EventLimits::new().max_buffered_bytes(40));
+        let events = push_all(
+            &mut grouper,
+            &[
+                record(AUDIT_SYSCALL, 1),
+                record(AUDIT_SYSCALL, 2),
+                record(AUDIT_CWD, 1),
+                record(AUDIT_EOE, 2),
+            ],
+        );
+        assert_eq!(
+            events,
+            vec![
+                (Some(1), vec![AUDIT_SYSCALL, AUDIT_CWD]),
+                (Some(2), vec![AUDIT_SYSCALL]),
+            ]
+        );
+        assert_eq!(grouper.forced_flushes(), 1);
+        assert_eq!(grouper.buffered_bytes, 0);
+    }
+}
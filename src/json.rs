@@ -0,0 +1,480 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    io::{self, Write},
+};
+
+use crate::{
+    filter::decoded, grouper::EventGrouper, AuditMessage, AuditMessageType,
+    EventId, EventLimits, RecordFields,
+};
+
+/// Fields the kernel logs in hexadecimal, without prefix
+const HEX_FIELDS: [&str; 5] = ["arch", "a0", "a1", "a2", "a3"];
+
+/// Write audit events as JSON lines, one object per event:
+///
+/// ```text
+/// {"time":"2020-06-24T09:50:00.383Z","serial":66,"records":[{"type":"SYSCALL","arch":"c000003e","syscall":59,...},{"type":"CWD","cwd":"/"}]}
+/// ```
+///
+/// The records of an event are grouped as with
+/// [`AusearchWriter`](crate::AusearchWriter), but are written in the order
+/// they were received. Messages that do not belong to an event are written
+/// on their own, without `time` and `serial`.
+///
+/// Each record is an object with its `type` and its fields. The values
+/// are interpreted:
+/// - the hex encoded strings (such as the `exe` or `name` of a path
+///   containing spaces) are decoded, the quoted ones are written as is.
+///   The arguments of `proctitle` are
+///   separated by spaces.
+/// - decimal integers are written as numbers, except for the fields the
+///   kernel logs in hexadecimal (`arch`, `a0` to `a3` and the capability
+///   sets).
+///
+/// Field names can be mapped to other keys with
+/// [`JsonWriter::map_field`]. If several fields of a record have the same
+/// key, only the first one is written.
+///
+/// With [`JsonWriter::coalesced`], the records of an event are merged into
+/// a single object keyed by record type instead, as
+/// [laurel](https://github.com/threathunters-io/laurel) does:
+///
+/// ```text
+/// {"ID":"1592992200.383:66","SYSCALL":{"arch":"c000003e","syscall":59,...},"EXECVE":{"argc":2,"ARGV":["ls","-l"]},"PATH":[{"item":0,"name":"/bin/ls"}],"PROCTITLE":{"ARGV":["ls","-l"]}}
+/// ```
+///
+/// The arguments of the `EXECVE` records of an event are joined in a
+/// single `ARGV` array, as are the arguments of `PROCTITLE`. `PATH` records
+/// are written as an array, as are the other types that appear several
+/// times in the event.
+#[derive(Debug)]
+pub struct JsonWriter<W> {
+    writer: W,
+    keys: HashMap<String, String>,
+    coalesced: bool,
+    grouper: EventGrouper,
+}
+
+impl<W: Write> JsonWriter<W> {
+    pub fn new(writer: W) -> Self {
+        JsonWriter {
+            writer,
+            keys: HashMap::new(),
+            coalesced: false,
+            grouper: EventGrouper::default(),
+        }
+    }
+
+    /// Write the `field` of the records under `key`
+    pub fn map_field(mut self, field: &str, key: &str) -> Self {
+        self.keys.insert(field.to_string(), key.to_string());
+        self
+    }
+
+    /// Write each event as a single object keyed by record type
+    pub fn coalesced(mut self) -> Self {
+        self.coalesced = true;
+        self
+    }
+
+    /// Set the limits on the records buffered while waiting for the end of
+    /// the events
+    pub fn limits(mut self, limits: EventLimits) -> Self {
+        self.grouper.set_limits(limits);
+        self
+    }
+
+    /// Return the number of events written before their last record was
+    /// received, because of the limits
+    pub fn forced_flushes(&self) -> u64 {
+        self.grouper.forced_flushes()
+    }
+
+    pub fn write(&mut self, message: &AuditMessage) -> io::Result<()> {
+        let JsonWriter {
+            writer,
+            keys,
+            coalesced,
+            grouper,
+        } = self;
+        grouper.push(message, |id, records| {
+            write_event(writer, keys, *coalesced, id, records)
+        })
+    }
+
+    /// Write the records of the pending events
+    pub fn flush(&mut self) -> io::Result<()> {
+        let JsonWriter {
+            writer,
+            keys,
+            coalesced,
+            grouper,
+        } = self;
+        grouper.flush(|id, records| {
+            write_event(writer, keys, *coalesced, id, records)
+        })
+    }
+
+    /// Write the records of the pending events, and return the underlying
+    /// writer
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.flush()?;
+        Ok(self.writer)
+    }
+}
+
+fn write_event<W: Write>(
+    writer: &mut W,
+    keys: &HashMap<String, String>,
+    coalesced: bool,
+    id: Option<EventId>,
+    records: &[AuditMessage],
+) -> io::Result<()> {
+    let out = if coalesced {
+        coalesced_event(keys, id, records)
+    } else {
+        event(keys, id, records)
+    };
+    writer.write_all(out.as_bytes())?;
+    writer.flush()
+}
+
+/// Write an event as an object with the array of its records
+fn event(
+    keys: &HashMap<String, String>,
+    id: Option<EventId>,
+    records: &[AuditMessage],
+) -> String {
+    let mut out = String::from("{");
+    if let Some(id) = id {
+        write!(
+            out,
+            "\"time\":\"{}\",\"serial\":{},",
+            id.rfc3339(),
+            id.serial
+        )
+        .unwrap();
+    }
+    out.push_str("\"records\":[");
+    for (i, record) in records.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_record(&mut out, keys, record);
+    }
+    out.push_str("]}\n");
+    out
+}
+
+fn write_record(
+    out: &mut String,
+    keys: &HashMap<String, String>,
+    record: &AuditMessage,
+) {
+    out.push_str("{\"type\":");
+    write_string(out, &record.message_type().to_string());
+    if let Some(data) = record.data_lossy() {
+        write_fields(out, keys, &data, vec!["type"]);
+    }
+    out.push('}');
+}
+
+/// Write an event as a single object keyed by record type
+fn coalesced_event(
+    keys: &HashMap<String, String>,
+    id: Option<EventId>,
+    records: &[AuditMessage],
+) -> String {
+    let mut out = String::from("{");
+    if let Some(id) = id {
+        write!(
+            out,
+            "\"ID\":\"{}.{:03}:{}\"",
+            id.seconds, id.milliseconds, id.serial
+        )
+        .unwrap();
+    }
+    // the records of each type, in the order the types were received
+    let mut types: Vec<(AuditMessageType, Vec<&AuditMessage>)> = vec![];
+    for record in records {
+        let message_type = record.message_type();
+        match types.iter_mut().find(|(t, _)| *t == message_type) {
+            Some((_, records)) => records.push(record),
+            None => types.push((message_type, vec![record])),
+        }
+    }
+    for (message_type, records) in types {
+        if out.len() > 1 {
+            out.push(',');
+        }
+        write_string(&mut out, &message_type.to_string());
+        out.push(':');
+        match message_type {
+            AuditMessageType::Execve | AuditMessageType::Proctitle => {
+                write_arguments(&mut out, &records)
+            }
+            _ => write_objects(&mut out, keys, &records),
+        }
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Write records as objects without their type, in an array if there are
+/// several of them or if they are `PATH` records
+fn write_objects(
+    out: &mut String,
+    keys: &HashMap<String, String>,
+    records: &[&AuditMessage],
+) {
+    let array = records.len() > 1
+        || records[0].message_type() == AuditMessageType::Path;
+    if array {
+        out.push('[');
+    }
+    for (i, record) in records.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        out.push('{');
+        if let Some(data) = record.data_lossy() {
+            write_fields(out, keys, &data, vec![]);
+        }
+        out.push('}');
+    }
+    if array {
+        out.push(']');
+    }
+}
+
+/// Write the `EXECVE` or `PROCTITLE` records of an event as an object whose
+/// `ARGV` member is the array of their arguments
+fn write_arguments(out: &mut String, records: &[&AuditMessage]) {
+    let mut argc = None;
+    let mut argv: Vec<String> = vec![];
+    for record in records {
+        let data = match record.data_lossy() {
+            Some(data) => data,
+            None => continue,
+        };
+        for field in RecordFields::new(&data) {
+            let value = if field.quoted {
+                field.value.into()
+            } else {
+                decoded(field.value)
+            };
+            match field.name {
+                "argc" => argc = Some(field.value.to_string()),
+                "proctitle" => {
+                    argv.extend(value.split('\0').map(str::to_string))
+                }
+                // the arguments longer than a record are split in
+                // `a1[0]`, `a1[1]`... fields, after a `a1_len` field
+                name if is_argument(name) => {
+                    if name.contains('[') && !name.ends_with("[0]") {
+                        if let Some(last) = argv.last_mut() {
+                            last.push_str(&value);
+                        }
+                    } else {
+                        argv.push(value.into_owned());
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+    out.push('{');
+    if let Some(argc) = argc.filter(|argc| is_integer(argc)) {
+        write!(out, "\"argc\":{argc},").unwrap();
+    }
+    out.push_str("\"ARGV\":[");
+    for (i, arg) in argv.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        write_string(out, arg);
+    }
+    out.push_str("]}");
+}
+
+/// Return `true` for the `a0`, `a1`... and `a1[0]`, `a1[1]`... fields of
+/// `EXECVE` records
+fn is_argument(name: &str) -> bool {
+    let digits = match name.strip_prefix('a') {
+        Some(rest) => rest.split('[').next().unwrap_or(rest),
+        None => return false,
+    };
+    !digits.is_empty() && digits.bytes().all(|c| c.is_ascii_digit())
+}
+
+/// Write the fields of a record as members of an object, after the members
+/// whose keys are `written`
+fn write_fields<'a>(
+    out: &mut String,
+    keys: &'a HashMap<String, String>,
+    data: &'a str,
+    mut written: Vec<&'a str>,
+) {
+    for field in RecordFields::new(data) {
+        let name = field.name;
+        let key = keys.get(name).map_or(name, String::as_str);
+        if written.contains(&key) {
+            continue;
+        }
+        if !written.is_empty() {
+            out.push(',');
+        }
+        written.push(key);
+        write_string(out, key);
+        out.push(':');
+        let value = field.decoded();
+        if is_integer(&value)
+            && !HEX_FIELDS.contains(&name)
+            && !name.starts_with("cap_")
+        {
+            out.push_str(&value);
+        } else {
+            write_string(out, &value);
+        }
+    }
+}
+
+/// Return `true` if `value` is a decimal integer that can be written as is
+/// in a JSON document
+fn is_integer(value: &str) -> bool {
+    let digits = value.strip_prefix('-').unwrap_or(value);
+    !digits.is_empty()
+        && digits.len() <= 19
+        && digits.bytes().all(|c| c.is_ascii_digit())
+        && (digits == "0" || !digits.starts_with('0'))
+        && value != "-0"
+}
+
+/// Write `s` as a JSON string
+fn write_string(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                write!(out, "\\u{:04x}", u32::from(c)).unwrap()
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{constants::*, EventPayload, UserMessage, UserMessageKind};
+
+    fn record(message_type: u16, data: &str) -> AuditMessage {
+        AuditMessage::Event(EventPayload::new(
+            message_type.into(),
+            data.as_bytes().to_vec(),
+        ))
+    }
+
+    #[test]
+    fn test_writer() {
+        let mut writer = JsonWriter::new(vec![]).map_field("auid", "user.id");
+        for msg in [
+            record(
+                AUDIT_SYSCALL,
+                "audit(1592992200.383:66): arch=c000003e syscall=59 a0=10 \
+                exit=-2 auid=1000 uid=1000 ses=007 comm=\"ls\" \
+                exe=2F746D702F6D7920657865",
+            ),
+            record(AUDIT_PATH, "audit(1592992200.383:66): name=\"/etc\""),
+            record(
+                AUDIT_PROCTITLE,
+                "audit(1592992200.383:66): proctitle=6C73002D6C",
+            ),
+            record(AUDIT_EOE, "audit(1592992200.383:66): "),
+            AuditMessage::GetStatus(None),
+            AuditMessage::User(UserMessage::new(
+                UserMessageKind::UserAvc,
+                b"audit(1592992201.000:67): b=2\tc".to_vec(),
+            )),
+        ] {
+            writer.write(&msg).unwrap();
+        }
+        let out = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert_eq!(
+            out,
+            "{\"time\":\"2020-06-24T09:50:00.383Z\",\"serial\":66,\
+            \"records\":[\
+            {\"type\":\"SYSCALL\",\"arch\":\"c000003e\",\"syscall\":59,\
+            \"a0\":\"10\",\"exit\":-2,\"user.id\":1000,\"uid\":1000,\
+            \"ses\":\"007\",\"comm\":\"ls\",\"exe\":\"/tmp/my exe\"},\
+            {\"type\":\"PATH\",\"name\":\"/etc\"},\
+            {\"type\":\"PROCTITLE\",\"proctitle\":\"ls -l\"}]}\n\
+            {\"records\":[{\"type\":\"GET\"}]}\n\
+            {\"time\":\"2020-06-24T09:50:01.000Z\",\"serial\":67,\
+            \"records\":[{\"type\":\"USER_AVC\",\"b\":\"2\\tc\"}]}\n"
+        );
+    }
+
+    #[test]
+    fn test_coalesced() {
+        let mut writer = JsonWriter::new(vec![]).coalesced();
+        for msg in [
+            record(
+                AUDIT_SYSCALL,
+                "audit(1592992200.383:66): arch=c000003e syscall=59 a0=10",
+            ),
+            record(
+                AUDIT_EXECVE,
+                "audit(1592992200.383:66): argc=3 a0=\"ls\" a1_len=6 \
+                a1[0]=2D6C\n",
+            ),
+            record(
+                AUDIT_EXECVE,
+                "audit(1592992200.383:66): a1[1]=2061 a2=\"/tmp\"",
+            ),
+            record(AUDIT_CWD, "audit(1592992200.383:66): cwd=\"/\""),
+            record(AUDIT_PATH, "audit(1592992200.383:66): item=0 name=\"/a\""),
+            record(AUDIT_PATH, "audit(1592992200.383:66): item=1 name=\"/b\""),
+            record(
+                AUDIT_PROCTITLE,
+                "audit(1592992200.383:66): proctitle=6C73002D6C",
+            ),
+            AuditMessage::GetStatus(None),
+            record(AUDIT_SYSCALL, "audit(1592992201.000:67): syscall=2"),
+            record(AUDIT_PATH, "audit(1592992201.000:67): item=0"),
+        ] {
+            writer.write(&msg).unwrap();
+        }
+        let out = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+        assert_eq!(
+            out,
+            "{\"ID\":\"1592992200.383:66\",\
+            \"SYSCALL\":{\"arch\":\"c000003e\",\"syscall\":59,\"a0\":\"10\"},\
+            \"EXECVE\":{\"argc\":3,\"ARGV\":[\"ls\",\"-l a\",\"/tmp\"]},\
+            \"CWD\":{\"cwd\":\"/\"},\
+            \"PATH\":[{\"item\":0,\"name\":\"/a\"},\
+            {\"item\":1,\"name\":\"/b\"}],\
+            \"PROCTITLE\":{\"ARGV\":[\"ls\",\"-l\"]}}\n\
+            {\"GET\":{}}\n\
+            {\"ID\":\"1592992201.000:67\",\"SYSCALL\":{\"syscall\":2},\
+            \"PATH\":[{\"item\":0}]}\n"
+        );
+    }
+
+    #[test]
+    fn test_write_string() {
+        let mut out = String::new();
+        write_string(&mut out, "a\"b\\c\nd\u{1}");
+        assert_eq!(out, "\"a\\\"b\\\\c\\nd\\u0001\"");
+    }
+}
@@ -8,17 +8,100 @@ use core::ops::Range;
 pub(crate) type Field = Range<usize>;
 
 mod codec;
-pub use codec::NetlinkAuditCodec;
+pub use codec::{
+    AuditCodecStats, DecodeAll, DecodeObserver, LengthPolicy, NetlinkAuditCodec,
+};
+
+#[cfg(feature = "async")]
+mod framed;
+#[cfg(feature = "async")]
+pub use framed::AuditFramed;
+
+mod byte_order;
+pub use byte_order::Endianness;
+
+mod grouper;
+pub use grouper::EventLimits;
+
+mod ausearch;
+pub use ausearch::AusearchWriter;
+
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "json")]
+pub use json::JsonWriter;
+
+mod framer;
+pub use framer::{AuditFrame, AuditFramer, AuditFramerBuilder, MalformedFrame};
+
+mod error;
+pub use error::{
+    into_audit_result, resolve_reply, AuditDecodeError, AuditEmitError,
+    AuditNetlinkError, AuditReply,
+};
+
+mod record;
+pub use record::{RecordField, RecordFields};
+
+mod reply;
+pub use reply::ReplyCollector;
+
+mod syslog;
+pub use syslog::SyslogFormatter;
+
+mod siem;
+pub use siem::{SiemFormat, SiemWriter};
+
+mod sequence;
+pub use sequence::{
+    EventId, SequenceGap, SequenceTracker, DEFAULT_REORDER_WINDOW,
+};
+
+pub mod audit_log;
+
+pub mod capture;
+
+pub mod filter;
+
+#[cfg(feature = "client")]
+pub mod client;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+pub mod prelude;
+
+pub mod requests;
+
+pub mod dump;
 
 pub mod status;
 pub use self::status::*;
 
+pub mod features;
+pub use self::features::*;
+
+pub mod signal_info;
+pub use self::signal_info::*;
+
+pub mod tty_status;
+pub use self::tty_status::*;
+
+pub mod make_equiv;
+pub use self::make_equiv::*;
+
+mod capabilities;
+pub use self::capabilities::*;
+
 pub mod rules;
 pub use self::rules::*;
 
 mod message;
 pub use self::message::*;
 
+mod message_type;
+pub use self::message_type::*;
+
 mod buffer;
 pub use self::buffer::*;
 
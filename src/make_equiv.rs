@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: MIT
+
+use byteorder::{ByteOrder, NativeEndian};
+
+use netlink_packet_utils::{
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use crate::{AuditDecodeError, Field};
+
+const OLD_LEN: Field = 0..4;
+const NEW_LEN: Field = 4..8;
+const BUF: usize = NEW_LEN.end;
+pub const MAKE_EQUIV_MESSAGE_MIN_LEN: usize = BUF;
+
+/// Payload of the `AUDIT_MAKE_EQUIV` requests: make the rules watching the
+/// `old` directory tree also apply to the `new` one
+#[derive(Debug, PartialEq, Eq, Clone, Default, Hash)]
+#[non_exhaustive]
+pub struct MakeEquivMessage {
+    pub old: String,
+    pub new: String,
+}
+
+impl MakeEquivMessage {
+    pub fn new(old: String, new: String) -> Self {
+        MakeEquivMessage { old, new }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct MakeEquivMessageBuffer<T> {
+    buffer: T,
+}
+
+impl<T: AsRef<[u8]>> MakeEquivMessageBuffer<T> {
+    pub fn new(buffer: T) -> MakeEquivMessageBuffer<T> {
+        MakeEquivMessageBuffer { buffer }
+    }
+
+    pub fn new_checked(
+        buffer: T,
+    ) -> Result<MakeEquivMessageBuffer<T>, DecodeError> {
+        let buf = Self::new(buffer);
+        buf.check_buffer_length()?;
+        Ok(buf)
+    }
+
+    pub fn check_buffer_length(&self) -> Result<(), AuditDecodeError> {
+        let len = self.buffer.as_ref().len();
+        if len < MAKE_EQUIV_MESSAGE_MIN_LEN {
+            return Err(AuditDecodeError::TruncatedMakeEquiv {
+                needed: MAKE_EQUIV_MESSAGE_MIN_LEN,
+                got: len,
+            });
+        }
+        let expected = BUF
+            .saturating_add(self.old_len() as usize)
+            .saturating_add(self.new_len() as usize);
+        if len < expected {
+            return Err(AuditDecodeError::TruncatedMakeEquiv {
+                needed: expected,
+                got: len,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> T {
+        self.buffer
+    }
+
+    pub fn old_len(&self) -> u32 {
+        NativeEndian::read_u32(&self.buffer.as_ref()[OLD_LEN])
+    }
+
+    pub fn new_len(&self) -> u32 {
+        NativeEndian::read_u32(&self.buffer.as_ref()[NEW_LEN])
+    }
+
+    pub fn old(&self) -> &[u8] {
+        let end = BUF + self.old_len() as usize;
+        &self.buffer.as_ref()[BUF..end]
+    }
+
+    pub fn new_path(&self) -> &[u8] {
+        let start = BUF + self.old_len() as usize;
+        let end = start + self.new_len() as usize;
+        &self.buffer.as_ref()[start..end]
+    }
+}
+
+impl<T: AsRef<[u8]> + AsMut<[u8]>> MakeEquivMessageBuffer<T> {
+    pub fn set_old_len(&mut self, value: u32) {
+        NativeEndian::write_u32(&mut self.buffer.as_mut()[OLD_LEN], value)
+    }
+
+    pub fn set_new_len(&mut self, value: u32) {
+        NativeEndian::write_u32(&mut self.buffer.as_mut()[NEW_LEN], value)
+    }
+
+    pub fn buf_mut(&mut self) -> &mut [u8] {
+        &mut self.buffer.as_mut()[BUF..]
+    }
+}
+
+impl<T: AsRef<[u8]>> Parseable<MakeEquivMessageBuffer<T>> for MakeEquivMessage {
+    fn parse(buf: &MakeEquivMessageBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self::try_parse(buf)?)
+    }
+}
+
+impl MakeEquivMessage {
+    pub(crate) fn try_parse<T: AsRef<[u8]>>(
+        buf: &MakeEquivMessageBuffer<T>,
+    ) -> Result<Self, AuditDecodeError> {
+        buf.check_buffer_length()?;
+        Ok(MakeEquivMessage {
+            old: String::from_utf8_lossy(buf.old()).into_owned(),
+            new: String::from_utf8_lossy(buf.new_path()).into_owned(),
+        })
+    }
+}
+
+impl Emitable for MakeEquivMessage {
+    fn buffer_len(&self) -> usize {
+        BUF + self.old.len() + self.new.len()
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = MakeEquivMessageBuffer::new(buffer);
+        buffer.set_old_len(self.old.len() as u32);
+        buffer.set_new_len(self.new.len() as u32);
+        let buf = buffer.buf_mut();
+        buf[..self.old.len()].copy_from_slice(self.old.as_bytes());
+        buf[self.old.len()..].copy_from_slice(self.new.as_bytes());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_make_equiv() {
+        let msg = MakeEquivMessage::new("/etc".into(), "/mnt/etc".into());
+        let mut buf = vec![0; msg.buffer_len()];
+        msg.emit(&mut buf);
+        assert_eq!(buf.len(), 8 + 4 + 8);
+        let parsed = MakeEquivMessage::parse(
+            &MakeEquivMessageBuffer::new_checked(&buf).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(parsed, msg);
+
+        assert!(MakeEquivMessageBuffer::new_checked(&buf[..19]).is_err());
+    }
+}
@@ -1,16 +1,23 @@
 // SPDX-License-Identifier: MIT
 
+use std::{borrow::Cow, fmt};
+
 use netlink_packet_core::{
-    NetlinkDeserializable, NetlinkHeader, NetlinkPayload, NetlinkSerializable,
+    NetlinkDeserializable, NetlinkHeader, NetlinkMessage, NetlinkPayload,
+    NetlinkSerializable,
 };
 use netlink_packet_utils::{
     traits::{Emitable, ParseableParametrized},
     DecodeError,
 };
 
-use crate::{constants::*, rules::RuleMessage, AuditBuffer, StatusMessage};
+use crate::{
+    rules::RuleMessage, AuditBuffer, AuditEmitError, AuditMessageClass,
+    AuditMessageType, EventId, FeaturesMessage, MakeEquivMessage,
+    SignalInfoMessage, StatusMessage, TtyStatusMessage, UserMessageKind,
+};
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
 #[non_exhaustive]
 pub enum AuditMessage {
     GetStatus(Option<StatusMessage>),
@@ -18,6 +25,13 @@ pub enum AuditMessage {
     AddRule(RuleMessage),
     DelRule(RuleMessage),
     ListRules(Option<RuleMessage>),
+    GetFeature(Option<FeaturesMessage>),
+    SetFeature(FeaturesMessage),
+    SignalInfo(Option<SignalInfoMessage>),
+    TtyGet(Option<TtyStatusMessage>),
+    TtySet(TtyStatusMessage),
+    Trim,
+    MakeEquiv(MakeEquivMessage),
     /// Event message (message types 1300 through 1399). This includes the
     /// following message types (this list is non-exhaustive, and not
     /// really kept up to date): `AUDIT_SYSCALL`, `AUDIT_PATH`,
@@ -31,11 +45,59 @@ pub enum AuditMessage {
     /// `AUDIT_PROCTITLE`, `AUDIT_FEATURE_CHANGE`, `AUDIT_REPLACE`,
     /// `AUDIT_KERN_MODULE`, `AUDIT_FANOTIFY`.
     ///
-    /// The first element of the tuple is the message type, and the second is
-    /// the event data.
-    Event((u16, String)),
+    /// The data is usually text, but the kernel does not guarantee it is
+    /// valid UTF-8 (see [`AuditMessage::data_lossy`]).
+    Event(EventPayload),
+    /// Message sent by a trusted userspace application (`AUDIT_USER`, and
+    /// message types 1100 through 1199 and 2100 through 2999), such as the
+    /// `USER_LOGIN` records emitted by PAM.
+    User(UserMessage),
     /// All the other events are parsed as such as they can be parsed also.
-    Other((u16, String)),
+    /// Their category can be found with [`AuditMessage::class`].
+    Other((AuditMessageType, Vec<u8>)),
+}
+
+/// Payload of [`AuditMessage::Event`]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[non_exhaustive]
+pub struct EventPayload {
+    pub kind: AuditMessageType,
+    /// Event data, usually text
+    pub data: Vec<u8>,
+}
+
+impl EventPayload {
+    pub fn new(kind: AuditMessageType, data: Vec<u8>) -> Self {
+        EventPayload { kind, data }
+    }
+}
+
+/// Payload of [`AuditMessage::User`]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[non_exhaustive]
+pub struct UserMessage {
+    pub kind: UserMessageKind,
+    /// Message data, usually text
+    pub payload: Vec<u8>,
+}
+
+impl UserMessage {
+    pub fn new(kind: UserMessageKind, payload: Vec<u8>) -> Self {
+        UserMessage { kind, payload }
+    }
+}
+
+/// How to handle event payloads that are not valid UTF-8
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Hash)]
+#[non_exhaustive]
+pub enum Utf8Policy {
+    /// Keep the payload as is
+    #[default]
+    Raw,
+    /// Fail to parse the message
+    Strict,
+    /// Replace the invalid sequences with `U+FFFD REPLACEMENT CHARACTER`
+    Lossy,
 }
 
 impl AuditMessage {
@@ -43,12 +105,35 @@ impl AuditMessage {
         matches!(self, AuditMessage::Event(_))
     }
 
+    /// Return `true` for the messages used to configure and query the audit
+    /// subsystem (message types 1000 to 1099), including the ones this
+    /// crate parses as `Other`. `AUDIT_USER` is in that range but is a user
+    /// message, so it is not considered a control message.
+    pub fn is_control(&self) -> bool {
+        !self.is_user_message() && self.class() == AuditMessageClass::Control
+    }
+
+    /// Return `true` for the messages sent by trusted userspace
+    /// applications
+    pub fn is_user_message(&self) -> bool {
+        matches!(self, AuditMessage::User(_))
+    }
+
+    /// Return the type of an event, or `None` if the message is not an
+    /// event
+    pub fn event_kind(&self) -> Option<AuditMessageType> {
+        match self {
+            AuditMessage::Event(event) => Some(event.kind),
+            _ => None,
+        }
+    }
+
     pub fn is_get_status(&self) -> bool {
         matches!(self, AuditMessage::GetStatus(_))
     }
 
     pub fn is_set_status(&self) -> bool {
-        matches!(self, AuditMessage::GetStatus(_))
+        matches!(self, AuditMessage::SetStatus(_))
     }
 
     pub fn is_add_rule(&self) -> bool {
@@ -63,19 +148,171 @@ impl AuditMessage {
         matches!(self, AuditMessage::ListRules(_))
     }
 
-    pub fn message_type(&self) -> u16 {
+    /// Return the raw data of an event
+    pub fn data(&self) -> Option<&[u8]> {
+        match self {
+            AuditMessage::Event(event) => Some(&event.data),
+            AuditMessage::Other((_, data)) => Some(data),
+            AuditMessage::User(msg) => Some(&msg.payload),
+            _ => None,
+        }
+    }
+
+    /// Return the data of an event as a string, replacing the invalid UTF-8
+    /// sequences with `U+FFFD REPLACEMENT CHARACTER`
+    pub fn data_lossy(&self) -> Option<Cow<'_, str>> {
+        self.data().map(String::from_utf8_lossy)
+    }
+
+    /// Return the identifier of the event this record belongs to, if any
+    pub fn event_id(&self) -> Option<EventId> {
+        self.data().and_then(|data| EventId::from_record(data).ok())
+    }
+
+    pub fn message_type(&self) -> AuditMessageType {
         use self::AuditMessage::*;
 
         match self {
-            GetStatus(_) => AUDIT_GET,
-            SetStatus(_) => AUDIT_SET,
-            ListRules(_) => AUDIT_LIST_RULES,
-            AddRule(_) => AUDIT_ADD_RULE,
-            DelRule(_) => AUDIT_DEL_RULE,
-            Event((message_type, _)) => *message_type,
+            GetStatus(_) => AuditMessageType::Get,
+            SetStatus(_) => AuditMessageType::Set,
+            ListRules(_) => AuditMessageType::ListRules,
+            AddRule(_) => AuditMessageType::AddRule,
+            DelRule(_) => AuditMessageType::DelRule,
+            GetFeature(_) => AuditMessageType::GetFeature,
+            SetFeature(_) => AuditMessageType::SetFeature,
+            SignalInfo(_) => AuditMessageType::SignalInfo,
+            TtyGet(_) => AuditMessageType::TtyGet,
+            TtySet(_) => AuditMessageType::TtySet,
+            Trim => AuditMessageType::Trim,
+            MakeEquiv(_) => AuditMessageType::MakeEquiv,
+            Event(event) => event.kind,
+            User(msg) => u16::from(msg.kind).into(),
             Other((message_type, _)) => *message_type,
         }
     }
+
+    /// Category of the message, according to the kernel's message type
+    /// ranges. This is mostly useful for the `Other` variant, to tell
+    /// apart the message types this crate does not handle explicitly.
+    pub fn class(&self) -> AuditMessageClass {
+        self.message_type().class()
+    }
+
+    /// Check that the message can be serialized. `emit()` panics on
+    /// messages that fail this check.
+    pub fn validate(&self) -> Result<(), AuditEmitError> {
+        use self::AuditMessage::*;
+
+        match self {
+            AddRule(ref msg) | DelRule(ref msg) | ListRules(Some(ref msg)) => {
+                msg.validate()
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checked version of `emit()`: validate the message and the buffer
+    /// size, then serialize the message at the start of `buffer`. Return
+    /// the number of bytes written.
+    pub fn try_emit(&self, buffer: &mut [u8]) -> Result<usize, AuditEmitError> {
+        self.validate()?;
+        let len = Emitable::buffer_len(self);
+        AuditEmitError::check_buffer(len, buffer)?;
+        self.emit(&mut buffer[..len]);
+        Ok(len)
+    }
+}
+
+/// Checked version of `NetlinkMessage::serialize()`: validate the audit
+/// payload, the header length and the buffer size before writing, instead
+/// of panicking. Return the number of bytes written.
+pub fn try_serialize(
+    message: &NetlinkMessage<AuditMessage>,
+    buffer: &mut [u8],
+) -> Result<usize, AuditEmitError> {
+    if let NetlinkPayload::InnerMessage(ref msg) = message.payload {
+        msg.validate()?;
+    }
+    let len = message.buffer_len();
+    if message.header.length as usize != len {
+        return Err(AuditEmitError::LengthMismatch {
+            header: message.header.length,
+            actual: len,
+        });
+    }
+    AuditEmitError::check_buffer(len, buffer)?;
+    message.serialize(&mut buffer[..len]);
+    Ok(len)
+}
+
+/// Render the message on a single line, the way `ausearch` does:
+/// `type=SYSCALL msg=audit(1592992200.383:66): arch=c000003e ...`. The
+/// payload of control messages is rendered as `name=value` pairs.
+impl fmt::Display for AuditMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::AuditMessage::*;
+
+        write!(f, "type={}", self.message_type())?;
+        match self {
+            Event(EventPayload { data, .. })
+            | Other((_, data))
+            | User(UserMessage { payload: data, .. }) => {
+                let end = data
+                    .iter()
+                    .rposition(|c| !matches!(c, b'\0' | b'\n'))
+                    .map_or(0, |i| i + 1);
+                write!(f, " msg={}", String::from_utf8_lossy(&data[..end]))
+            }
+            GetStatus(Some(status)) | SetStatus(status) => write!(
+                f,
+                " mask={:#x} enabled={} failure={} pid={} rate_limit={} \
+                backlog_limit={} lost={} backlog={} backlog_wait_time={} \
+                features={:#x}",
+                status.mask,
+                status.enabled,
+                status.failure,
+                status.pid,
+                status.rate_limiting,
+                status.backlog_limit,
+                status.lost,
+                status.backlog,
+                status.backlog_wait_time,
+                status.feature_bitmap
+            ),
+            AddRule(rule) | DelRule(rule) | ListRules(Some(rule)) => write!(
+                f,
+                " list={} action={} fields={}",
+                u32::from(rule.flags),
+                u32::from(rule.action),
+                rule.fields.len()
+            ),
+            GetFeature(Some(features)) | SetFeature(features) => write!(
+                f,
+                " version={} mask={:#x} features={:#x} lock={:#x}",
+                features.version,
+                features.mask,
+                features.features,
+                features.lock
+            ),
+            SignalInfo(Some(info)) => {
+                write!(f, " auid={} pid={}", info.uid, info.pid)?;
+                if !info.context.is_empty() {
+                    write!(f, " subj={}", info.context)?;
+                }
+                Ok(())
+            }
+            TtyGet(Some(tty)) | TtySet(tty) => write!(
+                f,
+                " enabled={} log_passwd={}",
+                tty.enabled, tty.log_passwd
+            ),
+            MakeEquiv(equiv) => {
+                write!(f, " old={} new={}", equiv.old, equiv.new)
+            }
+            GetStatus(None) | ListRules(None) | GetFeature(None)
+            | SignalInfo(None) | TtyGet(None) | Trim => Ok(()),
+        }
+    }
 }
 
 impl Emitable for AuditMessage {
@@ -88,8 +325,16 @@ impl Emitable for AuditMessage {
             AddRule(ref msg) => msg.buffer_len(),
             DelRule(ref msg) => msg.buffer_len(),
             ListRules(Some(ref msg)) => msg.buffer_len(),
-            GetStatus(None) | ListRules(None) => 0,
-            Event((_, ref data)) => data.len(),
+            GetFeature(Some(ref msg)) => msg.buffer_len(),
+            SetFeature(ref msg) => msg.buffer_len(),
+            SignalInfo(Some(ref msg)) => msg.buffer_len(),
+            TtyGet(Some(ref msg)) => msg.buffer_len(),
+            TtySet(ref msg) => msg.buffer_len(),
+            MakeEquiv(ref msg) => msg.buffer_len(),
+            GetStatus(None) | ListRules(None) | GetFeature(None)
+            | SignalInfo(None) | TtyGet(None) | Trim => 0,
+            Event(ref event) => event.data.len(),
+            User(ref msg) => msg.payload.len(),
             Other((_, ref data)) => data.len(),
         }
     }
@@ -103,16 +348,24 @@ impl Emitable for AuditMessage {
             AddRule(ref msg) => msg.emit(buffer),
             DelRule(ref msg) => msg.emit(buffer),
             ListRules(Some(ref msg)) => msg.emit(buffer),
-            ListRules(None) | GetStatus(None) => {}
-            Event((_, ref data)) => buffer.copy_from_slice(data.as_bytes()),
-            Other((_, ref data)) => buffer.copy_from_slice(data.as_bytes()),
+            GetFeature(Some(ref msg)) => msg.emit(buffer),
+            SetFeature(ref msg) => msg.emit(buffer),
+            SignalInfo(Some(ref msg)) => msg.emit(buffer),
+            TtyGet(Some(ref msg)) => msg.emit(buffer),
+            TtySet(ref msg) => msg.emit(buffer),
+            MakeEquiv(ref msg) => msg.emit(buffer),
+            ListRules(None) | GetStatus(None) | GetFeature(None)
+            | SignalInfo(None) | TtyGet(None) | Trim => {}
+            Event(ref event) => buffer.copy_from_slice(&event.data),
+            User(ref msg) => buffer.copy_from_slice(&msg.payload),
+            Other((_, ref data)) => buffer.copy_from_slice(data),
         }
     }
 }
 
 impl NetlinkSerializable for AuditMessage {
     fn message_type(&self) -> u16 {
-        self.message_type()
+        self.message_type().into()
     }
 
     fn buffer_len(&self) -> usize {
@@ -148,3 +401,423 @@ impl From<AuditMessage> for NetlinkPayload<AuditMessage> {
         NetlinkPayload::InnerMessage(message)
     }
 }
+
+/// Borrowed counterpart of [`AuditMessage`], returned by
+/// [`AuditMessageRef::parse_payload`]. The payload of events and other
+/// unknown messages points into the buffer being parsed, unless it had to
+/// be rewritten (see [`Utf8Policy::Lossy`]). The control messages are small,
+/// and are parsed as usual.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+#[non_exhaustive]
+pub enum AuditMessageRef<'a> {
+    /// Control message (status, rules, features...). This never holds
+    /// [`AuditMessage::Event`] or [`AuditMessage::Other`].
+    Control(AuditMessage),
+    /// Event message (message types 1300 through 1399)
+    Event((AuditMessageType, Cow<'a, [u8]>)),
+    /// Message sent by a trusted userspace application
+    User((UserMessageKind, Cow<'a, [u8]>)),
+    /// All the other messages
+    Other((AuditMessageType, Cow<'a, [u8]>)),
+}
+
+impl<'a> AuditMessageRef<'a> {
+    /// Return the raw data of an event
+    pub fn data(&self) -> Option<&[u8]> {
+        match self {
+            AuditMessageRef::Event((_, data))
+            | AuditMessageRef::User((_, data))
+            | AuditMessageRef::Other((_, data)) => Some(data),
+            AuditMessageRef::Control(_) => None,
+        }
+    }
+
+    pub fn message_type(&self) -> AuditMessageType {
+        match self {
+            AuditMessageRef::Control(message) => message.message_type(),
+            AuditMessageRef::Event((message_type, _))
+            | AuditMessageRef::Other((message_type, _)) => *message_type,
+            AuditMessageRef::User((kind, _)) => u16::from(*kind).into(),
+        }
+    }
+
+    /// Copy the payload, if it is still borrowed
+    pub fn into_owned(self) -> AuditMessage {
+        match self {
+            AuditMessageRef::Control(message) => message,
+            AuditMessageRef::Event((message_type, data)) => {
+                AuditMessage::Event(EventPayload::new(
+                    message_type,
+                    data.into_owned(),
+                ))
+            }
+            AuditMessageRef::User((kind, data)) => {
+                AuditMessage::User(UserMessage::new(kind, data.into_owned()))
+            }
+            AuditMessageRef::Other((message_type, data)) => {
+                AuditMessage::Other((message_type, data.into_owned()))
+            }
+        }
+    }
+}
+
+impl<'a> From<AuditMessageRef<'a>> for AuditMessage {
+    fn from(message: AuditMessageRef<'a>) -> Self {
+        message.into_owned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        constants::*,
+        rules::{tests::XorShift, RuleField, RuleFieldFlags},
+        STATUS_MESSAGE_MIN_LEN, TTY_STATUS_MESSAGE_MIN_LEN,
+    };
+
+    impl XorShift {
+        fn bytes(&mut self) -> Vec<u8> {
+            (0..self.below(64)).map(|_| self.next() as u8).collect()
+        }
+
+        fn message(&mut self) -> AuditMessage {
+            use self::AuditMessage::*;
+
+            let features = |rng: &mut Self| FeaturesMessage {
+                version: rng.next(),
+                mask: rng.next(),
+                features: rng.next(),
+                lock: rng.next(),
+            };
+            let tty = |rng: &mut Self| TtyStatusMessage {
+                enabled: rng.below(2),
+                log_passwd: rng.below(2),
+            };
+            match self.below(13) {
+                0 => Event(EventPayload::new(
+                    (AUDIT_EVENT_MESSAGE_MIN + self.below(100) as u16).into(),
+                    self.bytes(),
+                )),
+                1 => {
+                    let kind = match self.below(3) {
+                        0 => AUDIT_USER,
+                        1 => AUDIT_FIRST_USER_MSG + self.below(100) as u16,
+                        _ => AUDIT_FIRST_USER_MSG2 + self.below(900) as u16,
+                    };
+                    User(UserMessage::new(kind.into(), self.bytes()))
+                }
+                // neither control, user nor event messages
+                2 => Other((
+                    (1400 + self.below(700) as u16).into(),
+                    self.bytes(),
+                )),
+                3 => GetStatus(Some(self.status())),
+                4 => SetStatus(self.status()),
+                5 => AddRule(self.rule()),
+                6 => GetFeature(Some(features(self))),
+                7 => SetFeature(features(self)),
+                8 => SignalInfo(Some(SignalInfoMessage {
+                    uid: self.next(),
+                    pid: self.next(),
+                    context: self.string(),
+                })),
+                9 => TtyGet(Some(tty(self))),
+                10 => TtySet(tty(self)),
+                11 => MakeEquiv(MakeEquivMessage::new(
+                    self.string(),
+                    self.string(),
+                )),
+                // requests without payload
+                _ => [
+                    GetStatus(None),
+                    ListRules(None),
+                    GetFeature(None),
+                    SignalInfo(None),
+                    TtyGet(None),
+                    Trim,
+                ][self.below(6) as usize]
+                    .clone(),
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_random_messages() {
+        let mut rng = XorShift(0xd1b5_4a32_d192_ed03);
+        for _ in 0..2000 {
+            let message = rng.message();
+            let mut buf = vec![0; Emitable::buffer_len(&message)];
+            message.emit(&mut buf);
+            let message_type = message.message_type().into();
+            let parsed = AuditMessage::parse_payload(
+                &buf,
+                message_type,
+                Utf8Policy::Raw,
+            )
+            .unwrap();
+            assert_eq!(parsed, message);
+
+            let mut emitted = vec![0; Emitable::buffer_len(&parsed)];
+            parsed.emit(&mut emitted);
+            assert_eq!(emitted, buf);
+        }
+    }
+
+    #[test]
+    fn test_parse_borrowed() {
+        let payload = b"audit(1592992200.383:66): arch=c000003e syscall=59";
+        let msg = AuditMessageRef::parse_payload(
+            payload,
+            AUDIT_SYSCALL,
+            Utf8Policy::Strict,
+        )
+        .unwrap();
+        match msg {
+            AuditMessageRef::Event((_, Cow::Borrowed(data))) => {
+                assert_eq!(data.as_ptr(), payload.as_ptr())
+            }
+            _ => panic!("unexpected message {:?}", msg),
+        }
+        assert_eq!(
+            msg.into_owned(),
+            AuditMessage::Event(EventPayload::new(
+                AUDIT_SYSCALL.into(),
+                payload.to_vec()
+            ))
+        );
+
+        let msg = AuditMessageRef::parse_payload(
+            b"\xffaudit",
+            AUDIT_USER,
+            Utf8Policy::Lossy,
+        )
+        .unwrap();
+        assert!(matches!(
+            msg,
+            AuditMessageRef::User((UserMessageKind::User, Cow::Owned(_)))
+        ));
+        assert_eq!(msg.data(), Some("\u{fffd}audit".as_bytes()));
+
+        let msg =
+            AuditMessageRef::parse_payload(&[], AUDIT_GET, Utf8Policy::Raw)
+                .unwrap();
+        assert_eq!(
+            msg,
+            AuditMessageRef::Control(AuditMessage::GetStatus(None))
+        );
+    }
+
+    #[test]
+    fn test_parse_truncated() {
+        let mut rule = RuleMessage::new();
+        rule.fields
+            .push((RuleField::Watch("/etc".into()), RuleFieldFlags::Equal));
+        let messages = vec![
+            AuditMessage::SetStatus(StatusMessage::new()),
+            AuditMessage::AddRule(rule),
+            AuditMessage::SetFeature(FeaturesMessage::new()),
+            AuditMessage::SignalInfo(Some(SignalInfoMessage::default())),
+            AuditMessage::TtySet(TtyStatusMessage::new(true, true)),
+            AuditMessage::MakeEquiv(MakeEquivMessage::new(
+                "/etc".into(),
+                "/mnt/etc".into(),
+            )),
+        ];
+        for message in messages {
+            let mut payload = vec![0; Emitable::buffer_len(&message)];
+            Emitable::emit(&message, &mut payload);
+            let message_type = message.message_type().into();
+            // The payloads shorter than the oldest layout are truncated.
+            // An empty AUDIT_SIGNAL_INFO payload is the request.
+            let truncated = match message {
+                AuditMessage::SetStatus(_) => 0..STATUS_MESSAGE_MIN_LEN,
+                AuditMessage::TtySet(_) => 0..TTY_STATUS_MESSAGE_MIN_LEN,
+                AuditMessage::SignalInfo(_) => 1..payload.len(),
+                _ => 0..payload.len(),
+            };
+            // the parsers must fail instead of reading past the end of
+            // the payload
+            for len in truncated {
+                let parsed = AuditMessage::parse_payload(
+                    &payload[..len],
+                    message_type,
+                    Utf8Policy::Raw,
+                );
+                assert!(parsed.is_err(), "{:?}, {} bytes", message_type, len);
+            }
+            // lengths and counts that do not match the payload
+            let corrupted = vec![0xff; payload.len()];
+            let parsed = AuditMessage::parse_payload(
+                &corrupted,
+                message_type,
+                Utf8Policy::Raw,
+            );
+            if matches!(
+                message,
+                AuditMessage::AddRule(_) | AuditMessage::MakeEquiv(_)
+            ) {
+                assert!(parsed.is_err(), "{:?}", message_type);
+            }
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let msg = AuditMessage::Event(EventPayload::new(
+            AUDIT_SYSCALL.into(),
+            b"audit(1592992200.383:66): arch=c000003e syscall=59\n".to_vec(),
+        ));
+        assert_eq!(
+            msg.to_string(),
+            "type=SYSCALL msg=audit(1592992200.383:66): arch=c000003e \
+            syscall=59"
+        );
+
+        // the last type of the event range is an event too
+        let msg = AuditMessage::parse_payload(b"x", 1399, Utf8Policy::Strict)
+            .unwrap();
+        assert_eq!(
+            msg,
+            AuditMessage::Event(EventPayload::new(1399.into(), b"x".to_vec()))
+        );
+        assert_eq!(msg.to_string(), "type=UNKNOWN[1399] msg=x");
+
+        let msg = AuditMessage::Other((2999.into(), b"x".to_vec()));
+        assert_eq!(msg.to_string(), "type=UNKNOWN[2999] msg=x");
+
+        let msg = AuditMessage::User(UserMessage::new(
+            AUDIT_USER_LOGIN.into(),
+            b"pid=1 uid=0".to_vec(),
+        ));
+        assert_eq!(msg.to_string(), "type=USER_LOGIN msg=pid=1 uid=0");
+        assert_eq!(msg.message_type(), AUDIT_USER_LOGIN);
+        assert_eq!(msg.message_type().to_string(), "USER_LOGIN");
+
+        assert_eq!(AuditMessage::GetStatus(None).to_string(), "type=GET");
+        let msg = AuditMessage::TtySet(TtyStatusMessage::new(true, false));
+        assert_eq!(msg.to_string(), "type=TTY_SET enabled=1 log_passwd=0");
+    }
+
+    #[test]
+    fn test_message_kind() {
+        let msg = AuditMessage::Event(EventPayload::new(
+            AUDIT_SYSCALL.into(),
+            b"audit(1592992200.383:66): syscall=59".to_vec(),
+        ));
+        assert!(msg.is_event() && !msg.is_control() && !msg.is_user_message());
+        assert_eq!(msg.event_kind(), Some(AuditMessageType::Syscall));
+
+        let msg = AuditMessage::User(UserMessage::new(
+            UserMessageKind::User,
+            b"pid=1".to_vec(),
+        ));
+        assert!(!msg.is_event() && !msg.is_control() && msg.is_user_message());
+        assert_eq!(msg.event_kind(), None);
+
+        assert!(AuditMessage::GetStatus(None).is_control());
+        assert!(AuditMessage::Other((1099.into(), vec![])).is_control());
+        assert!(!AuditMessage::Other((2999.into(), vec![])).is_control());
+    }
+
+    #[test]
+    fn test_is_helpers() {
+        type Helper = fn(&AuditMessage) -> bool;
+        let helpers: [(&str, Helper); 8] = [
+            ("event", AuditMessage::is_event),
+            ("control", AuditMessage::is_control),
+            ("user_message", AuditMessage::is_user_message),
+            ("get_status", AuditMessage::is_get_status),
+            ("set_status", AuditMessage::is_set_status),
+            ("add_rule", AuditMessage::is_add_rule),
+            ("del_rule", AuditMessage::is_del_rule),
+            ("list_rules", AuditMessage::is_list_rules),
+        ];
+        let messages = [
+            (
+                AuditMessage::Event(EventPayload::new(
+                    AUDIT_SYSCALL.into(),
+                    vec![],
+                )),
+                &["event"][..],
+            ),
+            (
+                AuditMessage::User(UserMessage::new(
+                    UserMessageKind::User,
+                    vec![],
+                )),
+                &["user_message"],
+            ),
+            (AuditMessage::GetStatus(None), &["control", "get_status"]),
+            (
+                AuditMessage::SetStatus(StatusMessage::default()),
+                &["control", "set_status"],
+            ),
+            (
+                AuditMessage::AddRule(RuleMessage::default()),
+                &["control", "add_rule"],
+            ),
+            (
+                AuditMessage::DelRule(RuleMessage::default()),
+                &["control", "del_rule"],
+            ),
+            (AuditMessage::ListRules(None), &["control", "list_rules"]),
+            (AuditMessage::SignalInfo(None), &["control"]),
+        ];
+        for (message, expected) in &messages {
+            for (name, helper) in &helpers {
+                assert_eq!(
+                    helper(message),
+                    expected.contains(name),
+                    "is_{} on {:?}",
+                    name,
+                    message
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_try_emit() {
+        let msg = AuditMessage::Event(EventPayload::new(
+            AUDIT_SYSCALL.into(),
+            b"audit(1592992200.383:66): syscall=59".to_vec(),
+        ));
+        let mut buf = [0; 64];
+        assert_eq!(msg.try_emit(&mut buf), Ok(36));
+        assert_eq!(&buf[..36], msg.data().unwrap());
+        assert_eq!(
+            msg.try_emit(&mut buf[..10]),
+            Err(AuditEmitError::BufferTooShort {
+                needed: 36,
+                got: 10
+            })
+        );
+
+        let mut rule = RuleMessage::new();
+        rule.fields = vec![
+            (
+                crate::rules::RuleField::Pid(1),
+                crate::rules::RuleFieldFlags::Equal,
+            );
+            AUDIT_MAX_FIELDS + 1
+        ];
+        let msg = AuditMessage::AddRule(rule);
+        let mut buf = vec![0; Emitable::buffer_len(&msg)];
+        assert_eq!(
+            msg.try_emit(&mut buf),
+            Err(AuditEmitError::TooManyRuleFields {
+                count: AUDIT_MAX_FIELDS + 1,
+                max: AUDIT_MAX_FIELDS
+            })
+        );
+
+        let mut nl_msg = NetlinkMessage::from(AuditMessage::Trim);
+        assert!(matches!(
+            try_serialize(&nl_msg, &mut buf),
+            Err(AuditEmitError::LengthMismatch { header: 0, .. })
+        ));
+        nl_msg.finalize();
+        assert_eq!(try_serialize(&nl_msg, &mut buf), Ok(16));
+    }
+}
@@ -0,0 +1,545 @@
+// SPDX-License-Identifier: MIT
+
+use std::fmt;
+
+use crate::constants::*;
+
+macro_rules! message_types {
+    (
+        $(#[$attr:meta])*
+        pub enum $type:ident {
+            $($variant:ident = $constant:ident, $name:literal;)*
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+        #[non_exhaustive]
+        pub enum $type {
+            $(
+                #[doc = concat!("`", stringify!($constant), "`")]
+                $variant,
+            )*
+            /// A message type this crate does not know about
+            Other(u16),
+        }
+
+        impl From<u16> for $type {
+            fn from(value: u16) -> Self {
+                match value {
+                    $($constant => $type::$variant,)*
+                    other => $type::Other(other),
+                }
+            }
+        }
+
+        impl From<$type> for u16 {
+            fn from(value: $type) -> Self {
+                match value {
+                    $($type::$variant => $constant,)*
+                    $type::Other(other) => other,
+                }
+            }
+        }
+
+        impl $type {
+            const NAMES: &'static [(u16, &'static str)] =
+                &[$(($constant, $name),)*];
+
+            pub fn from_raw(value: u16) -> Self {
+                value.into()
+            }
+
+            pub fn into_raw(self) -> u16 {
+                self.into()
+            }
+
+            /// Return the canonical name of the message type, as used in
+            /// the `type=` field of the audit logs
+            pub fn name(&self) -> Option<&'static str> {
+                match self {
+                    $($type::$variant => Some($name),)*
+                    $type::Other(other) => Self::other_name(*other),
+                }
+            }
+        }
+
+        impl fmt::Display for $type {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self.name() {
+                    Some(name) => f.write_str(name),
+                    None => write!(f, "UNKNOWN[{}]", u16::from(*self)),
+                }
+            }
+        }
+    };
+}
+
+message_types! {
+    /// Type of an audit netlink message (`AUDIT_*` constants)
+    pub enum AuditMessageType {
+        Get = AUDIT_GET, "GET";
+        Set = AUDIT_SET, "SET";
+        List = AUDIT_LIST, "LIST";
+        Add = AUDIT_ADD, "ADD";
+        Del = AUDIT_DEL, "DEL";
+        User = AUDIT_USER, "USER";
+        Login = AUDIT_LOGIN, "LOGIN";
+        WatchIns = AUDIT_WATCH_INS, "WATCH_INS";
+        WatchRem = AUDIT_WATCH_REM, "WATCH_REM";
+        WatchList = AUDIT_WATCH_LIST, "WATCH_LIST";
+        SignalInfo = AUDIT_SIGNAL_INFO, "SIGNAL_INFO";
+        AddRule = AUDIT_ADD_RULE, "ADD_RULE";
+        DelRule = AUDIT_DEL_RULE, "DEL_RULE";
+        ListRules = AUDIT_LIST_RULES, "LIST_RULES";
+        Trim = AUDIT_TRIM, "TRIM";
+        MakeEquiv = AUDIT_MAKE_EQUIV, "MAKE_EQUIV";
+        TtyGet = AUDIT_TTY_GET, "TTY_GET";
+        TtySet = AUDIT_TTY_SET, "TTY_SET";
+        SetFeature = AUDIT_SET_FEATURE, "SET_FEATURE";
+        GetFeature = AUDIT_GET_FEATURE, "GET_FEATURE";
+        UserAvc = AUDIT_USER_AVC, "USER_AVC";
+        UserTty = AUDIT_USER_TTY, "USER_TTY";
+        DaemonStart = AUDIT_DAEMON_START, "DAEMON_START";
+        DaemonEnd = AUDIT_DAEMON_END, "DAEMON_END";
+        DaemonAbort = AUDIT_DAEMON_ABORT, "DAEMON_ABORT";
+        DaemonConfig = AUDIT_DAEMON_CONFIG, "DAEMON_CONFIG";
+        DaemonReconfig = AUDIT_DAEMON_RECONFIG, "DAEMON_RECONFIG";
+        DaemonRotate = AUDIT_DAEMON_ROTATE, "DAEMON_ROTATE";
+        DaemonResume = AUDIT_DAEMON_RESUME, "DAEMON_RESUME";
+        DaemonAccept = AUDIT_DAEMON_ACCEPT, "DAEMON_ACCEPT";
+        DaemonClose = AUDIT_DAEMON_CLOSE, "DAEMON_CLOSE";
+        DaemonErr = AUDIT_DAEMON_ERR, "DAEMON_ERR";
+        Syscall = AUDIT_SYSCALL, "SYSCALL";
+        Path = AUDIT_PATH, "PATH";
+        Ipc = AUDIT_IPC, "IPC";
+        Socketcall = AUDIT_SOCKETCALL, "SOCKETCALL";
+        ConfigChange = AUDIT_CONFIG_CHANGE, "CONFIG_CHANGE";
+        Sockaddr = AUDIT_SOCKADDR, "SOCKADDR";
+        Cwd = AUDIT_CWD, "CWD";
+        Execve = AUDIT_EXECVE, "EXECVE";
+        IpcSetPerm = AUDIT_IPC_SET_PERM, "IPC_SET_PERM";
+        MqOpen = AUDIT_MQ_OPEN, "MQ_OPEN";
+        MqSendrecv = AUDIT_MQ_SENDRECV, "MQ_SENDRECV";
+        MqNotify = AUDIT_MQ_NOTIFY, "MQ_NOTIFY";
+        MqGetsetattr = AUDIT_MQ_GETSETATTR, "MQ_GETSETATTR";
+        KernelOther = AUDIT_KERNEL_OTHER, "KERNEL_OTHER";
+        FdPair = AUDIT_FD_PAIR, "FD_PAIR";
+        ObjPid = AUDIT_OBJ_PID, "OBJ_PID";
+        Tty = AUDIT_TTY, "TTY";
+        Eoe = AUDIT_EOE, "EOE";
+        BprmFcaps = AUDIT_BPRM_FCAPS, "BPRM_FCAPS";
+        Capset = AUDIT_CAPSET, "CAPSET";
+        Mmap = AUDIT_MMAP, "MMAP";
+        NetfilterPkt = AUDIT_NETFILTER_PKT, "NETFILTER_PKT";
+        NetfilterCfg = AUDIT_NETFILTER_CFG, "NETFILTER_CFG";
+        Seccomp = AUDIT_SECCOMP, "SECCOMP";
+        Proctitle = AUDIT_PROCTITLE, "PROCTITLE";
+        FeatureChange = AUDIT_FEATURE_CHANGE, "FEATURE_CHANGE";
+        Replace = AUDIT_REPLACE, "REPLACE";
+        KernModule = AUDIT_KERN_MODULE, "KERN_MODULE";
+        Fanotify = AUDIT_FANOTIFY, "FANOTIFY";
+        TimeInjoffset = AUDIT_TIME_INJOFFSET, "TIME_INJOFFSET";
+        TimeAdjntpval = AUDIT_TIME_ADJNTPVAL, "TIME_ADJNTPVAL";
+        Bpf = AUDIT_BPF, "BPF";
+        EventListener = AUDIT_EVENT_LISTENER, "EVENT_LISTENER";
+        Uringop = AUDIT_URINGOP, "URINGOP";
+        Openat2 = AUDIT_OPENAT2, "OPENAT2";
+        DmCtrl = AUDIT_DM_CTRL, "DM_CTRL";
+        DmEvent = AUDIT_DM_EVENT, "DM_EVENT";
+        Avc = AUDIT_AVC, "AVC";
+        SelinuxErr = AUDIT_SELINUX_ERR, "SELINUX_ERR";
+        AvcPath = AUDIT_AVC_PATH, "AVC_PATH";
+        MacPolicyLoad = AUDIT_MAC_POLICY_LOAD, "MAC_POLICY_LOAD";
+        MacStatus = AUDIT_MAC_STATUS, "MAC_STATUS";
+        MacConfigChange = AUDIT_MAC_CONFIG_CHANGE, "MAC_CONFIG_CHANGE";
+        MacUnlblAllow = AUDIT_MAC_UNLBL_ALLOW, "MAC_UNLBL_ALLOW";
+        MacCipsov4Add = AUDIT_MAC_CIPSOV4_ADD, "MAC_CIPSOV4_ADD";
+        MacCipsov4Del = AUDIT_MAC_CIPSOV4_DEL, "MAC_CIPSOV4_DEL";
+        MacMapAdd = AUDIT_MAC_MAP_ADD, "MAC_MAP_ADD";
+        MacMapDel = AUDIT_MAC_MAP_DEL, "MAC_MAP_DEL";
+        MacIpsecAddsa = AUDIT_MAC_IPSEC_ADDSA, "MAC_IPSEC_ADDSA";
+        MacIpsecDelsa = AUDIT_MAC_IPSEC_DELSA, "MAC_IPSEC_DELSA";
+        MacIpsecAddspd = AUDIT_MAC_IPSEC_ADDSPD, "MAC_IPSEC_ADDSPD";
+        MacIpsecDelspd = AUDIT_MAC_IPSEC_DELSPD, "MAC_IPSEC_DELSPD";
+        MacIpsecEvent = AUDIT_MAC_IPSEC_EVENT, "MAC_IPSEC_EVENT";
+        MacUnlblStcadd = AUDIT_MAC_UNLBL_STCADD, "MAC_UNLBL_STCADD";
+        MacUnlblStcdel = AUDIT_MAC_UNLBL_STCDEL, "MAC_UNLBL_STCDEL";
+        MacCalipsoAdd = AUDIT_MAC_CALIPSO_ADD, "MAC_CALIPSO_ADD";
+        MacCalipsoDel = AUDIT_MAC_CALIPSO_DEL, "MAC_CALIPSO_DEL";
+        AnomPromiscuous = AUDIT_ANOM_PROMISCUOUS, "ANOM_PROMISCUOUS";
+        AnomAbend = AUDIT_ANOM_ABEND, "ANOM_ABEND";
+        AnomLink = AUDIT_ANOM_LINK, "ANOM_LINK";
+        AnomCreat = AUDIT_ANOM_CREAT, "ANOM_CREAT";
+        IntegrityData = AUDIT_INTEGRITY_DATA, "INTEGRITY_DATA";
+        IntegrityMetadata = AUDIT_INTEGRITY_METADATA, "INTEGRITY_METADATA";
+        IntegrityStatus = AUDIT_INTEGRITY_STATUS, "INTEGRITY_STATUS";
+        IntegrityHash = AUDIT_INTEGRITY_HASH, "INTEGRITY_HASH";
+        IntegrityPcr = AUDIT_INTEGRITY_PCR, "INTEGRITY_PCR";
+        IntegrityRule = AUDIT_INTEGRITY_RULE, "INTEGRITY_RULE";
+        IntegrityEvmXattr = AUDIT_INTEGRITY_EVM_XATTR, "INTEGRITY_EVM_XATTR";
+        IntegrityPolicyRule = AUDIT_INTEGRITY_POLICY_RULE, "INTEGRITY_POLICY_RULE";
+        Kernel = AUDIT_KERNEL, "KERNEL";
+    }
+}
+
+message_types! {
+    /// Type of a message sent by a trusted userspace application (see
+    /// [`UserMessage`](crate::UserMessage)). The names are the ones
+    /// defined by libaudit.
+    pub enum UserMessageKind {
+        User = AUDIT_USER, "USER";
+        UserAuth = AUDIT_USER_AUTH, "USER_AUTH";
+        UserAcct = AUDIT_USER_ACCT, "USER_ACCT";
+        UserMgmt = AUDIT_USER_MGMT, "USER_MGMT";
+        CredAcq = AUDIT_CRED_ACQ, "CRED_ACQ";
+        CredDisp = AUDIT_CRED_DISP, "CRED_DISP";
+        UserStart = AUDIT_USER_START, "USER_START";
+        UserEnd = AUDIT_USER_END, "USER_END";
+        UserAvc = AUDIT_USER_AVC, "USER_AVC";
+        UserChauthtok = AUDIT_USER_CHAUTHTOK, "USER_CHAUTHTOK";
+        UserErr = AUDIT_USER_ERR, "USER_ERR";
+        CredRefr = AUDIT_CRED_REFR, "CRED_REFR";
+        UsysConfig = AUDIT_USYS_CONFIG, "USYS_CONFIG";
+        UserLogin = AUDIT_USER_LOGIN, "USER_LOGIN";
+        UserLogout = AUDIT_USER_LOGOUT, "USER_LOGOUT";
+        AddUser = AUDIT_ADD_USER, "ADD_USER";
+        DelUser = AUDIT_DEL_USER, "DEL_USER";
+        AddGroup = AUDIT_ADD_GROUP, "ADD_GROUP";
+        DelGroup = AUDIT_DEL_GROUP, "DEL_GROUP";
+        DacCheck = AUDIT_DAC_CHECK, "DAC_CHECK";
+        ChgrpId = AUDIT_CHGRP_ID, "CHGRP_ID";
+        Test = AUDIT_TEST, "TEST";
+        TrustedApp = AUDIT_TRUSTED_APP, "TRUSTED_APP";
+        UserSelinuxErr = AUDIT_USER_SELINUX_ERR, "USER_SELINUX_ERR";
+        UserCmd = AUDIT_USER_CMD, "USER_CMD";
+        UserTty = AUDIT_USER_TTY, "USER_TTY";
+        ChuserId = AUDIT_CHUSER_ID, "CHUSER_ID";
+        GrpAuth = AUDIT_GRP_AUTH, "GRP_AUTH";
+        SystemBoot = AUDIT_SYSTEM_BOOT, "SYSTEM_BOOT";
+        SystemShutdown = AUDIT_SYSTEM_SHUTDOWN, "SYSTEM_SHUTDOWN";
+        SystemRunlevel = AUDIT_SYSTEM_RUNLEVEL, "SYSTEM_RUNLEVEL";
+        ServiceStart = AUDIT_SERVICE_START, "SERVICE_START";
+        ServiceStop = AUDIT_SERVICE_STOP, "SERVICE_STOP";
+        GrpMgmt = AUDIT_GRP_MGMT, "GRP_MGMT";
+        GrpChauthtok = AUDIT_GRP_CHAUTHTOK, "GRP_CHAUTHTOK";
+        MacCheck = AUDIT_MAC_CHECK, "MAC_CHECK";
+        AcctLock = AUDIT_ACCT_LOCK, "ACCT_LOCK";
+        AcctUnlock = AUDIT_ACCT_UNLOCK, "ACCT_UNLOCK";
+        UserDevice = AUDIT_USER_DEVICE, "USER_DEVICE";
+        SoftwareUpdate = AUDIT_SOFTWARE_UPDATE, "SOFTWARE_UPDATE";
+        AnomLoginFailures = AUDIT_ANOM_LOGIN_FAILURES, "ANOM_LOGIN_FAILURES";
+        AnomLoginTime = AUDIT_ANOM_LOGIN_TIME, "ANOM_LOGIN_TIME";
+        AnomLoginSessions = AUDIT_ANOM_LOGIN_SESSIONS, "ANOM_LOGIN_SESSIONS";
+        AnomLoginAcct = AUDIT_ANOM_LOGIN_ACCT, "ANOM_LOGIN_ACCT";
+        AnomLoginLocation = AUDIT_ANOM_LOGIN_LOCATION, "ANOM_LOGIN_LOCATION";
+        AnomMaxDac = AUDIT_ANOM_MAX_DAC, "ANOM_MAX_DAC";
+        AnomMaxMac = AUDIT_ANOM_MAX_MAC, "ANOM_MAX_MAC";
+        AnomAmtuFail = AUDIT_ANOM_AMTU_FAIL, "ANOM_AMTU_FAIL";
+        AnomRbacFail = AUDIT_ANOM_RBAC_FAIL, "ANOM_RBAC_FAIL";
+        AnomRbacIntegrityFail = AUDIT_ANOM_RBAC_INTEGRITY_FAIL, "ANOM_RBAC_INTEGRITY_FAIL";
+        AnomCryptoFail = AUDIT_ANOM_CRYPTO_FAIL, "ANOM_CRYPTO_FAIL";
+        AnomAccessFs = AUDIT_ANOM_ACCESS_FS, "ANOM_ACCESS_FS";
+        AnomExec = AUDIT_ANOM_EXEC, "ANOM_EXEC";
+        AnomMkExec = AUDIT_ANOM_MK_EXEC, "ANOM_MK_EXEC";
+        AnomAddAcct = AUDIT_ANOM_ADD_ACCT, "ANOM_ADD_ACCT";
+        AnomDelAcct = AUDIT_ANOM_DEL_ACCT, "ANOM_DEL_ACCT";
+        AnomModAcct = AUDIT_ANOM_MOD_ACCT, "ANOM_MOD_ACCT";
+        AnomRootTrans = AUDIT_ANOM_ROOT_TRANS, "ANOM_ROOT_TRANS";
+        AnomLoginService = AUDIT_ANOM_LOGIN_SERVICE, "ANOM_LOGIN_SERVICE";
+        AnomLoginRoot = AUDIT_ANOM_LOGIN_ROOT, "ANOM_LOGIN_ROOT";
+        AnomOriginFailures = AUDIT_ANOM_ORIGIN_FAILURES, "ANOM_ORIGIN_FAILURES";
+        AnomSession = AUDIT_ANOM_SESSION, "ANOM_SESSION";
+        RespAnomaly = AUDIT_RESP_ANOMALY, "RESP_ANOMALY";
+        RespAlert = AUDIT_RESP_ALERT, "RESP_ALERT";
+        RespKillProc = AUDIT_RESP_KILL_PROC, "RESP_KILL_PROC";
+        RespTermAccess = AUDIT_RESP_TERM_ACCESS, "RESP_TERM_ACCESS";
+        RespAcctRemote = AUDIT_RESP_ACCT_REMOTE, "RESP_ACCT_REMOTE";
+        RespAcctLockTimed = AUDIT_RESP_ACCT_LOCK_TIMED, "RESP_ACCT_LOCK_TIMED";
+        RespAcctUnlockTimed = AUDIT_RESP_ACCT_UNLOCK_TIMED, "RESP_ACCT_UNLOCK_TIMED";
+        RespAcctLock = AUDIT_RESP_ACCT_LOCK, "RESP_ACCT_LOCK";
+        RespTermLock = AUDIT_RESP_TERM_LOCK, "RESP_TERM_LOCK";
+        RespSebool = AUDIT_RESP_SEBOOL, "RESP_SEBOOL";
+        RespExec = AUDIT_RESP_EXEC, "RESP_EXEC";
+        RespSingle = AUDIT_RESP_SINGLE, "RESP_SINGLE";
+        RespHalt = AUDIT_RESP_HALT, "RESP_HALT";
+        RespOriginBlock = AUDIT_RESP_ORIGIN_BLOCK, "RESP_ORIGIN_BLOCK";
+        RespOriginBlockTimed = AUDIT_RESP_ORIGIN_BLOCK_TIMED, "RESP_ORIGIN_BLOCK_TIMED";
+        UserRoleChange = AUDIT_USER_ROLE_CHANGE, "USER_ROLE_CHANGE";
+        RoleAssign = AUDIT_ROLE_ASSIGN, "ROLE_ASSIGN";
+        RoleRemove = AUDIT_ROLE_REMOVE, "ROLE_REMOVE";
+        LabelOverride = AUDIT_LABEL_OVERRIDE, "LABEL_OVERRIDE";
+        LabelLevelChange = AUDIT_LABEL_LEVEL_CHANGE, "LABEL_LEVEL_CHANGE";
+        UserLabeledExport = AUDIT_USER_LABELED_EXPORT, "USER_LABELED_EXPORT";
+        UserUnlabeledExport = AUDIT_USER_UNLABELED_EXPORT, "USER_UNLABELED_EXPORT";
+        DevAlloc = AUDIT_DEV_ALLOC, "DEV_ALLOC";
+        DevDealloc = AUDIT_DEV_DEALLOC, "DEV_DEALLOC";
+        FsRelabel = AUDIT_FS_RELABEL, "FS_RELABEL";
+        UserMacPolicyLoad = AUDIT_USER_MAC_POLICY_LOAD, "USER_MAC_POLICY_LOAD";
+        RoleModify = AUDIT_ROLE_MODIFY, "ROLE_MODIFY";
+        UserMacConfigChange = AUDIT_USER_MAC_CONFIG_CHANGE, "USER_MAC_CONFIG_CHANGE";
+        UserMacStatus = AUDIT_USER_MAC_STATUS, "USER_MAC_STATUS";
+        CryptoTestUser = AUDIT_CRYPTO_TEST_USER, "CRYPTO_TEST_USER";
+        CryptoParamChangeUser = AUDIT_CRYPTO_PARAM_CHANGE_USER, "CRYPTO_PARAM_CHANGE_USER";
+        CryptoLogin = AUDIT_CRYPTO_LOGIN, "CRYPTO_LOGIN";
+        CryptoLogout = AUDIT_CRYPTO_LOGOUT, "CRYPTO_LOGOUT";
+        CryptoKeyUser = AUDIT_CRYPTO_KEY_USER, "CRYPTO_KEY_USER";
+        CryptoFailureUser = AUDIT_CRYPTO_FAILURE_USER, "CRYPTO_FAILURE_USER";
+        CryptoReplayUser = AUDIT_CRYPTO_REPLAY_USER, "CRYPTO_REPLAY_USER";
+        CryptoSession = AUDIT_CRYPTO_SESSION, "CRYPTO_SESSION";
+        CryptoIkeSa = AUDIT_CRYPTO_IKE_SA, "CRYPTO_IKE_SA";
+        CryptoIpsecSa = AUDIT_CRYPTO_IPSEC_SA, "CRYPTO_IPSEC_SA";
+        VirtControl = AUDIT_VIRT_CONTROL, "VIRT_CONTROL";
+        VirtResource = AUDIT_VIRT_RESOURCE, "VIRT_RESOURCE";
+        VirtMachineId = AUDIT_VIRT_MACHINE_ID, "VIRT_MACHINE_ID";
+        VirtIntegrityCheck = AUDIT_VIRT_INTEGRITY_CHECK, "VIRT_INTEGRITY_CHECK";
+        VirtCreate = AUDIT_VIRT_CREATE, "VIRT_CREATE";
+        VirtDestroy = AUDIT_VIRT_DESTROY, "VIRT_DESTROY";
+        VirtMigrateIn = AUDIT_VIRT_MIGRATE_IN, "VIRT_MIGRATE_IN";
+        VirtMigrateOut = AUDIT_VIRT_MIGRATE_OUT, "VIRT_MIGRATE_OUT";
+    }
+}
+
+impl AuditMessageType {
+    /// The user message types are not variants of `AuditMessageType`, but
+    /// they are named after the [`UserMessageKind`] they map to
+    fn other_name(message_type: u16) -> Option<&'static str> {
+        UserMessageKind::from(message_type).name()
+    }
+}
+
+impl UserMessageKind {
+    fn other_name(_: u16) -> Option<&'static str> {
+        None
+    }
+
+    /// Return `true` if messages of the given type come from a trusted
+    /// userspace application, and are parsed as
+    /// [`AuditMessage::User`](crate::AuditMessage::User)
+    pub fn is_user_message(message_type: u16) -> bool {
+        matches!(
+            message_type,
+            AUDIT_USER
+                | AUDIT_FIRST_USER_MSG..=AUDIT_LAST_USER_MSG
+                | AUDIT_FIRST_USER_MSG2..=AUDIT_LAST_USER_MSG2
+        )
+    }
+}
+
+/// Return the name of a message type, as used in the `type=` field of the
+/// audit logs. This is the equivalent of libaudit's
+/// `audit_msg_type_to_name()`.
+pub fn message_type_name(message_type: u16) -> Option<&'static str> {
+    AuditMessageType::from(message_type).name()
+}
+
+/// Return the message type with the given name (case insensitive). As with
+/// libaudit's `audit_name_to_msg_type()`, numbers and the `UNKNOWN[1234]`
+/// names produced by the `Display` implementations are accepted too.
+pub fn message_type_from_name(name: &str) -> Option<u16> {
+    AuditMessageType::NAMES
+        .iter()
+        .chain(UserMessageKind::NAMES)
+        .find(|(_, n)| n.eq_ignore_ascii_case(name))
+        .map(|(message_type, _)| *message_type)
+        .or_else(|| {
+            name.strip_prefix("UNKNOWN[")
+                .and_then(|s| s.strip_suffix(']'))
+                .unwrap_or(name)
+                .parse()
+                .ok()
+        })
+}
+
+/// Return `true` for the audit event records generated by the kernel
+/// (1300 - 1399)
+pub fn is_kernel_event_type(message_type: u16) -> bool {
+    (AUDIT_EVENT_MESSAGE_MIN..=AUDIT_EVENT_MESSAGE_MAX).contains(&message_type)
+}
+
+/// Return `true` for the messages sent by userspace applications (1100 -
+/// 1199 and 2100 - 2999). Unlike [`UserMessageKind::is_user_message`],
+/// this does not include the legacy `AUDIT_USER` message type.
+pub fn is_user_space_type(message_type: u16) -> bool {
+    matches!(
+        message_type,
+        AUDIT_FIRST_USER_MSG..=AUDIT_LAST_USER_MSG
+            | AUDIT_FIRST_USER_MSG2..=AUDIT_LAST_USER_MSG2
+    )
+}
+
+/// Return `true` for the anomalies detected by the kernel (1700 - 1799) or
+/// by userspace (2100 - 2199)
+pub fn is_anomaly_type(message_type: u16) -> bool {
+    matches!(
+        message_type,
+        AUDIT_FIRST_KERN_ANOM_MSG..=AUDIT_LAST_KERN_ANOM_MSG
+            | AUDIT_FIRST_ANOM_MSG..=AUDIT_LAST_ANOM_MSG
+    )
+}
+
+/// Return `true` for the kernel integrity events (1800 - 1899)
+pub fn is_integrity_type(message_type: u16) -> bool {
+    (1800..=1899).contains(&message_type)
+}
+
+/// Return `true` for the responses to anomalies (2200 - 2299)
+pub fn is_response_type(message_type: u16) -> bool {
+    (AUDIT_FIRST_ANOM_RESP..=AUDIT_LAST_ANOM_RESP).contains(&message_type)
+}
+
+/// Category of an audit message type, according to the ranges documented
+/// in the kernel's `linux/audit.h`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum AuditMessageClass {
+    /// 1000 - 1099: commands to the audit system
+    Control,
+    /// 1100 - 1199 and 2100 - 2999: messages from trusted userspace
+    /// applications
+    User,
+    /// 1200 - 1299: messages internal to the audit daemon
+    Daemon,
+    /// 1300 - 1399: audit event messages
+    Event,
+    /// 1400 - 1499: SELinux messages
+    Selinux,
+    /// 1500 - 1599: kernel LSPP events
+    Lspp,
+    /// 1600 - 1699: kernel crypto events
+    Crypto,
+    /// 1700 - 1799: kernel anomaly records
+    Anomaly,
+    /// 1800 - 1899: kernel integrity events
+    Integrity,
+    /// 2000: otherwise unclassified kernel messages
+    Kernel,
+    /// Any other message type
+    Unknown,
+}
+
+impl AuditMessageClass {
+    pub fn from_message_type(message_type: u16) -> Self {
+        use self::AuditMessageClass::*;
+
+        match message_type {
+            1000..=1099 => Control,
+            AUDIT_FIRST_USER_MSG..=AUDIT_LAST_USER_MSG => User,
+            AUDIT_FIRST_USER_MSG2..=AUDIT_LAST_USER_MSG2 => User,
+            1200..=1299 => Daemon,
+            AUDIT_EVENT_MESSAGE_MIN..=AUDIT_EVENT_MESSAGE_MAX => Event,
+            1400..=1499 => Selinux,
+            1500..=1599 => Lspp,
+            1600..=1699 => Crypto,
+            AUDIT_FIRST_KERN_ANOM_MSG..=AUDIT_LAST_KERN_ANOM_MSG => Anomaly,
+            1800..=1899 => Integrity,
+            AUDIT_KERNEL => Kernel,
+            _ => Unknown,
+        }
+    }
+}
+
+impl AuditMessageType {
+    /// Return the category of the message type. This also works for the
+    /// message types this crate does not know about.
+    pub fn class(&self) -> AuditMessageClass {
+        AuditMessageClass::from_message_type((*self).into())
+    }
+}
+
+impl PartialEq<u16> for AuditMessageType {
+    fn eq(&self, other: &u16) -> bool {
+        u16::from(*self) == *other
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_message_type() {
+        let syscall = AuditMessageType::from(AUDIT_SYSCALL);
+        assert_eq!(syscall, AuditMessageType::Syscall);
+        assert_eq!(syscall, AUDIT_SYSCALL);
+        assert_eq!(syscall.to_string(), "SYSCALL");
+        assert_eq!(
+            AuditMessageType::from(AUDIT_USER_AVC).to_string(),
+            "USER_AVC"
+        );
+        let login = AuditMessageType::from(AUDIT_USER_LOGIN);
+        assert_eq!(login, AuditMessageType::Other(AUDIT_USER_LOGIN));
+        assert_eq!(login.name(), Some("USER_LOGIN"));
+        assert_eq!(login.to_string(), "USER_LOGIN");
+
+        let unknown = AuditMessageType::from(1399);
+        assert_eq!(unknown, AuditMessageType::Other(1399));
+        assert_eq!(u16::from(unknown), 1399);
+        assert_eq!(unknown.to_string(), "UNKNOWN[1399]");
+        assert_eq!(unknown.class(), AuditMessageClass::Event);
+        assert_eq!(syscall.class(), AuditMessageClass::Event);
+        assert_eq!(
+            AuditMessageType::from(2150).class(),
+            AuditMessageClass::User
+        );
+        assert_eq!(
+            AuditMessageType::from(AUDIT_ANOM_ABEND).class(),
+            AuditMessageClass::Anomaly
+        );
+        assert_eq!(
+            AuditMessageType::from(1950).class(),
+            AuditMessageClass::Unknown
+        );
+    }
+
+    #[test]
+    fn test_user_message_kind() {
+        let login = UserMessageKind::from(AUDIT_USER_LOGIN);
+        assert_eq!(login, UserMessageKind::UserLogin);
+        assert_eq!(login.to_string(), "USER_LOGIN");
+        assert_eq!(UserMessageKind::from(2999).to_string(), "UNKNOWN[2999]");
+        assert!(UserMessageKind::is_user_message(AUDIT_USER));
+        assert!(UserMessageKind::is_user_message(AUDIT_USER_AVC));
+        assert!(UserMessageKind::is_user_message(2999));
+        assert!(!UserMessageKind::is_user_message(AUDIT_GET));
+        assert!(!UserMessageKind::is_user_message(AUDIT_DAEMON_START));
+    }
+
+    #[test]
+    fn test_type_ranges() {
+        assert!(is_kernel_event_type(AUDIT_SYSCALL));
+        assert!(!is_kernel_event_type(AUDIT_AVC));
+        assert!(is_user_space_type(AUDIT_USER_LOGIN));
+        assert!(is_user_space_type(AUDIT_ANOM_LOGIN_FAILURES));
+        assert!(!is_user_space_type(AUDIT_USER));
+        assert!(is_anomaly_type(AUDIT_ANOM_PROMISCUOUS));
+        assert!(is_anomaly_type(AUDIT_ANOM_LOGIN_FAILURES));
+        assert!(!is_anomaly_type(AUDIT_RESP_ANOMALY));
+        assert!(is_integrity_type(AUDIT_INTEGRITY_DATA));
+        assert!(!is_integrity_type(AUDIT_KERNEL));
+        assert!(is_response_type(AUDIT_RESP_ANOMALY));
+        assert!(!is_response_type(AUDIT_USER_ROLE_CHANGE));
+    }
+
+    #[test]
+    fn test_name_lookup() {
+        assert_eq!(message_type_name(AUDIT_SYSCALL), Some("SYSCALL"));
+        assert_eq!(message_type_name(AUDIT_USER_LOGIN), Some("USER_LOGIN"));
+        assert_eq!(message_type_name(AUDIT_VIRT_CONTROL), Some("VIRT_CONTROL"));
+        assert_eq!(message_type_name(1999), None);
+
+        assert_eq!(message_type_from_name("SYSCALL"), Some(AUDIT_SYSCALL));
+        assert_eq!(
+            message_type_from_name("user_login"),
+            Some(AUDIT_USER_LOGIN)
+        );
+        assert_eq!(message_type_from_name("UNKNOWN[1999]"), Some(1999));
+        assert_eq!(message_type_from_name("1300"), Some(AUDIT_SYSCALL));
+        assert_eq!(message_type_from_name("NOT_A_TYPE"), None);
+
+        for message_type in 1000..3000 {
+            if let Some(name) = message_type_name(message_type) {
+                assert_eq!(message_type_from_name(name), Some(message_type));
+            }
+        }
+    }
+}
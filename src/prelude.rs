@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: MIT
+
+//! Re-exports of the types most applications need to send requests to the
+//! kernel and parse what it sends back.
+//!
+//! ```
+//! use netlink_packet_audit::prelude::*;
+//!
+//! let mut rule = RuleMessage::new();
+//! rule.flags = RuleFlags::FilterExit;
+//! rule.action = RuleAction::Always;
+//! rule.fields.push((RuleField::Filterkey("ls".into()), RuleFieldFlags::Equal));
+//! let mut request = requests::add_rule(rule);
+//! request.header.sequence_number = 1;
+//!
+//! let mut buf = vec![0; request.buffer_len()];
+//! request.serialize(&mut buf);
+//!
+//! let mut framer = AuditFramer::new();
+//! framer.push(&buf).unwrap();
+//! let message = framer.next_audit_message().unwrap().unwrap();
+//! assert_eq!(message.message_type(), AuditMessageType::AddRule);
+//! ```
+
+pub use netlink_packet_core::{NetlinkHeader, NetlinkMessage, NetlinkPayload};
+
+pub use crate::{
+    into_audit_result, requests, resolve_reply, AuditDecodeError,
+    AuditEmitError, AuditFramer, AuditFramerBuilder, AuditMessage,
+    AuditMessageClass, AuditMessageType, AuditNetlinkError, AuditReply,
+    EventId, FeaturesMessage, LengthPolicy, NetlinkAuditCodec, RuleAction,
+    RuleField, RuleFieldFlags, RuleFlags, RuleMessage, RuleSyscalls,
+    StatusMessage, Utf8Policy,
+};
@@ -0,0 +1,186 @@
+// SPDX-License-Identifier: MIT
+
+use std::borrow::Cow;
+
+use crate::{filter::decoded, AuditMessage};
+
+/// Fields the kernel logs hex encoded when they contain special characters
+const ENCODED_FIELDS: [&str; 8] = [
+    "comm",
+    "cwd",
+    "data",
+    "exe",
+    "key",
+    "name",
+    "path",
+    "proctitle",
+];
+
+/// A `key=value` field of a record, returned by [`RecordFields`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RecordField<'a> {
+    pub name: &'a str,
+    /// Value of the field, without its quotes
+    pub value: &'a str,
+    /// Whether the value was quoted. The kernel quotes the strings it logs
+    /// as is, and hex encodes the others.
+    pub quoted: bool,
+}
+
+impl<'a> RecordField<'a> {
+    /// Return the value of the field, decoded if the kernel hex encoded
+    /// it. The arguments of `proctitle` are separated by spaces.
+    pub fn decoded(&self) -> Cow<'a, str> {
+        if self.quoted || !ENCODED_FIELDS.contains(&self.name) {
+            return Cow::Borrowed(self.value);
+        }
+        let value = decoded(self.value);
+        if self.name == "proctitle" && value.contains('\0') {
+            Cow::Owned(value.replace('\0', " "))
+        } else {
+            value
+        }
+    }
+}
+
+/// Iterator over the `key=value` fields of a record, returned by
+/// [`AuditMessage::fields`].
+///
+/// Quoted values (`name="/etc/passwd"` or `msg='op=login'`) are returned
+/// without their quotes. The values are not interpreted: hex encoded
+/// strings are returned as is, and can be decoded with
+/// [`RecordField::decoded`].
+#[derive(Debug, Clone)]
+pub struct RecordFields<'a> {
+    rest: &'a str,
+}
+
+impl<'a> RecordFields<'a> {
+    /// Iterate over the fields of a record. The `audit(...): ` prefix, if
+    /// any, is skipped.
+    pub fn new(record: &'a str) -> Self {
+        let rest = match record.strip_prefix("audit(") {
+            Some(rest) => rest.split_once("): ").map_or("", |(_, rest)| rest),
+            None => record,
+        };
+        RecordFields { rest }
+    }
+}
+
+impl<'a> Iterator for RecordFields<'a> {
+    type Item = RecordField<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = self.rest.trim_start_matches([' ', '\n', '\0']);
+            if rest.is_empty() {
+                self.rest = rest;
+                return None;
+            }
+            let (token_end, key_end) = match rest.find([' ', '=']) {
+                Some(i) if rest.as_bytes()[i] == b'=' => (None, i),
+                Some(i) => (Some(i), i),
+                None => (Some(rest.len()), rest.len()),
+            };
+            if let Some(end) = token_end {
+                // a word without a value: skip it
+                self.rest = &rest[end..];
+                continue;
+            }
+            let key = &rest[..key_end];
+            let value = &rest[key_end + 1..];
+            let quoted = matches!(value.chars().next(), Some('"' | '\''));
+            let (value, next) = if quoted {
+                let quote = value.as_bytes()[0] as char;
+                let value = &value[1..];
+                match value.find(quote) {
+                    Some(end) => (&value[..end], &value[end + 1..]),
+                    None => (value, ""),
+                }
+            } else {
+                let end = value.find(' ').unwrap_or(value.len());
+                (&value[..end], &value[end..])
+            };
+            self.rest = next;
+            return Some(RecordField {
+                name: key,
+                value: value.trim_end_matches(['\n', '\0']),
+                quoted,
+            });
+        }
+    }
+}
+
+impl AuditMessage {
+    /// Return an iterator over the `key=value` fields of the record, or
+    /// `None` if the message has no text payload or if it is not valid
+    /// UTF-8
+    pub fn fields(&self) -> Option<RecordFields<'_>> {
+        self.data()
+            .and_then(|data| std::str::from_utf8(data).ok())
+            .map(RecordFields::new)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn pairs(record: &str) -> Vec<(&str, &str)> {
+        RecordFields::new(record)
+            .map(|field| (field.name, field.value))
+            .collect()
+    }
+
+    #[test]
+    fn test_fields() {
+        let fields = pairs(
+            "audit(1592992200.383:66): arch=c000003e syscall=59 \
+            name=\"/bin/ls\" comm=636F6D6D key=(null)\n",
+        );
+        assert_eq!(
+            fields,
+            vec![
+                ("arch", "c000003e"),
+                ("syscall", "59"),
+                ("name", "/bin/ls"),
+                ("comm", "636F6D6D"),
+                ("key", "(null)"),
+            ]
+        );
+
+        let fields = pairs(
+            "audit(1.000:2): pid=1 msg='op=login acct=\"root\" res=success'",
+        );
+        assert_eq!(
+            fields,
+            vec![("pid", "1"), ("msg", "op=login acct=\"root\" res=success"),]
+        );
+
+        let fields = pairs("lonely word a= b=\"unterminated");
+        assert_eq!(fields, vec![("a", ""), ("b", "unterminated")]);
+    }
+
+    #[test]
+    fn test_decoded() {
+        let fields: Vec<_> = RecordFields::new(
+            "comm=\"CAFE\" key=\"1234\" name=\"DEADBEEF\" exe=2F62696E2F6C73 \
+            proctitle=6C73002D6C syscall=1234",
+        )
+        .map(|field| (field.name, field.decoded().into_owned(), field.quoted))
+        .collect();
+        let expected = [
+            ("comm", "CAFE", true),
+            ("key", "1234", true),
+            ("name", "DEADBEEF", true),
+            ("exe", "/bin/ls", false),
+            ("proctitle", "ls -l", false),
+            ("syscall", "1234", false),
+        ];
+        assert_eq!(fields.len(), expected.len());
+        for (field, (name, value, quoted)) in fields.iter().zip(expected) {
+            assert_eq!(*field, (name, value.to_string(), quoted));
+        }
+    }
+}
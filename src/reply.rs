@@ -0,0 +1,215 @@
+// SPDX-License-Identifier: MIT
+
+use netlink_packet_core::{
+    ErrorMessage, NetlinkMessage, NetlinkPayload, NLM_F_ACK, NLM_F_DUMP,
+};
+
+use crate::{error::is_error, AuditMessage, AuditNetlinkError};
+
+/// What marks the end of the kernel's answer to a request
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Completion {
+    /// A single reply
+    Reply,
+    /// An acknowledgement
+    Ack,
+    /// A `NLMSG_DONE` message, after any number of replies
+    Done,
+}
+
+/// Collect the answer to a request from the messages received on the
+/// socket, however they are split across reads.
+///
+/// The answer is complete after the first reply for queries, after the
+/// acknowledgement for commands (`NLM_F_ACK`), and after `NLMSG_DONE` for
+/// multipart requests such as `AUDIT_LIST_RULES` (`NLM_F_DUMP`). The
+/// events received in the meantime, which have no sequence number, are
+/// set aside for the caller to process; the answers to other requests
+/// are dropped.
+///
+/// ```
+/// use netlink_packet_audit::{prelude::*, ReplyCollector};
+///
+/// let mut request = requests::list_rules();
+/// request.header.sequence_number = 1;
+/// let mut collector = ReplyCollector::new(&request);
+/// # let received: Vec<NetlinkMessage<AuditMessage>> = vec![];
+/// for msg in received {
+///     if collector.push(msg)? {
+///         break;
+///     }
+/// }
+/// let rules = collector.into_replies();
+/// # Ok::<(), AuditNetlinkError>(())
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct ReplyCollector {
+    sequence_number: u32,
+    completion: Completion,
+    complete: bool,
+    ack: Option<ErrorMessage>,
+    replies: Vec<AuditMessage>,
+    events: Vec<AuditMessage>,
+}
+
+impl ReplyCollector {
+    /// Start collecting the answer to `request`, which must carry its
+    /// final sequence number and flags
+    pub fn new(request: &NetlinkMessage<AuditMessage>) -> Self {
+        let flags = request.header.flags;
+        let completion = if flags & NLM_F_DUMP == NLM_F_DUMP {
+            Completion::Done
+        } else if flags & NLM_F_ACK != 0 {
+            Completion::Ack
+        } else {
+            Completion::Reply
+        };
+        ReplyCollector {
+            sequence_number: request.header.sequence_number,
+            completion,
+            complete: false,
+            ack: None,
+            replies: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    /// Handle a received message, and return `true` once the answer is
+    /// complete. An error reported by the kernel for the request (a
+    /// negative code) is returned, and also completes the answer.
+    pub fn push(
+        &mut self,
+        message: NetlinkMessage<AuditMessage>,
+    ) -> Result<bool, AuditNetlinkError> {
+        let sequence_number = message.header.sequence_number;
+        let (header, payload) = message.into_parts();
+        if sequence_number != self.sequence_number || self.complete {
+            if let (0, NetlinkPayload::InnerMessage(event)) =
+                (sequence_number, payload)
+            {
+                self.events.push(event);
+            }
+            return Ok(self.complete);
+        }
+        self.complete = match payload {
+            NetlinkPayload::InnerMessage(msg) => {
+                self.replies.push(msg);
+                self.completion == Completion::Reply
+            }
+            NetlinkPayload::Error(ref e) if is_error(e) => {
+                self.complete = true;
+                return Err(AuditNetlinkError::new(&header, e));
+            }
+            NetlinkPayload::Error(e) => {
+                self.ack = Some(e);
+                self.completion == Completion::Ack
+            }
+            NetlinkPayload::Done(_) => true,
+            _ => false,
+        };
+        Ok(self.complete)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Return the acknowledgement of the request, if one was received.
+    /// Its code is zero, or the value some requests are acknowledged with
+    /// (see [`StatusMessage::lost_from_ack`]).
+    ///
+    /// [`StatusMessage::lost_from_ack`]: crate::StatusMessage::lost_from_ack
+    pub fn ack(&self) -> Option<&ErrorMessage> {
+        self.ack.as_ref()
+    }
+
+    /// Return the events received so far, and forget them
+    pub fn take_events(&mut self) -> Vec<AuditMessage> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Return the messages the kernel answered with
+    pub fn into_replies(self) -> Vec<AuditMessage> {
+        self.replies
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use netlink_packet_core::{DoneMessage, ErrorMessage, NetlinkHeader};
+
+    use super::*;
+    use crate::{
+        constants::*, requests, EventPayload, RuleMessage, StatusMessage,
+    };
+
+    fn message(
+        sequence_number: u32,
+        payload: NetlinkPayload<AuditMessage>,
+    ) -> NetlinkMessage<AuditMessage> {
+        let mut header = NetlinkHeader::default();
+        header.sequence_number = sequence_number;
+        NetlinkMessage::new(header, payload)
+    }
+
+    #[test]
+    fn test_multipart() {
+        let request = requests::with_sequence(requests::list_rules(), 5, 0);
+        let mut collector = ReplyCollector::new(&request);
+        let rule = AuditMessage::ListRules(Some(RuleMessage::new()));
+        let event =
+            AuditMessage::Event(EventPayload::new(AUDIT_EOE.into(), vec![]));
+
+        let reply = message(5, NetlinkPayload::InnerMessage(rule.clone()));
+        assert_eq!(collector.push(reply.clone()), Ok(false));
+        let unsolicited = NetlinkPayload::InnerMessage(event.clone());
+        assert_eq!(collector.push(message(0, unsolicited)), Ok(false));
+        let stale = NetlinkPayload::InnerMessage(rule.clone());
+        assert_eq!(collector.push(message(4, stale)), Ok(false));
+        assert_eq!(collector.push(reply), Ok(false));
+        assert!(!collector.is_complete());
+        let done = NetlinkPayload::Done(DoneMessage::default());
+        assert_eq!(collector.push(message(5, done)), Ok(true));
+        assert_eq!(collector.take_events(), vec![event]);
+        assert_eq!(collector.into_replies(), vec![rule.clone(), rule]);
+    }
+
+    #[test]
+    fn test_completion() {
+        let status = AuditMessage::GetStatus(Some(Default::default()));
+        let request = requests::with_sequence(requests::get_status(), 1, 0);
+        let mut collector = ReplyCollector::new(&request);
+        let reply = NetlinkPayload::InnerMessage(status.clone());
+        assert_eq!(collector.push(message(1, reply)), Ok(true));
+        assert_eq!(collector.into_replies(), vec![status]);
+
+        let request = requests::with_sequence(requests::trim(), 2, 0);
+        let mut collector = ReplyCollector::new(&request);
+        let ack = NetlinkPayload::Error(ErrorMessage::default());
+        assert_eq!(collector.push(message(2, ack)), Ok(true));
+        assert_eq!(collector.ack(), Some(&ErrorMessage::default()));
+
+        let reset = requests::with_sequence(
+            requests::set_status(StatusMessage::reset_lost()),
+            3,
+            0,
+        );
+        let mut collector = ReplyCollector::new(&reset);
+        let mut ack = ErrorMessage::default();
+        ack.code = std::num::NonZeroI32::new(42);
+        let reply = NetlinkPayload::Error(ack);
+        assert_eq!(collector.push(message(3, reply)), Ok(true));
+        let lost = collector.ack().and_then(StatusMessage::lost_from_ack);
+        assert_eq!(lost, Some(42));
+
+        let mut collector = ReplyCollector::new(&request);
+        let mut error = ErrorMessage::default();
+        error.code = std::num::NonZeroI32::new(-1);
+        let err = collector
+            .push(message(2, NetlinkPayload::Error(error)))
+            .unwrap_err();
+        assert_eq!(err.errno, 1);
+        assert!(collector.is_complete());
+    }
+}
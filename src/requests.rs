@@ -0,0 +1,246 @@
+// SPDX-License-Identifier: MIT
+
+//! Constructors for the requests understood by the kernel.
+//!
+//! The returned messages are finalized, and carry the flags the kernel
+//! expects:
+//! - queries (`get_*`, `signal_info`, `tty_get`) have `NLM_F_REQUEST`, the
+//!   kernel answers with a single reply.
+//! - commands have `NLM_F_REQUEST | NLM_F_ACK`, so that the kernel
+//!   acknowledges them, or reports why they failed.
+//! - [`list_rules`] has `NLM_F_REQUEST | NLM_F_DUMP`, the kernel answers
+//!   with one message per rule, followed by `NLMSG_DONE`.
+//!
+//! The sequence number is left to 0, for the caller to set, for instance
+//! with [`with_sequence`]. The `*_header` functions return the matching
+//! headers, for requests built by hand. Several requests can be sent at
+//! once, see [`batch`].
+
+use netlink_packet_core::{
+    NetlinkHeader, NetlinkMessage, NetlinkPayload, NLM_F_ACK, NLM_F_DUMP,
+    NLM_F_REQUEST,
+};
+
+use crate::{
+    rules::RuleMessage, AuditMessage, FeaturesMessage, MakeEquivMessage,
+    StatusMessage, TtyStatusMessage,
+};
+
+fn header(flags: u16, sequence_number: u32, port_number: u32) -> NetlinkHeader {
+    let mut header = NetlinkHeader::default();
+    header.flags = flags;
+    header.sequence_number = sequence_number;
+    header.port_number = port_number;
+    header
+}
+
+/// Header of a query, that the kernel answers with a single reply
+pub fn query_header(sequence_number: u32, port_number: u32) -> NetlinkHeader {
+    header(NLM_F_REQUEST, sequence_number, port_number)
+}
+
+/// Header of a command, that the kernel acknowledges or rejects
+pub fn command_header(sequence_number: u32, port_number: u32) -> NetlinkHeader {
+    header(NLM_F_REQUEST | NLM_F_ACK, sequence_number, port_number)
+}
+
+/// Header of a multipart request, that the kernel answers with several
+/// messages followed by `NLMSG_DONE`
+pub fn dump_header(sequence_number: u32, port_number: u32) -> NetlinkHeader {
+    header(NLM_F_REQUEST | NLM_F_DUMP, sequence_number, port_number)
+}
+
+/// Set the sequence number and port id of a request
+///
+/// ```
+/// use netlink_packet_audit::requests;
+///
+/// let request = requests::with_sequence(requests::get_status(), 1, 0);
+/// assert_eq!(request.header.sequence_number, 1);
+/// ```
+pub fn with_sequence(
+    mut message: NetlinkMessage<AuditMessage>,
+    sequence_number: u32,
+    port_number: u32,
+) -> NetlinkMessage<AuditMessage> {
+    message.header.sequence_number = sequence_number;
+    message.header.port_number = port_number;
+    message
+}
+
+/// Serialize requests back to back into a single buffer, to send them with
+/// one write. The requests are numbered from `first_sequence_number`, and
+/// each of them is padded to a multiple of 4 bytes, as netlink expects.
+///
+/// The kernel processes the requests in order, and answers each of them as
+/// if it had been sent on its own.
+///
+/// ```
+/// use netlink_packet_audit::{prelude::*, requests};
+///
+/// let rules = vec![RuleMessage::new(), RuleMessage::new()];
+/// let buf = requests::batch(rules.into_iter().map(requests::add_rule), 1);
+/// // send `buf` on the socket, and wait for the acknowledgements of
+/// // requests 1 and 2
+/// # assert_eq!(buf.len() % 4, 0);
+/// ```
+pub fn batch<I>(requests: I, first_sequence_number: u32) -> Vec<u8>
+where
+    I: IntoIterator<Item = NetlinkMessage<AuditMessage>>,
+{
+    let mut buf = Vec::new();
+    for (i, mut request) in requests.into_iter().enumerate() {
+        request.header.sequence_number =
+            first_sequence_number.wrapping_add(i as u32);
+        request.finalize();
+        let start = buf.len();
+        let len = request.buffer_len();
+        buf.resize(start + len.next_multiple_of(4), 0);
+        request.serialize(&mut buf[start..start + len]);
+    }
+    buf
+}
+
+fn request(
+    message: AuditMessage,
+    header: NetlinkHeader,
+) -> NetlinkMessage<AuditMessage> {
+    let mut msg = NetlinkMessage::new(header, NetlinkPayload::from(message));
+    msg.finalize();
+    msg
+}
+
+fn query(message: AuditMessage) -> NetlinkMessage<AuditMessage> {
+    request(message, query_header(0, 0))
+}
+
+fn command(message: AuditMessage) -> NetlinkMessage<AuditMessage> {
+    request(message, command_header(0, 0))
+}
+
+/// `AUDIT_GET` request
+pub fn get_status() -> NetlinkMessage<AuditMessage> {
+    query(AuditMessage::GetStatus(None))
+}
+
+/// `AUDIT_SET` request. Only the fields selected by `status.mask` are
+/// changed.
+pub fn set_status(status: StatusMessage) -> NetlinkMessage<AuditMessage> {
+    command(AuditMessage::SetStatus(status))
+}
+
+/// `AUDIT_ADD_RULE` request
+pub fn add_rule(rule: RuleMessage) -> NetlinkMessage<AuditMessage> {
+    command(AuditMessage::AddRule(rule))
+}
+
+/// `AUDIT_DEL_RULE` request
+pub fn del_rule(rule: RuleMessage) -> NetlinkMessage<AuditMessage> {
+    command(AuditMessage::DelRule(rule))
+}
+
+/// `AUDIT_LIST_RULES` request
+pub fn list_rules() -> NetlinkMessage<AuditMessage> {
+    request(AuditMessage::ListRules(None), dump_header(0, 0))
+}
+
+/// `AUDIT_GET_FEATURE` request
+pub fn get_features() -> NetlinkMessage<AuditMessage> {
+    query(AuditMessage::GetFeature(None))
+}
+
+/// `AUDIT_SET_FEATURE` request. Only the features selected by
+/// `features.mask` are changed.
+pub fn set_features(features: FeaturesMessage) -> NetlinkMessage<AuditMessage> {
+    command(AuditMessage::SetFeature(features))
+}
+
+/// `AUDIT_SIGNAL_INFO` request
+pub fn signal_info() -> NetlinkMessage<AuditMessage> {
+    query(AuditMessage::SignalInfo(None))
+}
+
+/// `AUDIT_TTY_GET` request
+pub fn tty_get() -> NetlinkMessage<AuditMessage> {
+    query(AuditMessage::TtyGet(None))
+}
+
+/// `AUDIT_TTY_SET` request
+pub fn tty_set(status: TtyStatusMessage) -> NetlinkMessage<AuditMessage> {
+    command(AuditMessage::TtySet(status))
+}
+
+/// `AUDIT_TRIM` request
+pub fn trim() -> NetlinkMessage<AuditMessage> {
+    command(AuditMessage::Trim)
+}
+
+/// `AUDIT_MAKE_EQUIV` request
+pub fn make_equiv(equiv: MakeEquivMessage) -> NetlinkMessage<AuditMessage> {
+    command(AuditMessage::MakeEquiv(equiv))
+}
+
+#[cfg(test)]
+mod test {
+    use netlink_packet_core::NetlinkBuffer;
+
+    use super::*;
+    use crate::{
+        constants::*,
+        rules::{RuleField, RuleFieldFlags},
+    };
+
+    #[test]
+    fn test_requests() {
+        let msg = get_status();
+        assert_eq!(msg.header.message_type, AUDIT_GET);
+        assert_eq!(msg.header.flags, NLM_F_REQUEST);
+        assert_eq!(msg.header.length, 16);
+
+        let msg = add_rule(RuleMessage::new());
+        assert_eq!(msg.header.message_type, AUDIT_ADD_RULE);
+        assert_eq!(msg.header.flags, NLM_F_REQUEST | NLM_F_ACK);
+        assert_eq!(msg.header.length as usize, msg.buffer_len());
+
+        let msg = list_rules();
+        assert_eq!(msg.header.message_type, AUDIT_LIST_RULES);
+        assert_eq!(msg.header.flags, NLM_F_REQUEST | NLM_F_DUMP);
+
+        let header = command_header(3, 42);
+        assert_eq!(header.flags, NLM_F_REQUEST | NLM_F_ACK);
+        assert_eq!(header.sequence_number, 3);
+        assert_eq!(header.port_number, 42);
+    }
+
+    #[test]
+    fn test_batch() {
+        let mut rule = RuleMessage::new();
+        rule.fields
+            .push((RuleField::Watch("/etc/x".into()), RuleFieldFlags::Equal));
+        let buf =
+            batch(vec![add_rule(rule.clone()), del_rule(rule.clone())], 7);
+        assert_eq!(buf.len() % 4, 0);
+
+        let mut offset = 0;
+        let mut messages = vec![];
+        while offset < buf.len() {
+            let len = NetlinkBuffer::new(&buf[offset..]).length() as usize;
+            messages.push(
+                NetlinkMessage::<AuditMessage>::deserialize(
+                    &buf[offset..offset + len],
+                )
+                .unwrap(),
+            );
+            offset += len.next_multiple_of(4);
+        }
+        assert_eq!(offset, buf.len());
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].header.sequence_number, 7);
+        assert_eq!(
+            messages[0].payload,
+            AuditMessage::AddRule(rule.clone()).into()
+        );
+        assert_eq!(messages[1].header.sequence_number, 8);
+        assert_eq!(messages[1].payload, AuditMessage::DelRule(rule).into());
+    }
+}
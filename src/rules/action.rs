@@ -1,8 +1,10 @@
 // SPDX-License-Identifier: MIT
 
+use std::cmp::Ordering;
+
 use crate::constants::*;
 
-#[derive(Copy, Debug, PartialEq, Eq, Clone)]
+#[derive(Copy, Debug, PartialEq, Eq, Clone, Hash)]
 #[non_exhaustive]
 pub enum RuleAction {
     Never,
@@ -23,6 +25,16 @@ impl From<u32> for RuleAction {
     }
 }
 
+impl RuleAction {
+    pub fn from_raw(value: u32) -> Self {
+        value.into()
+    }
+
+    pub fn into_raw(self) -> u32 {
+        self.into()
+    }
+}
+
 impl From<RuleAction> for u32 {
     fn from(value: RuleAction) -> Self {
         use self::RuleAction::*;
@@ -34,3 +46,19 @@ impl From<RuleAction> for u32 {
         }
     }
 }
+
+impl PartialOrd for RuleAction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Actions are ordered by their kernel value
+impl Ord for RuleAction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        u32::from(*self).cmp(&u32::from(*other)).then_with(|| {
+            matches!(self, RuleAction::Unknown(_))
+                .cmp(&matches!(other, RuleAction::Unknown(_)))
+        })
+    }
+}
@@ -1,10 +1,8 @@
 // SPDX-License-Identifier: MIT
 
-use anyhow::Context;
-use byteorder::{ByteOrder, NativeEndian};
 use netlink_packet_utils::{traits::Parseable, DecodeError};
 
-use crate::{constants::*, rules::*, Field};
+use crate::{constants::*, rules::*, AuditDecodeError, Endianness, Field};
 
 // FIXME: when const fn are stable, use them, instead of defining a macro
 // const fn u32_array(start: usize, len: usize) -> Field {
@@ -32,15 +30,52 @@ fn BUF(len: usize) -> Field {
     BUFLEN.end..(BUFLEN.end + len)
 }
 
+/// Return `true` for the fields whose value is the length of a string
+/// stored in the string buffer
+fn is_string_field(field: u32) -> bool {
+    matches!(
+        field,
+        AUDIT_WATCH
+            | AUDIT_DIR
+            | AUDIT_FILTERKEY
+            | AUDIT_SUBJ_USER
+            | AUDIT_SUBJ_ROLE
+            | AUDIT_SUBJ_TYPE
+            | AUDIT_SUBJ_SEN
+            | AUDIT_SUBJ_CLR
+            | AUDIT_OBJ_USER
+            | AUDIT_OBJ_ROLE
+            | AUDIT_OBJ_TYPE
+            | AUDIT_OBJ_LEV_LOW
+            | AUDIT_OBJ_LEV_HIGH
+    )
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[non_exhaustive]
 pub struct RuleBuffer<T> {
     buffer: T,
+    order: Endianness,
 }
 
 impl<T: AsRef<[u8]>> RuleBuffer<T> {
+    /// Wrap a buffer without checking its length: the accessors panic if it
+    /// is too short. Use [`RuleBuffer::new_checked`] or the `try_`
+    /// accessors for untrusted input.
     pub fn new(buffer: T) -> RuleBuffer<T> {
-        RuleBuffer { buffer }
+        Self::with_byte_order(buffer, Endianness::native())
+    }
+
+    /// Wrap a buffer whose integers are in the given byte order, such as a
+    /// rule captured on a machine of a different byte order. The accessors
+    /// and setters convert the header fields from and to this order, and
+    /// the parser converts the fields, values and syscall bit mask.
+    pub fn with_byte_order(buffer: T, order: Endianness) -> RuleBuffer<T> {
+        RuleBuffer { buffer, order }
+    }
+
+    pub fn byte_order(&self) -> Endianness {
+        self.order
     }
 
     pub fn new_checked(buffer: T) -> Result<Self, DecodeError> {
@@ -49,42 +84,93 @@ impl<T: AsRef<[u8]>> RuleBuffer<T> {
         Ok(packet)
     }
 
-    pub(crate) fn check_len(&self) -> Result<(), DecodeError> {
+    /// Check that the buffer holds the rule header, at most
+    /// `AUDIT_MAX_FIELDS` fields, and the string buffer with the string
+    /// values of the fields
+    pub fn check_len(&self) -> Result<(), AuditDecodeError> {
         let len = self.buffer.as_ref().len();
         if len < BUFLEN.end {
-            Err(format!(
-                "buffer size is {}, whereas a rule buffer is at least {} long",
-                len, BUFLEN.end
-            )
-            .into())
-        } else if len < BUFLEN.end + self.buflen() as usize {
-            Err(format!(
-                "buffer length is {}, but it should be {} (header) + {} \
-                (length field)",
-                len,
-                BUFLEN.end,
-                self.buflen()
-            )
-            .into())
-        } else {
-            Ok(())
+            return Err(AuditDecodeError::InvalidRuleBuffer {
+                needed: BUFLEN.end,
+                got: len,
+            });
+        }
+        let buflen = self.try_buflen()? as usize;
+        if len < BUFLEN.end.saturating_add(buflen) {
+            return Err(AuditDecodeError::InvalidRuleBuffer {
+                needed: BUFLEN.end.saturating_add(buflen),
+                got: len,
+            });
+        }
+        let count = self.try_field_count()? as usize;
+        if count > AUDIT_MAX_FIELDS {
+            return Err(AuditDecodeError::TooManyRuleFields {
+                count,
+                max: AUDIT_MAX_FIELDS,
+            });
+        }
+        let buffer = self.buffer.as_ref();
+        let mut strings_len: usize = 0;
+        for i in 0..count {
+            let field = self.order.read_u32(&buffer[FIELDS][i * 4..]);
+            if is_string_field(field) {
+                let value = self.order.read_u32(&buffer[VALUES][i * 4..]);
+                strings_len = strings_len.saturating_add(value as usize);
+                if strings_len > buflen {
+                    return Err(AuditDecodeError::InvalidRuleField { field });
+                }
+            }
         }
+        Ok(())
     }
 
     pub fn flags(&self) -> u32 {
-        NativeEndian::read_u32(&self.buffer.as_ref()[FLAGS])
+        self.order.read_u32(&self.buffer.as_ref()[FLAGS])
     }
 
     pub fn action(&self) -> u32 {
-        NativeEndian::read_u32(&self.buffer.as_ref()[ACTION])
+        self.order.read_u32(&self.buffer.as_ref()[ACTION])
     }
 
     pub fn field_count(&self) -> u32 {
-        NativeEndian::read_u32(&self.buffer.as_ref()[FIELD_COUNT])
+        self.order.read_u32(&self.buffer.as_ref()[FIELD_COUNT])
     }
 
     pub fn buflen(&self) -> u32 {
-        NativeEndian::read_u32(&self.buffer.as_ref()[BUFLEN])
+        self.order.read_u32(&self.buffer.as_ref()[BUFLEN])
+    }
+
+    /// Checked version of `flags()`
+    pub fn try_flags(&self) -> Result<u32, AuditDecodeError> {
+        self.try_get(FLAGS).map(|bytes| self.order.read_u32(bytes))
+    }
+
+    /// Checked version of `action()`
+    pub fn try_action(&self) -> Result<u32, AuditDecodeError> {
+        self.try_get(ACTION).map(|bytes| self.order.read_u32(bytes))
+    }
+
+    /// Checked version of `field_count()`
+    pub fn try_field_count(&self) -> Result<u32, AuditDecodeError> {
+        self.try_get(FIELD_COUNT)
+            .map(|bytes| self.order.read_u32(bytes))
+    }
+
+    /// Checked version of `buflen()`
+    pub fn try_buflen(&self) -> Result<u32, AuditDecodeError> {
+        self.try_get(BUFLEN).map(|bytes| self.order.read_u32(bytes))
+    }
+
+    /// Return the bytes of a field, or fail if the buffer is too short to
+    /// contain it
+    fn try_get(&self, field: Field) -> Result<&[u8], AuditDecodeError> {
+        let buffer = self.buffer.as_ref();
+        buffer
+            .get(field.clone())
+            .ok_or(AuditDecodeError::InvalidRuleBuffer {
+                needed: field.end,
+                got: buffer.len(),
+            })
     }
 }
 
@@ -109,23 +195,63 @@ impl<'a, T: AsRef<[u8]> + ?Sized> RuleBuffer<&'a T> {
         let field = BUF(self.buflen() as usize);
         &self.buffer.as_ref()[field.start..field.end]
     }
+
+    /// Checked version of `syscalls()`
+    pub fn try_syscalls(&self) -> Result<&'a [u8], AuditDecodeError> {
+        self.try_slice(SYSCALLS)
+    }
+
+    /// Checked version of `fields()`
+    pub fn try_fields(&self) -> Result<&'a [u8], AuditDecodeError> {
+        self.try_slice(FIELDS)
+    }
+
+    /// Checked version of `values()`
+    pub fn try_values(&self) -> Result<&'a [u8], AuditDecodeError> {
+        self.try_slice(VALUES)
+    }
+
+    /// Checked version of `field_flags()`
+    pub fn try_field_flags(&self) -> Result<&'a [u8], AuditDecodeError> {
+        self.try_slice(FIELD_FLAGS)
+    }
+
+    /// Checked version of `buf()`
+    pub fn try_buf(&self) -> Result<&'a [u8], AuditDecodeError> {
+        let buflen = self.try_buflen()? as usize;
+        self.try_slice(BUFLEN.end..BUFLEN.end.saturating_add(buflen))
+    }
+
+    fn try_slice(&self, field: Field) -> Result<&'a [u8], AuditDecodeError> {
+        let buffer: &'a [u8] = self.buffer.as_ref();
+        buffer
+            .get(field.clone())
+            .ok_or(AuditDecodeError::InvalidRuleBuffer {
+                needed: field.end,
+                got: buffer.len(),
+            })
+    }
 }
 
 impl<T: AsRef<[u8]> + AsMut<[u8]>> RuleBuffer<T> {
     pub fn set_flags(&mut self, value: u32) {
-        NativeEndian::write_u32(&mut self.buffer.as_mut()[FLAGS], value)
+        self.order
+            .write_u32(&mut self.buffer.as_mut()[FLAGS], value)
     }
 
     pub fn set_action(&mut self, value: u32) {
-        NativeEndian::write_u32(&mut self.buffer.as_mut()[ACTION], value)
+        self.order
+            .write_u32(&mut self.buffer.as_mut()[ACTION], value)
     }
 
     pub fn set_field_count(&mut self, value: u32) {
-        NativeEndian::write_u32(&mut self.buffer.as_mut()[FIELD_COUNT], value)
+        self.order
+            .write_u32(&mut self.buffer.as_mut()[FIELD_COUNT], value)
     }
 
     pub fn set_buflen(&mut self, value: u32) {
-        NativeEndian::write_u32(&mut self.buffer.as_mut()[BUFLEN], value)
+        self.order
+            .write_u32(&mut self.buffer.as_mut()[BUFLEN], value)
     }
 
     pub fn syscalls_mut(&mut self) -> &mut [u8] {
@@ -139,10 +265,8 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> RuleBuffer<T> {
     pub fn set_field(&mut self, position: usize, value: u32) {
         let offset = FIELDS.start + (position * 4);
         assert!(position <= FIELDS.end - 4);
-        NativeEndian::write_u32(
-            &mut self.buffer.as_mut()[offset..offset + 4],
-            value,
-        )
+        self.order
+            .write_u32(&mut self.buffer.as_mut()[offset..offset + 4], value)
     }
 
     pub fn values_mut(&mut self) -> &mut [u8] {
@@ -152,10 +276,8 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> RuleBuffer<T> {
     pub fn set_value(&mut self, position: usize, value: u32) {
         let offset = VALUES.start + (position * 4);
         assert!(position <= VALUES.end - 4);
-        NativeEndian::write_u32(
-            &mut self.buffer.as_mut()[offset..offset + 4],
-            value,
-        )
+        self.order
+            .write_u32(&mut self.buffer.as_mut()[offset..offset + 4], value)
     }
 
     pub fn field_flags_mut(&mut self) -> &mut [u8] {
@@ -165,10 +287,8 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> RuleBuffer<T> {
     pub fn set_field_flags(&mut self, position: usize, value: u32) {
         let offset = FIELD_FLAGS.start + (position * 4);
         assert!(position <= FIELD_FLAGS.end - 4);
-        NativeEndian::write_u32(
-            &mut self.buffer.as_mut()[offset..offset + 4],
-            value,
-        )
+        self.order
+            .write_u32(&mut self.buffer.as_mut()[offset..offset + 4], value)
     }
 
     pub fn buf_mut(&mut self) -> &mut [u8] {
@@ -179,26 +299,37 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> RuleBuffer<T> {
 
 impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<RuleBuffer<&'a T>> for RuleMessage {
     fn parse(buf: &RuleBuffer<&'a T>) -> Result<Self, DecodeError> {
+        Ok(Self::try_parse(buf)?)
+    }
+}
+
+impl RuleMessage {
+    pub(crate) fn try_parse<T: AsRef<[u8]> + ?Sized>(
+        buf: &RuleBuffer<&T>,
+    ) -> Result<Self, AuditDecodeError> {
         use self::RuleField::*;
 
-        buf.check_len().context("invalid rule message buffer")?;
+        buf.check_len()?;
         let mut rule = RuleMessage::new();
-        rule.flags = buf.flags().into();
-        rule.action = buf.action().into();
-        rule.syscalls = RuleSyscalls::from_slice(buf.syscalls())?;
+        rule.flags = buf.try_flags()?.into();
+        rule.action = buf.try_action()?.into();
+        rule.syscalls =
+            RuleSyscalls::from_slice_with(buf.try_syscalls()?, buf.order)?;
 
-        let mut offset = 0;
+        let mut offset: usize = 0;
+        let strings = buf.try_buf()?;
 
-        let fields = buf.fields().chunks(4).map(NativeEndian::read_u32);
-        let values = buf.values().chunks(4).map(NativeEndian::read_u32);
+        let read_u32 = |chunk: &[u8]| buf.order.read_u32(chunk);
+        let fields = buf.try_fields()?.chunks(4).map(read_u32);
+        let values = buf.try_values()?.chunks(4).map(read_u32);
         let field_flags = buf
-            .field_flags()
+            .try_field_flags()?
             .chunks(4)
-            .map(|chunk| RuleFieldFlags::from(NativeEndian::read_u32(chunk)));
+            .map(|chunk| RuleFieldFlags::from(read_u32(chunk)));
         for (field, value, flags) in fields
             .zip(values.zip(field_flags))
             .map(|(field, (value, flags))| (field, value, flags))
-            .take(buf.field_count() as usize)
+            .take(buf.try_field_count()? as usize)
         {
             let field = match field {
                 AUDIT_PID => Pid(value),
@@ -235,17 +366,15 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<RuleBuffer<&'a T>> for RuleMessage {
                 AUDIT_ARG3 => Arg3(value),
                 _ => {
                     // For all the other fields, the value is a string
-                    let str_end = offset + value as usize;
-                    if str_end > buf.buf().len() {
-                        return Err(format!(
-                            "failed to decode field. type={field} \
-                            (value should be a string?)"
-                        )
-                        .into());
-                    }
-                    let s: String =
-                        String::from_utf8_lossy(&buf.buf()[offset..str_end])
-                            .into();
+                    let str_end = offset.saturating_add(value as usize);
+                    let s: String = match strings.get(offset..str_end) {
+                        Some(s) => String::from_utf8_lossy(s).into(),
+                        None => {
+                            return Err(AuditDecodeError::InvalidRuleField {
+                                field,
+                            })
+                        }
+                    };
                     offset = str_end;
                     match field {
                         AUDIT_WATCH => Watch(s),
@@ -262,11 +391,9 @@ impl<'a, T: AsRef<[u8]> + ?Sized> Parseable<RuleBuffer<&'a T>> for RuleMessage {
                         AUDIT_OBJ_LEV_LOW => ObjLevLow(s),
                         AUDIT_OBJ_LEV_HIGH => ObjLevHigh(s),
                         _ => {
-                            return Err(format!(
-                                "failed to decode field (unknown type) \
-                                type={field}, value={s}"
-                            )
-                            .into());
+                            return Err(AuditDecodeError::UnknownRuleField {
+                                field,
+                            });
                         }
                     }
                 }
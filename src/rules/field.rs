@@ -1,8 +1,10 @@
 // SPDX-License-Identifier: MIT
 
+use std::cmp::Ordering;
+
 use crate::constants::*;
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
 #[non_exhaustive]
 pub enum RuleField {
     Pid(u32),
@@ -55,7 +57,7 @@ pub enum RuleField {
     ObjLevHigh(String),
 }
 
-#[derive(Copy, Debug, PartialEq, Eq, Clone)]
+#[derive(Copy, Debug, PartialEq, Eq, Clone, Hash)]
 #[non_exhaustive]
 pub enum RuleFieldFlags {
     BitMask,
@@ -88,6 +90,16 @@ impl From<u32> for RuleFieldFlags {
     }
 }
 
+impl RuleFieldFlags {
+    pub fn from_raw(value: u32) -> Self {
+        value.into()
+    }
+
+    pub fn into_raw(self) -> u32 {
+        self.into()
+    }
+}
+
 impl From<RuleFieldFlags> for u32 {
     fn from(value: RuleFieldFlags) -> Self {
         use self::RuleFieldFlags::*;
@@ -105,3 +117,92 @@ impl From<RuleFieldFlags> for u32 {
         }
     }
 }
+
+impl RuleField {
+    /// Return the type of the field
+    pub fn field_type(&self) -> RuleFieldType {
+        RuleFieldType::from_raw(self.key().0)
+    }
+
+    /// Return the kernel field type (`AUDIT_*`) and the value of the field:
+    /// either a number, or a string.
+    pub(crate) fn key(&self) -> (u32, Result<u32, &str>) {
+        use self::RuleField::*;
+        match self {
+            Pid(value) => (AUDIT_PID, Ok(*value)),
+            Uid(value) => (AUDIT_UID, Ok(*value)),
+            Euid(value) => (AUDIT_EUID, Ok(*value)),
+            Suid(value) => (AUDIT_SUID, Ok(*value)),
+            Fsuid(value) => (AUDIT_FSUID, Ok(*value)),
+            Gid(value) => (AUDIT_GID, Ok(*value)),
+            Egid(value) => (AUDIT_EGID, Ok(*value)),
+            Sgid(value) => (AUDIT_SGID, Ok(*value)),
+            Fsgid(value) => (AUDIT_FSGID, Ok(*value)),
+            Loginuid(value) => (AUDIT_LOGINUID, Ok(*value)),
+            Pers(value) => (AUDIT_PERS, Ok(*value)),
+            Arch(value) => (AUDIT_ARCH, Ok(*value)),
+            Msgtype(value) => (AUDIT_MSGTYPE, Ok(*value)),
+            Ppid(value) => (AUDIT_PPID, Ok(*value)),
+            LoginuidSet(value) => (AUDIT_LOGINUID_SET, Ok(*value)),
+            Sessionid(value) => (AUDIT_SESSIONID, Ok(*value)),
+            Fstype(value) => (AUDIT_FSTYPE, Ok(*value)),
+            Devmajor(value) => (AUDIT_DEVMAJOR, Ok(*value)),
+            Devminor(value) => (AUDIT_DEVMINOR, Ok(*value)),
+            Inode(value) => (AUDIT_INODE, Ok(*value)),
+            Exit(value) => (AUDIT_EXIT, Ok(*value)),
+            Success(value) => (AUDIT_SUCCESS, Ok(*value)),
+            Perm(value) => (AUDIT_PERM, Ok(*value)),
+            Filetype(value) => (AUDIT_FILETYPE, Ok(*value)),
+            ObjUid(value) => (AUDIT_OBJ_UID, Ok(*value)),
+            ObjGid(value) => (AUDIT_OBJ_GID, Ok(*value)),
+            FieldCompare(value) => (AUDIT_FIELD_COMPARE, Ok(*value)),
+            Exe(value) => (AUDIT_EXE, Ok(*value)),
+            Arg0(value) => (AUDIT_ARG0, Ok(*value)),
+            Arg1(value) => (AUDIT_ARG1, Ok(*value)),
+            Arg2(value) => (AUDIT_ARG2, Ok(*value)),
+            Arg3(value) => (AUDIT_ARG3, Ok(*value)),
+            Watch(ref value) => (AUDIT_WATCH, Err(value.as_str())),
+            Dir(ref value) => (AUDIT_DIR, Err(value.as_str())),
+            Filterkey(ref value) => (AUDIT_FILTERKEY, Err(value.as_str())),
+            SubjUser(ref value) => (AUDIT_SUBJ_USER, Err(value.as_str())),
+            SubjRole(ref value) => (AUDIT_SUBJ_ROLE, Err(value.as_str())),
+            SubjType(ref value) => (AUDIT_SUBJ_TYPE, Err(value.as_str())),
+            SubjSen(ref value) => (AUDIT_SUBJ_SEN, Err(value.as_str())),
+            SubjClr(ref value) => (AUDIT_SUBJ_CLR, Err(value.as_str())),
+            ObjUser(ref value) => (AUDIT_OBJ_USER, Err(value.as_str())),
+            ObjRole(ref value) => (AUDIT_OBJ_ROLE, Err(value.as_str())),
+            ObjType(ref value) => (AUDIT_OBJ_TYPE, Err(value.as_str())),
+            ObjLevLow(ref value) => (AUDIT_OBJ_LEV_LOW, Err(value.as_str())),
+            ObjLevHigh(ref value) => (AUDIT_OBJ_LEV_HIGH, Err(value.as_str())),
+        }
+    }
+}
+
+impl PartialOrd for RuleField {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Fields are ordered by their kernel type, then by value
+impl Ord for RuleField {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl PartialOrd for RuleFieldFlags {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Operators are ordered by their kernel value
+impl Ord for RuleFieldFlags {
+    fn cmp(&self, other: &Self) -> Ordering {
+        u32::from(*self).cmp(&u32::from(*other)).then_with(|| {
+            matches!(self, RuleFieldFlags::Unknown(_))
+                .cmp(&matches!(other, RuleFieldFlags::Unknown(_)))
+        })
+    }
+}
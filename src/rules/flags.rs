@@ -1,8 +1,10 @@
 // SPDX-License-Identifier: MIT
 
+use std::cmp::Ordering;
+
 use crate::constants::*;
 
-#[derive(Copy, Debug, PartialEq, Eq, Clone)]
+#[derive(Copy, Debug, PartialEq, Eq, Clone, Hash)]
 #[non_exhaustive]
 pub enum RuleFlags {
     FilterUser,
@@ -12,6 +14,7 @@ pub enum RuleFlags {
     FilterExit,
     FilterType,
     FilterFs,
+    FilterUringExit,
     NrFilters,
     FilterPrepend,
     Unset,
@@ -29,6 +32,7 @@ impl From<u32> for RuleFlags {
             AUDIT_FILTER_EXIT => FilterExit,
             AUDIT_FILTER_TYPE => FilterType,
             AUDIT_FILTER_FS => FilterFs,
+            AUDIT_FILTER_URING_EXIT => FilterUringExit,
             AUDIT_NR_FILTERS => NrFilters,
             AUDIT_FILTER_PREPEND => FilterPrepend,
             AUDIT_FILTER_UNSET => Unset,
@@ -37,6 +41,16 @@ impl From<u32> for RuleFlags {
     }
 }
 
+impl RuleFlags {
+    pub fn from_raw(value: u32) -> Self {
+        value.into()
+    }
+
+    pub fn into_raw(self) -> u32 {
+        self.into()
+    }
+}
+
 impl From<RuleFlags> for u32 {
     fn from(value: RuleFlags) -> Self {
         use self::RuleFlags::*;
@@ -48,6 +62,7 @@ impl From<RuleFlags> for u32 {
             FilterExit => AUDIT_FILTER_EXIT,
             FilterType => AUDIT_FILTER_TYPE,
             FilterFs => AUDIT_FILTER_FS,
+            FilterUringExit => AUDIT_FILTER_URING_EXIT,
             NrFilters => AUDIT_NR_FILTERS,
             FilterPrepend => AUDIT_FILTER_PREPEND,
             Unset => AUDIT_FILTER_UNSET,
@@ -55,3 +70,19 @@ impl From<RuleFlags> for u32 {
         }
     }
 }
+
+impl PartialOrd for RuleFlags {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Flags are ordered by their kernel value
+impl Ord for RuleFlags {
+    fn cmp(&self, other: &Self) -> Ordering {
+        u32::from(*self).cmp(&u32::from(*other)).then_with(|| {
+            matches!(self, RuleFlags::Unknown(_))
+                .cmp(&matches!(other, RuleFlags::Unknown(_)))
+        })
+    }
+}
@@ -18,5 +18,8 @@ pub use self::buffer::*;
 mod rule;
 pub use self::rule::*;
 
+mod plan;
+pub use self::plan::*;
+
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
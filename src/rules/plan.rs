@@ -0,0 +1,53 @@
+// SPDX-License-Identifier: MIT
+
+use std::collections::HashMap;
+
+use crate::rules::RuleMessage;
+
+/// Changes needed to go from the rules loaded in the kernel to a desired
+/// set of rules.
+///
+/// Rules are compared exactly, so the desired rules should be written the
+/// way the kernel lists them back (for instance without
+/// `AUDIT_FILTER_PREPEND`). The order of the rules within a filter list is
+/// not reconciled: rules that are both loaded and desired are kept where
+/// they are.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct RulePlan {
+    /// Loaded rules that are not desired, in the order they were listed
+    pub delete: Vec<RuleMessage>,
+    /// Desired rules that are not loaded, in the order they were given
+    pub add: Vec<RuleMessage>,
+}
+
+impl RulePlan {
+    pub fn new(installed: &[RuleMessage], desired: &[RuleMessage]) -> Self {
+        let mut loaded: HashMap<&RuleMessage, usize> = HashMap::new();
+        for rule in installed {
+            *loaded.entry(rule).or_default() += 1;
+        }
+        let mut add = Vec::new();
+        for rule in desired {
+            match loaded.get_mut(rule) {
+                Some(count) if *count > 0 => *count -= 1,
+                _ => add.push(rule.clone()),
+            }
+        }
+        let mut delete = Vec::new();
+        for rule in installed {
+            if let Some(count) = loaded.get_mut(rule) {
+                if *count > 0 {
+                    *count -= 1;
+                    delete.push(rule.clone());
+                }
+            }
+        }
+        RulePlan { delete, add }
+    }
+
+    /// Return `true` if the loaded rules already match the desired ones
+    pub fn is_empty(&self) -> bool {
+        self.delete.is_empty() && self.add.is_empty()
+    }
+}
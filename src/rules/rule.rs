@@ -9,9 +9,18 @@ use crate::{
         RuleAction, RuleBuffer, RuleField, RuleFieldFlags, RuleFlags,
         RuleSyscalls, RULE_BUF_MIN_LEN,
     },
+    AuditEmitError, RuleFieldType,
 };
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+/// Maximum length of the string values of rule fields, other than the
+/// filter key (`PATH_MAX`)
+const MAX_STRING_LEN: usize = 4096;
+
+/// Rules are ordered by flags, action, fields and syscalls, in that order.
+/// Flags, actions and fields are compared through their kernel values,
+/// so that the ordering does not change with the declaration order of the
+/// enums.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, PartialOrd, Ord)]
 #[non_exhaustive]
 pub struct RuleMessage {
     pub flags: RuleFlags,
@@ -36,6 +45,54 @@ impl RuleMessage {
         }
     }
 
+    /// Check that the rule can be serialized: the kernel accepts at most
+    /// `AUDIT_MAX_FIELDS` fields, and string values of at most `PATH_MAX`
+    /// bytes (`AUDIT_MAX_KEY_LEN` for the filter key). Fields the kernel
+    /// rejects on the rule's filter list are reported as well (see
+    /// `audit_field_valid()` in `kernel/auditfilter.c`).
+    pub fn validate(&self) -> Result<(), AuditEmitError> {
+        if self.fields.len() > AUDIT_MAX_FIELDS {
+            return Err(AuditEmitError::TooManyRuleFields {
+                count: self.fields.len(),
+                max: AUDIT_MAX_FIELDS,
+            });
+        }
+        let filter =
+            RuleFlags::from(u32::from(self.flags) & !AUDIT_FILTER_PREPEND);
+        for (field, _) in self.fields.iter() {
+            let (field, value) = (field.field_type(), field.key().1);
+            if !field_allowed(field, filter) {
+                return Err(AuditEmitError::FieldNotAllowed { field, filter });
+            }
+            if let Err(value) = value {
+                let max = if field == RuleFieldType::Filterkey {
+                    AUDIT_MAX_KEY_LEN
+                } else {
+                    MAX_STRING_LEN
+                };
+                if value.len() > max {
+                    return Err(AuditEmitError::StringTooLong {
+                        field,
+                        len: value.len(),
+                        max,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checked version of `emit()`: validate the rule and the buffer size,
+    /// then serialize the rule at the start of `buffer`. Return the number
+    /// of bytes written.
+    pub fn try_emit(&self, buffer: &mut [u8]) -> Result<usize, AuditEmitError> {
+        self.validate()?;
+        let len = self.buffer_len();
+        AuditEmitError::check_buffer(len, buffer)?;
+        self.emit(&mut buffer[..len]);
+        Ok(len)
+    }
+
     #[rustfmt::skip]
     fn compute_string_values_length(&self) -> usize {
         use self::RuleField::*;
@@ -63,6 +120,19 @@ impl RuleMessage {
     }
 }
 
+fn field_allowed(field: RuleFieldType, filter: RuleFlags) -> bool {
+    use self::RuleFieldType::*;
+    match field {
+        Msgtype => {
+            matches!(filter, RuleFlags::FilterType | RuleFlags::FilterUser)
+        }
+        Fstype => filter == RuleFlags::FilterFs,
+        Perm => filter != RuleFlags::FilterUringExit,
+        Filterkey => true,
+        _ => filter != RuleFlags::FilterFs,
+    }
+}
+
 fn set_str_field<T>(
     rule_buffer: &mut RuleBuffer<T>,
     position: usize,
@@ -93,8 +163,8 @@ impl Emitable for RuleMessage {
         rule_buffer.set_field_count(self.fields.len() as u32);
         {
             let syscalls = rule_buffer.syscalls_mut();
-            for (i, word) in self.syscalls.0.iter().enumerate() {
-                NativeEndian::write_u32(&mut syscalls[i * 4..i * 4 + 4], *word);
+            for (i, bytes) in syscalls.chunks_exact_mut(4).enumerate() {
+                NativeEndian::write_u32(bytes, self.syscalls.word(i));
             }
         }
         rule_buffer.set_buflen(self.compute_string_values_length() as u32);
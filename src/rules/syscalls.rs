@@ -1,14 +1,26 @@
 // SPDX-License-Identifier: MIT
 
-use byteorder::{ByteOrder, NativeEndian};
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
 
-use netlink_packet_utils::DecodeError;
+use crate::{constants::*, AuditDecodeError, Endianness};
 
-use crate::constants::*;
-
-#[derive(Debug, PartialEq, Eq, Clone)]
+/// Syscalls are ordered by their bit mask, compared word by word.
+///
+/// The bit mask is only allocated when it is neither empty nor full, so
+/// that the rules that do not filter on syscalls stay small.
+#[derive(Debug, Clone)]
 #[non_exhaustive]
-pub struct RuleSyscalls(pub(crate) Vec<u32>);
+pub struct RuleSyscalls(pub(crate) SyscallMask);
+
+#[derive(Debug, Clone)]
+pub(crate) enum SyscallMask {
+    Empty,
+    All,
+    Bitmap(Box<[u32; AUDIT_BITMASK_SIZE]>),
+}
 
 const BITMASK_BYTE_LEN: usize = AUDIT_BITMASK_SIZE * 4;
 const BITMASK_BIT_LEN: u32 = AUDIT_BITMASK_SIZE as u32 * 32;
@@ -16,72 +28,116 @@ const BITMASK_BIT_LEN: u32 = AUDIT_BITMASK_SIZE as u32 * 32;
 // FIXME: I'm not 100% sure this implementation is correct wrt to endianness.
 impl RuleSyscalls {
     // FIXME: this should be a TryFrom when it stabilized...
-    pub fn from_slice(slice: &[u8]) -> Result<Self, DecodeError> {
+    pub fn from_slice(slice: &[u8]) -> Result<Self, AuditDecodeError> {
+        Self::from_slice_with(slice, Endianness::native())
+    }
+
+    /// Parse a bit mask whose words are in the given byte order
+    pub(crate) fn from_slice_with(
+        slice: &[u8],
+        order: Endianness,
+    ) -> Result<Self, AuditDecodeError> {
         if slice.len() != BITMASK_BYTE_LEN {
-            return Err(DecodeError::from(format!(
-                "invalid bitmask size: expected {} bytes got {}",
-                BITMASK_BYTE_LEN,
-                slice.len()
-            )));
-        }
-        let mut mask = RuleSyscalls::new_zeroed();
-        let mut word = 0;
-        while word < AUDIT_BITMASK_SIZE {
-            mask.0[word] =
-                NativeEndian::read_u32(&slice[word * 4..word * 4 + 4]);
-            word += 1;
+            return Err(AuditDecodeError::InvalidSyscallMask {
+                needed: BITMASK_BYTE_LEN,
+                got: slice.len(),
+            });
+        }
+        let mut bitmap = [0; AUDIT_BITMASK_SIZE];
+        for (word, bytes) in bitmap.iter_mut().zip(slice.chunks_exact(4)) {
+            *word = order.read_u32(bytes);
         }
+        let mut mask = RuleSyscalls(SyscallMask::Bitmap(Box::new(bitmap)));
+        mask.compact();
         Ok(mask)
     }
 
     pub fn new_zeroed() -> Self {
-        RuleSyscalls(vec![0; AUDIT_BITMASK_SIZE])
+        RuleSyscalls(SyscallMask::Empty)
     }
 
     pub fn new_maxed() -> Self {
-        RuleSyscalls(vec![0xffff_ffff; AUDIT_BITMASK_SIZE])
+        RuleSyscalls(SyscallMask::All)
+    }
+
+    /// Return the `i`-th word of the bit mask
+    pub(crate) fn word(&self, i: usize) -> u32 {
+        match self.0 {
+            SyscallMask::Empty => 0,
+            SyscallMask::All => 0xffff_ffff,
+            SyscallMask::Bitmap(ref bitmap) => bitmap[i],
+        }
+    }
+
+    fn words(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..AUDIT_BITMASK_SIZE).map(move |i| self.word(i))
+    }
+
+    fn bitmap_mut(&mut self) -> &mut [u32; AUDIT_BITMASK_SIZE] {
+        let fill = match self.0 {
+            SyscallMask::Bitmap(ref mut bitmap) => return bitmap,
+            SyscallMask::Empty => 0,
+            SyscallMask::All => 0xffff_ffff,
+        };
+        self.0 = SyscallMask::Bitmap(Box::new([fill; AUDIT_BITMASK_SIZE]));
+        match self.0 {
+            SyscallMask::Bitmap(ref mut bitmap) => bitmap,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Release the bit mask if it is empty or full
+    fn compact(&mut self) {
+        if let SyscallMask::Bitmap(ref bitmap) = self.0 {
+            if bitmap.iter().all(|word| *word == 0) {
+                self.0 = SyscallMask::Empty;
+            } else if bitmap.iter().all(|word| *word == 0xffff_ffff) {
+                self.0 = SyscallMask::All;
+            }
+        }
     }
 
     /// Unset all the bits
     pub fn unset_all(&mut self) -> &mut Self {
-        self.0 = vec![0; AUDIT_BITMASK_SIZE];
+        self.0 = SyscallMask::Empty;
         self
     }
 
     /// Return `true` if all the syscalls are set, `false` otherwise
     pub fn is_all(&self) -> bool {
-        for i in 0..AUDIT_BITMASK_SIZE {
-            if self.0[i] != 0xffff_ffff {
-                return false;
-            }
-        }
-        true
+        self.words().all(|word| word == 0xffff_ffff)
     }
 
     /// Set all the bits
     pub fn set_all(&mut self) -> &mut Self {
-        self.0 = vec![0xffff_ffff; AUDIT_BITMASK_SIZE];
+        self.0 = SyscallMask::All;
         self
     }
 
     /// Unset the bit corresponding to the given syscall
     pub fn unset(&mut self, syscall: u32) -> &mut Self {
         let (word, mask) = Self::syscall_coordinates(syscall);
-        self.0[word] &= !mask;
+        if self.word(word) & mask != 0 {
+            self.bitmap_mut()[word] &= !mask;
+            self.compact();
+        }
         self
     }
 
     /// Set the bit corresponding to the given syscall
     pub fn set(&mut self, syscall: u32) -> &mut Self {
         let (word, mask) = Self::syscall_coordinates(syscall);
-        self.0[word] |= mask;
+        if self.word(word) & mask == 0 {
+            self.bitmap_mut()[word] |= mask;
+            self.compact();
+        }
         self
     }
 
     /// Check if the bit corresponding to the given syscall is set
     pub fn has(&self, syscall: u32) -> bool {
         let (word, mask) = Self::syscall_coordinates(syscall);
-        self.0[word] & mask == mask
+        self.word(word) & mask == mask
     }
 
     fn syscall_coordinates(syscall: u32) -> (usize, u32) {
@@ -91,6 +147,34 @@ impl RuleSyscalls {
     }
 }
 
+impl PartialEq for RuleSyscalls {
+    fn eq(&self, other: &Self) -> bool {
+        self.words().eq(other.words())
+    }
+}
+
+impl Eq for RuleSyscalls {}
+
+impl Hash for RuleSyscalls {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for word in self.words() {
+            word.hash(state);
+        }
+    }
+}
+
+impl PartialOrd for RuleSyscalls {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RuleSyscalls {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.words().cmp(other.words())
+    }
+}
+
 // FIXME: There is a LOT of copy paste for those iterator implementations...
 // This feels wrong but I could not figure out how to avoid it :(
 
@@ -138,7 +222,7 @@ impl<'a> IntoIterator for &'a RuleSyscalls {
     }
 }
 
-impl<'a> Iterator for RuleSyscallsIter<&'a RuleSyscalls> {
+impl Iterator for RuleSyscallsIter<&RuleSyscalls> {
     type Item = u32;
     fn next(&mut self) -> Option<Self::Item> {
         while self.index < BITMASK_BIT_LEN {
@@ -164,7 +248,7 @@ impl<'a> IntoIterator for &'a mut RuleSyscalls {
     }
 }
 
-impl<'a> Iterator for RuleSyscallsIter<&'a mut RuleSyscalls> {
+impl Iterator for RuleSyscallsIter<&mut RuleSyscalls> {
     type Item = u32;
     fn next(&mut self) -> Option<Self::Item> {
         while self.index < BITMASK_BIT_LEN {
@@ -185,11 +269,16 @@ mod test {
     fn test_from_slice() {
         let s: Vec<u8> = vec![0xff; BITMASK_BYTE_LEN];
         let syscalls = RuleSyscalls::from_slice(&s[..]).unwrap();
-        assert_eq!(syscalls.0, vec![0xffff_ffff; AUDIT_BITMASK_SIZE]);
+        assert!(matches!(syscalls.0, SyscallMask::All));
 
         let s: Vec<u8> = vec![0; BITMASK_BYTE_LEN];
         let syscalls = RuleSyscalls::from_slice(&s[..]).unwrap();
-        assert_eq!(syscalls.0, vec![0; AUDIT_BITMASK_SIZE]);
+        assert!(matches!(syscalls.0, SyscallMask::Empty));
+
+        let mut s: Vec<u8> = vec![0; BITMASK_BYTE_LEN];
+        s[7] = 0x80;
+        let syscalls = RuleSyscalls::from_slice(&s[..]).unwrap();
+        assert_eq!(syscalls.into_iter().collect::<Vec<_>>(), vec![63]);
     }
 
     #[test]
@@ -214,10 +303,22 @@ mod test {
         for i in 0..BITMASK_BIT_LEN {
             syscalls.set(i);
         }
-        assert_eq!(syscalls.0, vec![0xffff_ffff; AUDIT_BITMASK_SIZE]);
+        assert!(matches!(syscalls.0, SyscallMask::All));
+        assert_eq!(syscalls, RuleSyscalls::new_maxed());
         for i in 0..BITMASK_BIT_LEN {
             syscalls.unset(BITMASK_BIT_LEN - 1 - i);
         }
-        assert_eq!(syscalls.0, vec![0; AUDIT_BITMASK_SIZE]);
+        assert!(matches!(syscalls.0, SyscallMask::Empty));
+
+        syscalls.set(59);
+        assert!(matches!(syscalls.0, SyscallMask::Bitmap(_)));
+        let mut other = RuleSyscalls::new_maxed();
+        assert!(syscalls < other);
+        for i in 0..BITMASK_BIT_LEN {
+            if i != 59 {
+                other.unset(i);
+            }
+        }
+        assert_eq!(syscalls, other);
     }
 }
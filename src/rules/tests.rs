@@ -2,9 +2,12 @@
 
 use netlink_packet_utils::traits::{Emitable, Parseable};
 
-use crate::rules::{
-    RuleAction, RuleBuffer, RuleField, RuleFieldFlags, RuleFlags, RuleMessage,
-    RuleSyscalls,
+use crate::{
+    rules::{
+        RuleAction, RuleBuffer, RuleField, RuleFieldFlags, RuleFlags,
+        RuleMessage, RuleSyscalls, RULE_BUF_MIN_LEN,
+    },
+    Endianness,
 };
 
 const AUDIT_ARCH_X86_64: u32 = 0xC000_003E;
@@ -54,6 +57,181 @@ fn emit_rule_3() {
     assert_eq!(&buf[..], &M3_BYTES[..]);
 }
 
+#[test]
+fn rule_ordering() {
+    use std::collections::{BTreeSet, HashSet};
+
+    let mut exit = RuleMessage::new();
+    exit.flags = RuleFlags::FilterExit;
+    exit.action = RuleAction::Always;
+    let mut key = exit.clone();
+    key.fields
+        .push((RuleField::Filterkey("a".into()), RuleFieldFlags::Equal));
+    let mut uid = exit.clone();
+    uid.fields.push((RuleField::Uid(0), RuleFieldFlags::Equal));
+    let mut user = exit.clone();
+    user.flags = RuleFlags::FilterUser;
+
+    // AUDIT_FILTER_USER < AUDIT_FILTER_EXIT, and AUDIT_UID < AUDIT_FILTERKEY
+    let sorted: Vec<_> =
+        vec![key.clone(), uid.clone(), exit.clone(), user.clone()]
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+    assert_eq!(sorted, vec![user, exit, uid, key]);
+
+    let set: HashSet<_> = sorted.iter().chain(sorted.iter()).collect();
+    assert_eq!(set.len(), 4);
+    assert!(
+        RuleFlags::Unknown(crate::constants::AUDIT_FILTER_EXIT)
+            > RuleFlags::FilterExit
+    );
+}
+
+#[test]
+fn validate_filter_fields() {
+    use crate::{constants::*, AuditEmitError, RuleFieldType};
+
+    let mut rule = RuleMessage::new();
+    rule.flags = RuleFlags::FilterUringExit;
+    rule.action = RuleAction::Always;
+    rule.fields
+        .push((RuleField::Filterkey("uring".into()), RuleFieldFlags::Equal));
+    assert_eq!(rule.validate(), Ok(()));
+
+    rule.fields
+        .push((RuleField::Perm(4), RuleFieldFlags::Equal));
+    assert_eq!(
+        rule.validate(),
+        Err(AuditEmitError::FieldNotAllowed {
+            field: RuleFieldType::Perm,
+            filter: RuleFlags::FilterUringExit,
+        })
+    );
+
+    // the prepend bit does not change the filter list
+    rule.flags = RuleFlags::from(AUDIT_FILTER_EXIT | AUDIT_FILTER_PREPEND);
+    assert_eq!(rule.validate(), Ok(()));
+
+    rule.flags = RuleFlags::FilterFs;
+    rule.fields.truncate(1);
+    rule.fields
+        .push((RuleField::Fstype(0x9fa0), RuleFieldFlags::Equal));
+    assert_eq!(rule.validate(), Ok(()));
+    rule.fields.push((RuleField::Uid(0), RuleFieldFlags::Equal));
+    assert!(rule.validate().is_err());
+}
+
+#[test]
+fn try_emit_rule() {
+    use crate::{constants::*, AuditEmitError, RuleFieldType};
+
+    let mut rule = RuleMessage::new();
+    rule.flags = RuleFlags::FilterExit;
+    rule.action = RuleAction::Always;
+    rule.fields.push((
+        RuleField::Filterkey("k".repeat(AUDIT_MAX_KEY_LEN)),
+        RuleFieldFlags::Equal,
+    ));
+    let mut buf = vec![0; rule.buffer_len() + 1];
+    assert_eq!(rule.try_emit(&mut buf), Ok(rule.buffer_len()));
+    assert_eq!(
+        rule.try_emit(&mut buf[..100]),
+        Err(AuditEmitError::BufferTooShort {
+            needed: rule.buffer_len(),
+            got: 100,
+        })
+    );
+
+    rule.fields[0].0 = RuleField::Filterkey("k".repeat(AUDIT_MAX_KEY_LEN + 1));
+    assert_eq!(
+        rule.try_emit(&mut buf),
+        Err(AuditEmitError::StringTooLong {
+            field: RuleFieldType::Filterkey,
+            len: AUDIT_MAX_KEY_LEN + 1,
+            max: AUDIT_MAX_KEY_LEN,
+        })
+    );
+    rule.fields[0].0 = RuleField::Watch("/".repeat(4097));
+    assert!(matches!(
+        rule.validate(),
+        Err(AuditEmitError::StringTooLong { max: 4096, .. })
+    ));
+}
+
+#[test]
+fn parse_rule_byte_order() {
+    let mut native = vec![0; M2.buffer_len()];
+    M2.emit(&mut native);
+    for order in [Endianness::Little, Endianness::Big] {
+        // the integers precede the string buffer
+        let mut bytes = native.clone();
+        for word in bytes[..RULE_BUF_MIN_LEN].chunks_exact_mut(4) {
+            let value = Endianness::native().read_u32(word);
+            order.write_u32(word, value);
+        }
+        let buf = RuleBuffer::with_byte_order(&bytes[..], order);
+        assert_eq!(buf.field_count(), M2.fields.len() as u32);
+        assert_eq!(RuleMessage::parse(&buf).unwrap(), *M2);
+    }
+}
+
+#[test]
+fn check_rule_buffer() {
+    use crate::{constants::*, AuditDecodeError};
+
+    let mut rule = RuleMessage::new();
+    rule.fields = vec![
+        (RuleField::Watch("/etc".into()), RuleFieldFlags::Equal),
+        (RuleField::Filterkey("key".into()), RuleFieldFlags::Equal),
+    ];
+    let mut bytes = vec![0; rule.buffer_len()];
+    rule.emit(&mut bytes);
+    assert_eq!(RuleBuffer::new(&bytes).check_len(), Ok(()));
+
+    let mut buf = RuleBuffer::new(bytes.clone());
+    buf.set_value(1, 4);
+    assert_eq!(
+        buf.check_len(),
+        Err(AuditDecodeError::InvalidRuleField {
+            field: AUDIT_FILTERKEY
+        })
+    );
+    buf.set_value(0, u32::MAX);
+    assert_eq!(
+        buf.check_len(),
+        Err(AuditDecodeError::InvalidRuleField { field: AUDIT_WATCH })
+    );
+
+    let mut buf = RuleBuffer::new(bytes);
+    buf.set_field_count(AUDIT_MAX_FIELDS as u32 + 1);
+    assert_eq!(
+        buf.check_len(),
+        Err(AuditDecodeError::TooManyRuleFields {
+            count: AUDIT_MAX_FIELDS + 1,
+            max: AUDIT_MAX_FIELDS,
+        })
+    );
+}
+
+#[test]
+fn rule_plan() {
+    use crate::rules::RulePlan;
+
+    let installed = vec![M1.clone(), M2.clone(), M2.clone()];
+    let desired = vec![M3.clone(), M2.clone(), M1.clone()];
+    let plan = RulePlan::new(&installed, &desired);
+    assert_eq!(plan.delete, vec![M2.clone()]);
+    assert_eq!(plan.add, vec![M3.clone()]);
+    assert!(!plan.is_empty());
+
+    assert!(RulePlan::new(&desired, &desired).is_empty());
+    let plan = RulePlan::new(&[], &desired);
+    assert_eq!(plan.add, desired);
+    assert!(plan.delete.is_empty());
+}
+
 lazy_static! {
     // -w /etc/passwd -p rwxa
     static ref M1_BYTES: Vec<u8> = vec![
@@ -194,3 +372,78 @@ lazy_static! {
         }
     };
 }
+
+/// Minimal xorshift generator, to build pseudo-random messages
+/// reproducibly
+pub(crate) struct XorShift(pub(crate) u64);
+
+impl XorShift {
+    pub(crate) fn next(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 32) as u32
+    }
+
+    pub(crate) fn below(&mut self, max: u32) -> u32 {
+        self.next() % max
+    }
+
+    pub(crate) fn string(&mut self) -> String {
+        let len = self.below(40) as usize;
+        (0..len)
+            .map(|_| char::from(b' ' + self.below(95) as u8))
+            .collect()
+    }
+
+    pub(crate) fn rule(&mut self) -> RuleMessage {
+        use self::RuleField::*;
+        use crate::constants::AUDIT_MAX_FIELDS;
+
+        #[rustfmt::skip]
+        let numeric = [
+            Pid, Uid, Euid, Suid, Fsuid, Gid, Egid, Sgid, Fsgid, Loginuid,
+            Pers, Arch, Msgtype, Ppid, LoginuidSet, Sessionid, Fstype,
+            Devmajor, Devminor, Inode, Exit, Success, Perm, Filetype, ObjUid,
+            ObjGid, FieldCompare, Exe, Arg0, Arg1, Arg2, Arg3,
+        ];
+        let string = [
+            Watch, Dir, Filterkey, SubjUser, SubjRole, SubjType, SubjSen,
+            SubjClr, ObjUser, ObjRole, ObjType, ObjLevLow, ObjLevHigh,
+        ];
+        let mut rule = RuleMessage::new();
+        rule.flags = RuleFlags::from(self.below(8));
+        rule.action = RuleAction::from(self.below(4));
+        for _ in 0..self.below(AUDIT_MAX_FIELDS as u32 + 1) {
+            let kind = self.below((numeric.len() + string.len()) as u32);
+            let field = match numeric.get(kind as usize) {
+                Some(field) => field(self.next()),
+                None => string[kind as usize - numeric.len()](self.string()),
+            };
+            let flags = RuleFieldFlags::from(self.below(16) << 27);
+            rule.fields.push((field, flags));
+        }
+        for _ in 0..self.below(64) {
+            rule.syscalls.set(self.below(2048));
+        }
+        rule
+    }
+}
+
+#[test]
+fn round_trip_random_rules() {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    for _ in 0..500 {
+        let rule = rng.rule();
+        let mut buf = vec![0; rule.buffer_len()];
+        rule.emit(&mut buf);
+        let parsed =
+            RuleMessage::parse(&RuleBuffer::new_checked(&buf[..]).unwrap())
+                .unwrap();
+        assert_eq!(parsed, rule);
+
+        let mut emitted = vec![0; parsed.buffer_len()];
+        parsed.emit(&mut emitted);
+        assert_eq!(emitted, buf);
+    }
+}
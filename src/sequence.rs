@@ -0,0 +1,307 @@
+// SPDX-License-Identifier: MIT
+
+use std::{collections::VecDeque, fmt, str::FromStr};
+
+use netlink_packet_utils::DecodeError;
+
+/// Identifier of an audit event, found at the beginning of each of its
+/// records: `audit(1592992200.383:66)`.
+///
+/// Identifiers are ordered by timestamp, then by serial number.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub struct EventId {
+    /// Seconds since the epoch
+    pub seconds: u64,
+    /// Milliseconds
+    pub milliseconds: u32,
+    /// Serial number of the event. All the records of an event share the
+    /// same serial number.
+    pub serial: u32,
+}
+
+impl EventId {
+    pub fn new(seconds: u64, milliseconds: u32, serial: u32) -> Self {
+        EventId {
+            seconds,
+            milliseconds,
+            serial,
+        }
+    }
+
+    /// Parse the event identifier at the beginning of a record
+    pub fn from_record<T>(record: &T) -> Result<Self, DecodeError>
+    where
+        T: AsRef<[u8]> + ?Sized,
+    {
+        let record = record.as_ref();
+        let end = record
+            .iter()
+            .position(|c| *c == b')')
+            .map(|i| i + 1)
+            .unwrap_or(record.len());
+        std::str::from_utf8(&record[..end])
+            .map_err(|e| format!("invalid event id: {e}"))?
+            .parse()
+    }
+
+    /// Format the timestamp of the event as an RFC 3339 date, in UTC
+    pub(crate) fn rfc3339(&self) -> String {
+        let (year, month, day) = civil_from_days(self.seconds / 86400);
+        let secs = self.seconds % 86400;
+        format!(
+            "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+            secs / 3600,
+            secs % 3600 / 60,
+            secs % 60,
+            self.milliseconds
+        )
+    }
+}
+
+impl FromStr for EventId {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || DecodeError::from(format!("invalid event id {s:?}"));
+        let inner = s
+            .strip_prefix("audit(")
+            .and_then(|s| s.strip_suffix(')'))
+            .ok_or_else(err)?;
+        let (timestamp, serial) = inner.split_once(':').ok_or_else(err)?;
+        let (seconds, milliseconds) =
+            timestamp.split_once('.').ok_or_else(err)?;
+        Ok(EventId {
+            seconds: seconds.parse().map_err(|_| err())?,
+            milliseconds: milliseconds.parse().map_err(|_| err())?,
+            serial: serial.parse().map_err(|_| err())?,
+        })
+    }
+}
+
+impl fmt::Display for EventId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "audit({}.{:03}:{})",
+            self.seconds, self.milliseconds, self.serial
+        )
+    }
+}
+
+/// Convert a number of days since the epoch to a `(year, month, day)` date
+/// in the proleptic Gregorian calendar, see
+/// <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+pub(crate) fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Serial numbers missing between two consecutive events
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub struct SequenceGap {
+    /// Serial number that was expected
+    pub expected: u32,
+    /// Serial number that was received instead
+    pub received: u32,
+}
+
+impl SequenceGap {
+    /// Number of events that are missing
+    pub fn count(&self) -> u32 {
+        self.received.wrapping_sub(self.expected)
+    }
+}
+
+/// Number of serial numbers a record can arrive late by, by default,
+/// before the events it belongs to are reported as lost
+pub const DEFAULT_REORDER_WINDOW: u32 = 16;
+
+/// Detect the gaps in the serial numbers of the events received from the
+/// kernel, which usually means events were lost.
+///
+/// The records of concurrent events can be interleaved, so the serial
+/// numbers are not always received in order. A missing serial number is
+/// only reported once a serial number greater by more than the reorder
+/// window has been received.
+///
+/// Note that the kernel also allocates serial numbers to events that are
+/// filtered out by the rules, so a gap does not always mean an event was
+/// lost.
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct SequenceTracker {
+    last: Option<u32>,
+    window: u32,
+    /// Serial numbers not received yet, in increasing order
+    missing: VecDeque<SequenceGap>,
+}
+
+impl Default for SequenceTracker {
+    fn default() -> Self {
+        Self::with_reorder_window(DEFAULT_REORDER_WINDOW)
+    }
+}
+
+impl SequenceTracker {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Create a tracker that waits for records arriving late by up to
+    /// `window` serial numbers. With a window of 0, the gaps are reported
+    /// as soon as they are observed.
+    pub fn with_reorder_window(window: u32) -> Self {
+        SequenceTracker {
+            last: None,
+            window,
+            missing: VecDeque::new(),
+        }
+    }
+
+    /// Record the serial number of an event record, and return the gaps
+    /// that fell out of the reorder window. Records of the same event can
+    /// be observed several times. If the serial number goes backward by
+    /// more than the reorder window, the kernel is assumed to have been
+    /// restarted: the pending gaps are returned, and tracking starts over.
+    pub fn observe(&mut self, serial: u32) -> Vec<SequenceGap> {
+        let last = match self.last {
+            Some(last) => last,
+            None => {
+                self.last = Some(serial);
+                return vec![];
+            }
+        };
+        if serial > last {
+            self.last = Some(serial);
+            if serial - last > 1 {
+                self.missing.push_back(SequenceGap {
+                    expected: last + 1,
+                    received: serial,
+                });
+            }
+            self.expire(serial)
+        } else if last - serial <= self.window {
+            self.fill(serial);
+            vec![]
+        } else {
+            self.last = Some(serial);
+            self.flush()
+        }
+    }
+
+    /// Return the gaps still waiting for late records, and forget them
+    pub fn flush(&mut self) -> Vec<SequenceGap> {
+        self.missing.drain(..).collect()
+    }
+
+    /// Remove `serial` from the missing serial numbers
+    fn fill(&mut self, serial: u32) {
+        let index = match self
+            .missing
+            .iter()
+            .position(|gap| gap.expected <= serial && serial < gap.received)
+        {
+            Some(index) => index,
+            None => return,
+        };
+        let gap = self.missing[index];
+        let after = SequenceGap {
+            expected: serial + 1,
+            received: gap.received,
+        };
+        self.missing[index].received = serial;
+        if after.count() > 0 {
+            self.missing.insert(index + 1, after);
+        }
+        if self.missing[index].count() == 0 {
+            self.missing.remove(index);
+        }
+    }
+
+    /// Remove and return the gaps whose last serial number fell out of the
+    /// reorder window
+    fn expire(&mut self, last: u32) -> Vec<SequenceGap> {
+        let mut expired = vec![];
+        while let Some(gap) = self.missing.front() {
+            if last - (gap.received - 1) <= self.window {
+                break;
+            }
+            expired.extend(self.missing.pop_front());
+        }
+        expired
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_event_id() {
+        let id =
+            EventId::from_record("audit(1592992200.083:66): pid=1").unwrap();
+        assert_eq!(id, EventId::new(1592992200, 83, 66));
+        assert_eq!(id.to_string(), "audit(1592992200.083:66)");
+        assert!(EventId::from_record("pid=1").is_err());
+        assert!(EventId::from_record("audit(1592992200:66)").is_err());
+    }
+
+    #[test]
+    fn test_sequence_tracker() {
+        let mut tracker = SequenceTracker::with_reorder_window(0);
+        assert_eq!(tracker.observe(10), vec![]);
+        assert_eq!(tracker.observe(10), vec![]);
+        assert_eq!(tracker.observe(11), vec![]);
+        let gaps = tracker.observe(14);
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].expected, 12);
+        assert_eq!(gaps[0].count(), 2);
+        assert_eq!(tracker.observe(1), vec![]);
+        assert_eq!(tracker.observe(2), vec![]);
+    }
+
+    #[test]
+    fn test_sequence_tracker_reorder() {
+        let mut tracker = SequenceTracker::with_reorder_window(4);
+        // records of concurrent events, interleaved
+        for serial in [10, 12, 11, 12, 14, 13, 15, 18, 16, 20] {
+            assert_eq!(tracker.observe(serial), vec![]);
+        }
+        // 17 and 19 are still missing
+        let gaps = tracker.observe(22);
+        assert_eq!(gaps.len(), 1);
+        assert_eq!((gaps[0].expected, gaps[0].count()), (17, 1));
+        let gaps = tracker.flush();
+        assert_eq!(gaps.len(), 2);
+        assert_eq!((gaps[0].expected, gaps[0].count()), (19, 1));
+        assert_eq!((gaps[1].expected, gaps[1].count()), (21, 1));
+
+        // a late record splits the missing serial numbers
+        assert_eq!(tracker.observe(30), vec![]);
+        assert_eq!(tracker.observe(27), vec![]);
+        let gaps = tracker.observe(40);
+        assert_eq!(gaps.len(), 2);
+        assert_eq!((gaps[0].expected, gaps[0].count()), (23, 4));
+        assert_eq!((gaps[1].expected, gaps[1].count()), (28, 2));
+        let gaps = tracker.observe(40);
+        assert!(gaps.is_empty());
+
+        // restart of the kernel
+        assert_eq!(tracker.observe(42), vec![]);
+        let gaps = tracker.observe(1);
+        assert_eq!(gaps.len(), 2);
+        assert_eq!((gaps[0].expected, gaps[0].count()), (31, 9));
+        assert_eq!((gaps[1].expected, gaps[1].count()), (41, 1));
+        assert_eq!(tracker.observe(2), vec![]);
+    }
+}
@@ -0,0 +1,416 @@
+// SPDX-License-Identifier: MIT
+
+use std::{
+    collections::HashMap,
+    fmt::Write as _,
+    io::{self, Write},
+};
+
+use crate::{
+    grouper::EventGrouper, AuditMessage, EventId, EventLimits, RecordFields,
+};
+
+/// Keys of the CEF extension fields the audit fields are mapped to, by
+/// default
+const CEF_KEYS: [(&str, &str); 9] = [
+    ("serial", "externalId"),
+    ("pid", "spid"),
+    ("uid", "suid"),
+    ("exe", "sproc"),
+    ("name", "filePath"),
+    ("acct", "suser"),
+    ("addr", "src"),
+    ("hostname", "shost"),
+    ("res", "outcome"),
+];
+/// Keys of the LEEF attributes the audit fields are mapped to, by default
+const LEEF_KEYS: [(&str, &str); 3] = [
+    ("acct", "usrName"),
+    ("addr", "src"),
+    ("hostname", "srcHostName"),
+];
+/// `devTimeFormat` of the LEEF events, in the syntax of Java's
+/// `SimpleDateFormat`
+const LEEF_TIME_FORMAT: &str = "yyyy-MM-dd'T'HH:mm:ss.SSSX";
+
+/// Line format of a [`SiemWriter`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub enum SiemFormat {
+    /// ArcSight Common Event Format, version 0
+    Cef,
+    /// IBM QRadar Log Event Extended Format, version 1.0
+    Leef,
+}
+
+/// Write audit events as CEF or LEEF lines, one line per event:
+///
+/// ```text
+/// CEF:0|Linux|audit||SYSCALL|SYSCALL|3|rt=1592992200383 externalId=66 arch=c000003e syscall=59 ...
+/// LEEF:1.0|Linux|audit||SYSCALL|devTime=2020-06-24T09:50:00.383Z devTimeFormat=... serial=66 arch=c000003e ...
+/// ```
+///
+/// The fields are separated by spaces in CEF, and by tabs in LEEF.
+///
+/// The records of an event are grouped as with
+/// [`AusearchWriter`](crate::AusearchWriter), and the event is named after
+/// its first record. The fields of all the records are written in the
+/// order they were received, with the fields of the `msg` of the user
+/// messages. Hex encoded strings are decoded.
+///
+/// Fields are mapped to the keys each format defines (for instance `pid`
+/// to `spid` in CEF, or `acct` to `usrName` in LEEF), and other mappings
+/// can be added with [`SiemWriter::map_field`]. If several fields have the
+/// same key, as the fields of the `PATH` records of an event, the key of
+/// the later ones is numbered: the `name` of the second `PATH` record is
+/// written as `filePath1`, the third as `filePath2`. Fields whose names
+/// are not valid keys are left out.
+#[derive(Debug)]
+pub struct SiemWriter<W> {
+    writer: W,
+    format: SiemFormat,
+    vendor: String,
+    product: String,
+    version: String,
+    severity: u8,
+    keys: HashMap<String, String>,
+    grouper: EventGrouper,
+}
+
+impl<W: Write> SiemWriter<W> {
+    /// Create a writer with the default mapping of `format`, that
+    /// identifies the events as produced by the `audit` product of `Linux`
+    pub fn new(writer: W, format: SiemFormat) -> Self {
+        let keys = match format {
+            SiemFormat::Cef => &CEF_KEYS[..],
+            SiemFormat::Leef => &LEEF_KEYS[..],
+        };
+        SiemWriter {
+            writer,
+            format,
+            vendor: "Linux".to_string(),
+            product: "audit".to_string(),
+            version: String::new(),
+            severity: 3,
+            keys: keys
+                .iter()
+                .map(|(field, key)| (field.to_string(), key.to_string()))
+                .collect(),
+            grouper: EventGrouper::default(),
+        }
+    }
+
+    /// Set the vendor, product and version of the device the events are
+    /// attributed to
+    pub fn device(
+        mut self,
+        vendor: &str,
+        product: &str,
+        version: &str,
+    ) -> Self {
+        self.vendor = vendor.to_string();
+        self.product = product.to_string();
+        self.version = version.to_string();
+        self
+    }
+
+    /// Set the severity of the events, from 0 to 10. It is 3 by default.
+    pub fn severity(mut self, severity: u8) -> Self {
+        self.severity = severity.min(10);
+        self
+    }
+
+    /// Write the `field` of the records under `key`. The serial number of
+    /// the events can be mapped as the `serial` field.
+    pub fn map_field(mut self, field: &str, key: &str) -> Self {
+        self.keys.insert(field.to_string(), key.to_string());
+        self
+    }
+
+    /// Set the limits on the records buffered while waiting for the end of
+    /// the events
+    pub fn limits(mut self, limits: EventLimits) -> Self {
+        self.grouper.set_limits(limits);
+        self
+    }
+
+    /// Return the number of events written before their last record was
+    /// received, because of the limits
+    pub fn forced_flushes(&self) -> u64 {
+        self.grouper.forced_flushes()
+    }
+
+    pub fn write(&mut self, message: &AuditMessage) -> io::Result<()> {
+        let (writer, grouper, header) = self.split();
+        grouper.push(message, |id, records| {
+            header.write_event(writer, id, records)
+        })
+    }
+
+    /// Write the records of the pending events
+    pub fn flush(&mut self) -> io::Result<()> {
+        let (writer, grouper, header) = self.split();
+        grouper.flush(|id, records| header.write_event(writer, id, records))
+    }
+
+    /// Write the records of the pending events, and return the underlying
+    /// writer
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.flush()?;
+        Ok(self.writer)
+    }
+
+    fn split(&mut self) -> (&mut W, &mut EventGrouper, Header<'_>) {
+        let SiemWriter {
+            writer,
+            format,
+            vendor,
+            product,
+            version,
+            severity,
+            keys,
+            grouper,
+        } = self;
+        let header = Header {
+            format: *format,
+            vendor,
+            product,
+            version,
+            severity: *severity,
+            keys,
+        };
+        (writer, grouper, header)
+    }
+}
+
+/// Configuration of a [`SiemWriter`], borrowed while its writer is in use
+struct Header<'a> {
+    format: SiemFormat,
+    vendor: &'a str,
+    product: &'a str,
+    version: &'a str,
+    severity: u8,
+    keys: &'a HashMap<String, String>,
+}
+
+impl<'a> Header<'a> {
+    fn write_event<W: Write>(
+        &self,
+        writer: &mut W,
+        id: Option<EventId>,
+        records: &[AuditMessage],
+    ) -> io::Result<()> {
+        let name = match records.first() {
+            Some(record) => record.message_type().to_string(),
+            None => return Ok(()),
+        };
+        let mut line = Line::new(self.format);
+        match self.format {
+            SiemFormat::Cef => line.out.push_str("CEF:0"),
+            SiemFormat::Leef => line.out.push_str("LEEF:1.0"),
+        }
+        for field in [self.vendor, self.product, self.version, &name] {
+            line.header(field);
+        }
+        if self.format == SiemFormat::Cef {
+            line.header(&name);
+            line.header(&self.severity.to_string());
+        }
+        line.out.push('|');
+
+        if let Some(id) = id {
+            match self.format {
+                SiemFormat::Cef => {
+                    let millis = id.seconds * 1000 + u64::from(id.milliseconds);
+                    line.field("rt", &millis.to_string());
+                }
+                SiemFormat::Leef => {
+                    line.field("devTime", &id.rfc3339());
+                    line.field("devTimeFormat", LEEF_TIME_FORMAT);
+                    line.field("sev", &self.severity.to_string());
+                }
+            }
+            line.field(self.key("serial"), &id.serial.to_string());
+        } else if self.format == SiemFormat::Leef {
+            line.field("sev", &self.severity.to_string());
+        }
+        for record in records {
+            let data = match record.data_lossy() {
+                Some(data) => data,
+                None => continue,
+            };
+            for field in RecordFields::new(&data) {
+                if field.name == "msg" && field.value.contains('=') {
+                    for field in RecordFields::new(field.value) {
+                        line.field(self.key(field.name), &field.decoded());
+                    }
+                } else {
+                    line.field(self.key(field.name), &field.decoded());
+                }
+            }
+        }
+        line.out.push('\n');
+        writer.write_all(line.out.as_bytes())?;
+        writer.flush()
+    }
+
+    fn key<'b>(&'b self, field: &'b str) -> &'b str {
+        self.keys.get(field).map_or(field, String::as_str)
+    }
+}
+
+/// CEF or LEEF line being written
+struct Line {
+    format: SiemFormat,
+    out: String,
+    keys: Vec<String>,
+}
+
+impl Line {
+    fn new(format: SiemFormat) -> Self {
+        Line {
+            format,
+            out: String::new(),
+            keys: Vec::new(),
+        }
+    }
+
+    /// Append a header field, with the pipes and backslashes escaped
+    fn header(&mut self, value: &str) {
+        self.out.push('|');
+        for c in value.chars() {
+            match c {
+                '|' | '\\' => {
+                    self.out.push('\\');
+                    self.out.push(c);
+                }
+                c if c.is_control() => self.out.push(' '),
+                c => self.out.push(c),
+            }
+        }
+    }
+
+    /// Append a `key=value` field, unless the key is not valid. If the key
+    /// was already written, it is numbered.
+    fn field(&mut self, key: &str, value: &str) {
+        if !is_key(key) {
+            return;
+        }
+        let mut key = key.to_string();
+        if self.keys.contains(&key) {
+            key = (1..)
+                .map(|i| format!("{key}{i}"))
+                .find(|k| !self.keys.contains(k))
+                .unwrap();
+        }
+        if !self.keys.is_empty() {
+            match self.format {
+                SiemFormat::Cef => self.out.push(' '),
+                SiemFormat::Leef => self.out.push('\t'),
+            }
+        }
+        write!(self.out, "{key}=").unwrap();
+        self.keys.push(key);
+        for c in value.chars() {
+            match (self.format, c) {
+                (SiemFormat::Cef, '\\' | '=') => {
+                    self.out.push('\\');
+                    self.out.push(c);
+                }
+                (SiemFormat::Cef, '\n') => self.out.push_str("\\n"),
+                (SiemFormat::Cef, '\r') => self.out.push_str("\\r"),
+                (_, c) if c.is_control() => self.out.push(' '),
+                (_, c) => self.out.push(c),
+            }
+        }
+    }
+}
+
+/// Return `true` if `key` can be used as the key of a CEF or LEEF field
+fn is_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .bytes()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, b'_' | b'.'))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{constants::*, EventPayload, UserMessage, UserMessageKind};
+
+    fn record(message_type: u16, data: &str) -> AuditMessage {
+        AuditMessage::Event(EventPayload::new(
+            message_type.into(),
+            data.as_bytes().to_vec(),
+        ))
+    }
+
+    fn messages() -> Vec<AuditMessage> {
+        vec![
+            record(
+                AUDIT_SYSCALL,
+                "audit(1592992200.383:66): syscall=59 pid=1024 uid=0 \
+                exe=2F746D702F6D7920657865 key=\"a=b\"",
+            ),
+            record(AUDIT_CWD, "audit(1592992200.383:66): cwd=\"/\""),
+            record(
+                AUDIT_PATH,
+                "audit(1592992200.383:66): item=0 name=\"/a|b\"",
+            ),
+            record(AUDIT_PATH, "audit(1592992200.383:66): item=1 name=\"/c\""),
+            record(AUDIT_EOE, "audit(1592992200.383:66): "),
+            AuditMessage::GetStatus(None),
+            AuditMessage::User(UserMessage::new(
+                UserMessageKind::UserLogin,
+                b"audit(1592992201.000:67): pid=1 msg='op=login \
+                acct=\"root\" addr=10.0.0.1 res=success'"
+                    .to_vec(),
+            )),
+        ]
+    }
+
+    fn write(writer: SiemWriter<Vec<u8>>) -> String {
+        let mut writer = writer;
+        for msg in messages() {
+            writer.write(&msg).unwrap();
+        }
+        String::from_utf8(writer.into_inner().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_cef() {
+        let writer = SiemWriter::new(vec![], SiemFormat::Cef)
+            .device("Acme|Corp", "audit", "1.0")
+            .map_field("key", "cs1");
+        assert_eq!(
+            write(writer),
+            "CEF:0|Acme\\|Corp|audit|1.0|SYSCALL|SYSCALL|3|\
+            rt=1592992200383 externalId=66 syscall=59 spid=1024 suid=0 \
+            sproc=/tmp/my exe cs1=a\\=b cwd=/ item=0 filePath=/a|b item1=1 \
+            filePath1=/c\n\
+            CEF:0|Acme\\|Corp|audit|1.0|GET|GET|3|\n\
+            CEF:0|Acme\\|Corp|audit|1.0|USER_LOGIN|USER_LOGIN|3|\
+            rt=1592992201000 externalId=67 spid=1 op=login suser=root \
+            src=10.0.0.1 outcome=success\n"
+        );
+    }
+
+    #[test]
+    fn test_leef() {
+        let writer = SiemWriter::new(vec![], SiemFormat::Leef).severity(12);
+        assert_eq!(
+            write(writer),
+            "LEEF:1.0|Linux|audit||SYSCALL|\
+            devTime=2020-06-24T09:50:00.383Z\t\
+            devTimeFormat=yyyy-MM-dd'T'HH:mm:ss.SSSX\tsev=10\tserial=66\t\
+            syscall=59\tpid=1024\tuid=0\texe=/tmp/my exe\tkey=a=b\tcwd=/\t\
+            item=0\tname=/a|b\titem1=1\tname1=/c\n\
+            LEEF:1.0|Linux|audit||GET|sev=10\n\
+            LEEF:1.0|Linux|audit||USER_LOGIN|\
+            devTime=2020-06-24T09:50:01.000Z\t\
+            devTimeFormat=yyyy-MM-dd'T'HH:mm:ss.SSSX\tsev=10\tserial=67\t\
+            pid=1\top=login\tusrName=root\tsrc=10.0.0.1\tres=success\n"
+        );
+    }
+}
@@ -0,0 +1,163 @@
+// SPDX-License-Identifier: MIT
+
+use byteorder::{ByteOrder, NativeEndian};
+
+use netlink_packet_utils::{
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use crate::{AuditDecodeError, Field};
+
+const UID: Field = 0..4;
+const PID: Field = 4..8;
+const CONTEXT: usize = PID.end;
+pub const SIGNAL_INFO_MESSAGE_MIN_LEN: usize = CONTEXT;
+
+/// Payload of the `AUDIT_SIGNAL_INFO` replies (`struct audit_sig_info`):
+/// the process that last sent a signal to the audit daemon
+#[derive(Debug, PartialEq, Eq, Clone, Default, Hash)]
+#[non_exhaustive]
+pub struct SignalInfoMessage {
+    /// Login uid of the sender
+    pub uid: u32,
+    /// Pid of the sender
+    pub pid: u32,
+    /// Security context of the sender, empty if there is no LSM
+    pub context: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct SignalInfoMessageBuffer<T> {
+    buffer: T,
+}
+
+impl<T: AsRef<[u8]>> SignalInfoMessageBuffer<T> {
+    pub fn new(buffer: T) -> SignalInfoMessageBuffer<T> {
+        SignalInfoMessageBuffer { buffer }
+    }
+
+    pub fn new_checked(
+        buffer: T,
+    ) -> Result<SignalInfoMessageBuffer<T>, DecodeError> {
+        let buf = Self::new(buffer);
+        buf.check_buffer_length()?;
+        Ok(buf)
+    }
+
+    pub fn check_buffer_length(&self) -> Result<(), AuditDecodeError> {
+        let len = self.buffer.as_ref().len();
+        if len < SIGNAL_INFO_MESSAGE_MIN_LEN {
+            return Err(AuditDecodeError::TruncatedSignalInfo {
+                needed: SIGNAL_INFO_MESSAGE_MIN_LEN,
+                got: len,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> T {
+        self.buffer
+    }
+
+    pub fn uid(&self) -> u32 {
+        NativeEndian::read_u32(&self.buffer.as_ref()[UID])
+    }
+
+    pub fn pid(&self) -> u32 {
+        NativeEndian::read_u32(&self.buffer.as_ref()[PID])
+    }
+
+    pub fn context(&self) -> &[u8] {
+        &self.buffer.as_ref()[CONTEXT..]
+    }
+}
+
+impl<T: AsRef<[u8]> + AsMut<[u8]>> SignalInfoMessageBuffer<T> {
+    pub fn set_uid(&mut self, value: u32) {
+        NativeEndian::write_u32(&mut self.buffer.as_mut()[UID], value)
+    }
+
+    pub fn set_pid(&mut self, value: u32) {
+        NativeEndian::write_u32(&mut self.buffer.as_mut()[PID], value)
+    }
+
+    pub fn context_mut(&mut self) -> &mut [u8] {
+        &mut self.buffer.as_mut()[CONTEXT..]
+    }
+}
+
+impl<T: AsRef<[u8]>> Parseable<SignalInfoMessageBuffer<T>>
+    for SignalInfoMessage
+{
+    fn parse(buf: &SignalInfoMessageBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self::try_parse(buf)?)
+    }
+}
+
+impl SignalInfoMessage {
+    pub(crate) fn try_parse<T: AsRef<[u8]>>(
+        buf: &SignalInfoMessageBuffer<T>,
+    ) -> Result<Self, AuditDecodeError> {
+        buf.check_buffer_length()?;
+        // the context is NUL terminated
+        let context = buf.context();
+        let context = match context.iter().position(|c| *c == 0) {
+            Some(end) => &context[..end],
+            None => context,
+        };
+        Ok(SignalInfoMessage {
+            uid: buf.uid(),
+            pid: buf.pid(),
+            context: String::from_utf8_lossy(context).into_owned(),
+        })
+    }
+}
+
+impl Emitable for SignalInfoMessage {
+    fn buffer_len(&self) -> usize {
+        if self.context.is_empty() {
+            CONTEXT
+        } else {
+            CONTEXT + self.context.len() + 1
+        }
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = SignalInfoMessageBuffer::new(buffer);
+        buffer.set_uid(self.uid);
+        buffer.set_pid(self.pid);
+        let context = buffer.context_mut();
+        if !self.context.is_empty() {
+            context[..self.context.len()]
+                .copy_from_slice(self.context.as_bytes());
+            context[self.context.len()] = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_signal_info() {
+        let msg = SignalInfoMessage {
+            uid: 1000,
+            pid: 42,
+            context: "system_u:system_r:init_t:s0".into(),
+        };
+        let mut buf = vec![0; msg.buffer_len()];
+        msg.emit(&mut buf);
+        assert_eq!(buf.len(), 8 + 28);
+        assert_eq!(buf[buf.len() - 1], 0);
+        let parsed = SignalInfoMessage::parse(
+            &SignalInfoMessageBuffer::new_checked(&buf).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(parsed, msg);
+
+        assert!(SignalInfoMessageBuffer::new_checked(&buf[..7]).is_err());
+    }
+}
@@ -1,13 +1,16 @@
 // SPDX-License-Identifier: MIT
 
-use byteorder::{ByteOrder, NativeEndian};
+use std::fmt;
 
+use netlink_packet_core::ErrorMessage;
 use netlink_packet_utils::{
-    traits::{Emitable, Parseable},
+    traits::{Emitable, Parseable, ParseableParametrized},
     DecodeError,
 };
 
-use crate::Field;
+use crate::{
+    constants::*, AuditDecodeError, AuditEmitError, Endianness, Field,
+};
 
 const MASK: Field = 0..4;
 const ENABLED: Field = 4..8;
@@ -19,9 +22,63 @@ const LOST: Field = 24..28;
 const BACKLOG: Field = 28..32;
 const FEATURE_BITMAP: Field = 32..36;
 const BACKLOG_WAIT_TIME: Field = 36..40;
-pub const STATUS_MESSAGE_LEN: usize = BACKLOG_WAIT_TIME.end;
+const BACKLOG_WAIT_TIME_ACTUAL: Field = 40..44;
+pub const STATUS_MESSAGE_LEN: usize = BACKLOG_WAIT_TIME_ACTUAL.end;
+/// Length of the oldest `struct audit_status` layout
+pub const STATUS_MESSAGE_MIN_LEN: usize = BACKLOG.end;
 
-#[derive(Debug, PartialEq, Eq, Clone, Default)]
+/// A `struct audit_status` layout, as found in a given kernel era
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub struct StatusLayout {
+    /// Size of the structure in bytes
+    pub len: usize,
+    /// Name of the last field of the structure
+    pub last_field: &'static str,
+}
+
+/// Known layouts of `struct audit_status`, from the oldest to the most
+/// recent one. Each layout only appends a field to the previous one.
+pub const STATUS_MESSAGE_LAYOUTS: [StatusLayout; 4] = [
+    // original layout
+    StatusLayout {
+        len: BACKLOG.end,
+        last_field: "backlog",
+    },
+    // feature bitmap (initially a version number)
+    StatusLayout {
+        len: FEATURE_BITMAP.end,
+        last_field: "feature_bitmap",
+    },
+    // backlog wait time (Linux 3.14)
+    StatusLayout {
+        len: BACKLOG_WAIT_TIME.end,
+        last_field: "backlog_wait_time",
+    },
+    // actual backlog wait time (Linux 5.10)
+    StatusLayout {
+        len: BACKLOG_WAIT_TIME_ACTUAL.end,
+        last_field: "backlog_wait_time_actual",
+    },
+];
+
+/// How strictly the length of a status message is checked when parsing it
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Hash)]
+#[non_exhaustive]
+pub enum StatusParseMode {
+    /// The length must match one of the [`STATUS_MESSAGE_LAYOUTS`]
+    Strict,
+    /// Any length of at least [`STATUS_MESSAGE_MIN_LEN`] is accepted. The
+    /// fields that are not present are set to 0 and trailing bytes are
+    /// ignored.
+    #[default]
+    Lenient,
+    /// Any length is accepted: the buffer is padded with zeros up to
+    /// [`STATUS_MESSAGE_LEN`].
+    Padded,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Hash)]
 #[non_exhaustive]
 pub struct StatusMessage {
     /// Bit mask for valid entries
@@ -43,23 +100,315 @@ pub struct StatusMessage {
     pub feature_bitmap: u32,
     /// Message queue wait timeout
     pub backlog_wait_time: u32,
+    /// Time spent waiting while the message limit was exceeded
+    pub backlog_wait_time_actual: u32,
 }
 
 impl StatusMessage {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Build an `AUDIT_SET` payload that resets the kernel's lost
+    /// messages counter. This requires a kernel advertising
+    /// `AUDIT_FEATURE_BITMAP_LOST_RESET`.
+    ///
+    /// The kernel replies with the value the counter had before the
+    /// reset, which can be retrieved from the acknowledgement with
+    /// [`StatusMessage::lost_from_ack`].
+    pub fn reset_lost() -> Self {
+        StatusMessage {
+            mask: AUDIT_STATUS_LOST,
+            lost: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Build an `AUDIT_SET` payload that resets the kernel's
+    /// `backlog_wait_time_actual` counter (Linux 5.10+). As with
+    /// [`StatusMessage::reset_lost`], the kernel replies with the previous
+    /// value, which [`StatusMessage::lost_from_ack`] extracts as well.
+    pub fn reset_backlog_wait_time_actual() -> Self {
+        StatusMessage {
+            mask: AUDIT_STATUS_BACKLOG_WAIT_TIME_ACTUAL,
+            ..Default::default()
+        }
+    }
+
+    /// Build an `AUDIT_SET` payload that registers the process `pid` as
+    /// the audit daemon, or unregisters the current daemon if `pid` is 0.
+    /// The events are then sent to the socket the request is sent on.
+    pub fn new_pid(pid: u32) -> Self {
+        StatusMessage {
+            mask: AUDIT_STATUS_PID,
+            pid,
+            ..Default::default()
+        }
+    }
+
+    /// Build an `AUDIT_SET` payload that only updates the rate limit
+    /// (messages per second, 0 meaning no limit).
+    pub fn new_rate_limit(rate_limiting: u32) -> Self {
+        StatusMessage {
+            mask: AUDIT_STATUS_RATE_LIMIT,
+            rate_limiting,
+            ..Default::default()
+        }
+    }
+
+    /// Build an `AUDIT_SET` payload that only updates the backlog limit
+    /// (0 meaning no limit).
+    pub fn new_backlog_limit(backlog_limit: u32) -> Self {
+        StatusMessage {
+            mask: AUDIT_STATUS_BACKLOG_LIMIT,
+            backlog_limit,
+            ..Default::default()
+        }
+    }
+
+    /// Build an `AUDIT_SET` payload that only updates the backlog wait
+    /// time. This requires a kernel advertising
+    /// `AUDIT_FEATURE_BITMAP_BACKLOG_WAIT_TIME`.
+    pub fn new_backlog_wait_time(backlog_wait_time: u32) -> Self {
+        StatusMessage {
+            mask: AUDIT_STATUS_BACKLOG_WAIT_TIME,
+            backlog_wait_time,
+            ..Default::default()
+        }
+    }
+
+    /// Compute the `AUDIT_SET` payloads needed to move from the current
+    /// status (as returned by `AUDIT_GET`) to the given backlog limit and
+    /// backlog wait time.
+    ///
+    /// Each field is updated by a dedicated message, so that a failure
+    /// can be attributed to a single setting. When the backlog limit is
+    /// raised, it is updated before the wait time, so that the queue
+    /// grows before senders are made to wait longer on it. When it is
+    /// lowered, the wait time is updated first. Settings that already
+    /// have the requested value are skipped.
+    ///
+    /// The request is validated against the feature bitmap reported by
+    /// the kernel. The range of wait times the kernel accepts depends on
+    /// its tick rate (`HZ`), so it is not checked here: the kernel
+    /// rejects the values out of range with `EINVAL`.
+    pub fn backlog_tuning(
+        &self,
+        backlog_limit: u32,
+        backlog_wait_time: u32,
+    ) -> Result<Vec<StatusMessage>, StatusTuningError> {
+        let mut limit = None;
+        let mut wait_time = None;
+
+        if backlog_limit != self.backlog_limit {
+            limit = Some(Self::new_backlog_limit(backlog_limit));
+        }
+
+        if backlog_wait_time != self.backlog_wait_time {
+            if self.feature_bitmap & AUDIT_FEATURE_BITMAP_BACKLOG_WAIT_TIME == 0
+            {
+                return Err(StatusTuningError::Unsupported(
+                    AUDIT_FEATURE_BITMAP_BACKLOG_WAIT_TIME,
+                ));
+            }
+            wait_time = Some(Self::new_backlog_wait_time(backlog_wait_time));
+        }
+
+        let messages = if backlog_limit >= self.backlog_limit {
+            limit.into_iter().chain(wait_time).collect()
+        } else {
+            wait_time.into_iter().chain(limit).collect()
+        };
+        Ok(messages)
+    }
+
+    /// Extract the previous value of the lost messages counter from the
+    /// acknowledgement of a [`StatusMessage::reset_lost`] request.
+    ///
+    /// Instead of a regular ACK, the kernel reports the old counter value
+    /// as a positive code in the `NLMSG_ERROR` message, so a plain ACK
+    /// means it was zero. Returns `None` if the message carries an actual
+    /// (negative) error code.
+    pub fn lost_from_ack(ack: &ErrorMessage) -> Option<u32> {
+        match ack.raw_code() {
+            code if code >= 0 => Some(code as u32),
+            _ => None,
+        }
+    }
+
+    /// Return the mask of the fields to update as a [`StatusMask`]
+    pub fn status_mask(&self) -> StatusMask {
+        StatusMask::from_raw(self.mask)
+    }
+
+    /// Return the feature bitmap as a [`FeatureBitmap`]
+    pub fn features(&self) -> FeatureBitmap {
+        FeatureBitmap::from_raw(self.feature_bitmap)
+    }
+}
+
+/// Error returned by the [`StatusMessage`] tuning helpers
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub enum StatusTuningError {
+    /// The kernel does not advertise the given
+    /// `AUDIT_FEATURE_BITMAP_*` feature.
+    Unsupported(u32),
+}
+
+impl fmt::Display for StatusTuningError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatusTuningError::Unsupported(feature) => {
+                write!(f, "kernel does not support audit feature {feature:#x}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for StatusTuningError {}
+
+/// Events the kernel reported as lost between two statuses
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+#[non_exhaustive]
+pub struct LostEvents {
+    /// Number of events lost since the previous status
+    pub count: u32,
+    /// Number of events waiting in the kernel queue
+    pub backlog: u32,
+    /// Backlog limit at the time of the status (0 meaning no limit)
+    pub backlog_limit: u32,
+}
+
+/// Track the kernel's lost events counter across successive `AUDIT_GET`
+/// replies, which are meant to be requested periodically.
+///
+/// The first status only sets the baseline. If the counter goes down, it
+/// is assumed to have been reset (see [`StatusMessage::reset_lost`]), and
+/// its new value is reported as lost.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+#[non_exhaustive]
+pub struct LostEventTracker {
+    last_lost: Option<u32>,
+    max_backlog_limit: Option<u32>,
+}
+
+impl LostEventTracker {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Enable raising the backlog limit when events are lost: see
+    /// [`LostEventTracker::backlog_increase`]
+    pub fn raise_backlog_limit(&mut self, max_backlog_limit: u32) {
+        self.max_backlog_limit = Some(max_backlog_limit);
+    }
+
+    /// Update the tracker with a new status, and return the events lost
+    /// since the previous one, if any
+    pub fn observe(&mut self, status: &StatusMessage) -> Option<LostEvents> {
+        let last = self.last_lost.replace(status.lost)?;
+        let count = if status.lost >= last {
+            status.lost - last
+        } else {
+            status.lost
+        };
+        (count > 0).then_some(LostEvents {
+            count,
+            backlog: status.backlog,
+            backlog_limit: status.backlog_limit,
+        })
+    }
+
+    /// Return the `AUDIT_SET` payload that doubles the backlog limit, up
+    /// to the maximum given to [`LostEventTracker::raise_backlog_limit`].
+    /// Returns `None` if raising the limit is not enabled, if there is no
+    /// limit, or if the maximum is already reached.
+    pub fn backlog_increase(&self, lost: &LostEvents) -> Option<StatusMessage> {
+        let max = self.max_backlog_limit?;
+        let limit = lost.backlog_limit.saturating_mul(2).min(max);
+        (lost.backlog_limit != 0 && limit > lost.backlog_limit)
+            .then(|| StatusMessage::new_backlog_limit(limit))
+    }
+}
+
+const FEATURE_NAMES: [(u32, &str); 7] = [
+    (AUDIT_FEATURE_BITMAP_BACKLOG_LIMIT, "backlog_limit"),
+    (AUDIT_FEATURE_BITMAP_BACKLOG_WAIT_TIME, "backlog_wait_time"),
+    (AUDIT_FEATURE_BITMAP_EXECUTABLE_PATH, "executable_path"),
+    (AUDIT_FEATURE_BITMAP_EXCLUDE_EXTEND, "exclude_extend"),
+    (AUDIT_FEATURE_BITMAP_SESSIONID_FILTER, "sessionid_filter"),
+    (AUDIT_FEATURE_BITMAP_LOST_RESET, "lost_reset"),
+    (AUDIT_FEATURE_BITMAP_FILTER_FS, "filter_fs"),
+];
+
+/// Render the status the same way `auditctl -s` does, one `name value`
+/// pair per line. The failure mode and the feature bitmap are decoded
+/// in parentheses after their raw values.
+impl fmt::Display for StatusMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "enabled {}", self.enabled)?;
+        let failure = match self.failure {
+            AUDIT_FAIL_SILENT => " (silent)",
+            AUDIT_FAIL_PRINTK => " (printk)",
+            AUDIT_FAIL_PANIC => " (panic)",
+            _ => "",
+        };
+        writeln!(f, "failure {}{failure}", self.failure)?;
+        writeln!(f, "pid {}", self.pid)?;
+        writeln!(f, "rate_limit {}", self.rate_limiting)?;
+        writeln!(f, "backlog_limit {}", self.backlog_limit)?;
+        writeln!(f, "lost {}", self.lost)?;
+        writeln!(f, "backlog {}", self.backlog)?;
+        writeln!(f, "backlog_wait_time {}", self.backlog_wait_time)?;
+        writeln!(
+            f,
+            "backlog_wait_time_actual {}",
+            self.backlog_wait_time_actual
+        )?;
+        write!(f, "features {:#x} (", self.feature_bitmap)?;
+        let mut first = true;
+        for (bit, name) in FEATURE_NAMES.iter() {
+            if self.feature_bitmap & bit != 0 {
+                if !first {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{name}")?;
+                first = false;
+            }
+        }
+        write!(f, ")")
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 #[non_exhaustive]
 pub struct StatusMessageBuffer<T> {
     buffer: T,
+    order: Endianness,
 }
 
 impl<T: AsRef<[u8]>> StatusMessageBuffer<T> {
+    /// Wrap a buffer without checking its length: the accessors of the
+    /// fields of the original layout panic if it is too short. Use
+    /// [`StatusMessageBuffer::new_checked`] or the `try_` accessors for
+    /// untrusted input.
     pub fn new(buffer: T) -> StatusMessageBuffer<T> {
-        StatusMessageBuffer { buffer }
+        Self::with_byte_order(buffer, Endianness::native())
+    }
+
+    /// Wrap a buffer whose fields are in the given byte order, such as a
+    /// status captured on a machine of a different byte order. The
+    /// accessors and setters convert the fields from and to this order.
+    pub fn with_byte_order(
+        buffer: T,
+        order: Endianness,
+    ) -> StatusMessageBuffer<T> {
+        StatusMessageBuffer { buffer, order }
+    }
+
+    pub fn byte_order(&self) -> Endianness {
+        self.order
     }
 
     pub fn new_checked(
@@ -70,106 +419,211 @@ impl<T: AsRef<[u8]>> StatusMessageBuffer<T> {
         Ok(buf)
     }
 
-    fn check_buffer_length(&self) -> Result<(), DecodeError> {
+    pub fn check_buffer_length(&self) -> Result<(), AuditDecodeError> {
+        self.check_buffer_length_with(StatusParseMode::default())
+    }
+
+    pub fn check_buffer_length_with(
+        &self,
+        mode: StatusParseMode,
+    ) -> Result<(), AuditDecodeError> {
         let len = self.buffer.as_ref().len();
-        if len < STATUS_MESSAGE_LEN {
-            return Err(format!(
-                "invalid StatusMessageBuffer buffer: length is {len} \
-                instead of {STATUS_MESSAGE_LEN}"
-            )
-            .into());
+        match mode {
+            StatusParseMode::Strict
+                if !STATUS_MESSAGE_LAYOUTS
+                    .iter()
+                    .any(|layout| layout.len == len) =>
+            {
+                Err(AuditDecodeError::UnknownStatusLayout { got: len })
+            }
+            StatusParseMode::Lenient if len < STATUS_MESSAGE_MIN_LEN => {
+                Err(AuditDecodeError::TruncatedStatus {
+                    needed: STATUS_MESSAGE_MIN_LEN,
+                    got: len,
+                })
+            }
+            _ => Ok(()),
         }
-        Ok(())
     }
 
     pub fn into_inner(self) -> T {
         self.buffer
     }
 
+    /// Return the most recent known layout that fits in this buffer, or
+    /// `None` if the buffer is shorter than the original layout.
+    pub fn layout(&self) -> Option<StatusLayout> {
+        let len = self.buffer.as_ref().len();
+        STATUS_MESSAGE_LAYOUTS
+            .iter()
+            .rev()
+            .find(|layout| layout.len <= len)
+            .copied()
+    }
+
     pub fn mask(&self) -> u32 {
-        NativeEndian::read_u32(&self.buffer.as_ref()[MASK])
+        self.order.read_u32(&self.buffer.as_ref()[MASK])
     }
 
     pub fn enabled(&self) -> u32 {
-        NativeEndian::read_u32(&self.buffer.as_ref()[ENABLED])
+        self.order.read_u32(&self.buffer.as_ref()[ENABLED])
     }
 
     pub fn failure(&self) -> u32 {
-        NativeEndian::read_u32(&self.buffer.as_ref()[FAILURE])
+        self.order.read_u32(&self.buffer.as_ref()[FAILURE])
     }
 
     pub fn pid(&self) -> u32 {
-        NativeEndian::read_u32(&self.buffer.as_ref()[PID])
+        self.order.read_u32(&self.buffer.as_ref()[PID])
     }
 
     pub fn rate_limiting(&self) -> u32 {
-        NativeEndian::read_u32(&self.buffer.as_ref()[RATE_LIMITING])
+        self.order.read_u32(&self.buffer.as_ref()[RATE_LIMITING])
     }
 
     pub fn backlog_limit(&self) -> u32 {
-        NativeEndian::read_u32(&self.buffer.as_ref()[BACKLOG_LIMIT])
+        self.order.read_u32(&self.buffer.as_ref()[BACKLOG_LIMIT])
     }
 
     pub fn lost(&self) -> u32 {
-        NativeEndian::read_u32(&self.buffer.as_ref()[LOST])
+        self.order.read_u32(&self.buffer.as_ref()[LOST])
     }
 
     pub fn backlog(&self) -> u32 {
-        NativeEndian::read_u32(&self.buffer.as_ref()[BACKLOG])
+        self.order.read_u32(&self.buffer.as_ref()[BACKLOG])
     }
 
+    /// Return the feature bitmap, or 0 if the buffer uses the original
+    /// layout, which predates it
     pub fn feature_bitmap(&self) -> u32 {
-        NativeEndian::read_u32(&self.buffer.as_ref()[FEATURE_BITMAP])
+        self.read_or_zero(FEATURE_BITMAP)
     }
 
+    /// Return the backlog wait time, or 0 if the buffer uses a layout
+    /// that predates it
     pub fn backlog_wait_time(&self) -> u32 {
-        NativeEndian::read_u32(&self.buffer.as_ref()[BACKLOG_WAIT_TIME])
+        self.read_or_zero(BACKLOG_WAIT_TIME)
+    }
+
+    /// Return the actual backlog wait time, or 0 if the buffer uses a
+    /// layout that predates it
+    pub fn backlog_wait_time_actual(&self) -> u32 {
+        self.read_or_zero(BACKLOG_WAIT_TIME_ACTUAL)
+    }
+
+    /// Checked version of `mask()`
+    pub fn try_mask(&self) -> Result<u32, AuditDecodeError> {
+        self.try_read(MASK)
+    }
+
+    /// Checked version of `enabled()`
+    pub fn try_enabled(&self) -> Result<u32, AuditDecodeError> {
+        self.try_read(ENABLED)
+    }
+
+    /// Checked version of `failure()`
+    pub fn try_failure(&self) -> Result<u32, AuditDecodeError> {
+        self.try_read(FAILURE)
+    }
+
+    /// Checked version of `pid()`
+    pub fn try_pid(&self) -> Result<u32, AuditDecodeError> {
+        self.try_read(PID)
+    }
+
+    /// Checked version of `rate_limiting()`
+    pub fn try_rate_limiting(&self) -> Result<u32, AuditDecodeError> {
+        self.try_read(RATE_LIMITING)
+    }
+
+    /// Checked version of `backlog_limit()`
+    pub fn try_backlog_limit(&self) -> Result<u32, AuditDecodeError> {
+        self.try_read(BACKLOG_LIMIT)
+    }
+
+    /// Checked version of `lost()`
+    pub fn try_lost(&self) -> Result<u32, AuditDecodeError> {
+        self.try_read(LOST)
+    }
+
+    /// Checked version of `backlog()`
+    pub fn try_backlog(&self) -> Result<u32, AuditDecodeError> {
+        self.try_read(BACKLOG)
+    }
+
+    /// Read a field, or fail if the buffer is too short to contain it
+    fn try_read(&self, field: Field) -> Result<u32, AuditDecodeError> {
+        let buffer = self.buffer.as_ref();
+        buffer
+            .get(field.clone())
+            .map(|bytes| self.order.read_u32(bytes))
+            .ok_or(AuditDecodeError::TruncatedStatus {
+                needed: field.end,
+                got: buffer.len(),
+            })
+    }
+
+    /// Read a field, or return 0 if the buffer is too short to contain it
+    fn read_or_zero(&self, field: Field) -> u32 {
+        self.buffer
+            .as_ref()
+            .get(field)
+            .map(|bytes| self.order.read_u32(bytes))
+            .unwrap_or(0)
     }
 }
 
 impl<T: AsRef<[u8]> + AsMut<[u8]>> StatusMessageBuffer<T> {
     pub fn set_mask(&mut self, value: u32) {
-        NativeEndian::write_u32(&mut self.buffer.as_mut()[MASK], value)
+        self.order.write_u32(&mut self.buffer.as_mut()[MASK], value)
     }
 
     pub fn set_enabled(&mut self, value: u32) {
-        NativeEndian::write_u32(&mut self.buffer.as_mut()[ENABLED], value)
+        self.order
+            .write_u32(&mut self.buffer.as_mut()[ENABLED], value)
     }
 
     pub fn set_failure(&mut self, value: u32) {
-        NativeEndian::write_u32(&mut self.buffer.as_mut()[FAILURE], value)
+        self.order
+            .write_u32(&mut self.buffer.as_mut()[FAILURE], value)
     }
 
     pub fn set_pid(&mut self, value: u32) {
-        NativeEndian::write_u32(&mut self.buffer.as_mut()[PID], value)
+        self.order.write_u32(&mut self.buffer.as_mut()[PID], value)
     }
 
     pub fn set_rate_limiting(&mut self, value: u32) {
-        NativeEndian::write_u32(&mut self.buffer.as_mut()[RATE_LIMITING], value)
+        self.order
+            .write_u32(&mut self.buffer.as_mut()[RATE_LIMITING], value)
     }
 
     pub fn set_backlog_limit(&mut self, value: u32) {
-        NativeEndian::write_u32(&mut self.buffer.as_mut()[BACKLOG_LIMIT], value)
+        self.order
+            .write_u32(&mut self.buffer.as_mut()[BACKLOG_LIMIT], value)
     }
 
     pub fn set_lost(&mut self, value: u32) {
-        NativeEndian::write_u32(&mut self.buffer.as_mut()[LOST], value)
+        self.order.write_u32(&mut self.buffer.as_mut()[LOST], value)
     }
 
     pub fn set_backlog(&mut self, value: u32) {
-        NativeEndian::write_u32(&mut self.buffer.as_mut()[BACKLOG], value)
+        self.order
+            .write_u32(&mut self.buffer.as_mut()[BACKLOG], value)
     }
 
     pub fn set_feature_bitmap(&mut self, value: u32) {
-        NativeEndian::write_u32(
-            &mut self.buffer.as_mut()[FEATURE_BITMAP],
-            value,
-        )
+        self.order
+            .write_u32(&mut self.buffer.as_mut()[FEATURE_BITMAP], value)
     }
 
     pub fn set_backlog_wait_time(&mut self, value: u32) {
-        NativeEndian::write_u32(
-            &mut self.buffer.as_mut()[BACKLOG_WAIT_TIME],
+        self.order
+            .write_u32(&mut self.buffer.as_mut()[BACKLOG_WAIT_TIME], value)
+    }
+
+    pub fn set_backlog_wait_time_actual(&mut self, value: u32) {
+        self.order.write_u32(
+            &mut self.buffer.as_mut()[BACKLOG_WAIT_TIME_ACTUAL],
             value,
         )
     }
@@ -177,22 +631,71 @@ impl<T: AsRef<[u8]> + AsMut<[u8]>> StatusMessageBuffer<T> {
 
 impl<T: AsRef<[u8]>> Parseable<StatusMessageBuffer<T>> for StatusMessage {
     fn parse(buf: &StatusMessageBuffer<T>) -> Result<Self, DecodeError> {
-        buf.check_buffer_length()?;
+        Self::parse_with_param(buf, StatusParseMode::default())
+    }
+}
+
+impl<T: AsRef<[u8]>>
+    ParseableParametrized<StatusMessageBuffer<T>, StatusParseMode>
+    for StatusMessage
+{
+    fn parse_with_param(
+        buf: &StatusMessageBuffer<T>,
+        mode: StatusParseMode,
+    ) -> Result<Self, DecodeError> {
+        Ok(Self::try_parse(buf, mode)?)
+    }
+}
+
+impl StatusMessage {
+    pub(crate) fn try_parse<T: AsRef<[u8]>>(
+        buf: &StatusMessageBuffer<T>,
+        mode: StatusParseMode,
+    ) -> Result<Self, AuditDecodeError> {
+        buf.check_buffer_length_with(mode)?;
+        if mode == StatusParseMode::Padded {
+            // Reading the missing fields as 0 is equivalent to padding the
+            // buffer with zeros.
+            return Ok(StatusMessage {
+                mask: buf.read_or_zero(MASK),
+                enabled: buf.read_or_zero(ENABLED),
+                failure: buf.read_or_zero(FAILURE),
+                pid: buf.read_or_zero(PID),
+                rate_limiting: buf.read_or_zero(RATE_LIMITING),
+                backlog_limit: buf.read_or_zero(BACKLOG_LIMIT),
+                lost: buf.read_or_zero(LOST),
+                backlog: buf.read_or_zero(BACKLOG),
+                feature_bitmap: buf.feature_bitmap(),
+                backlog_wait_time: buf.backlog_wait_time(),
+                backlog_wait_time_actual: buf.backlog_wait_time_actual(),
+            });
+        }
         Ok(StatusMessage {
-            mask: buf.mask(),
-            enabled: buf.enabled(),
-            failure: buf.failure(),
-            pid: buf.pid(),
-            rate_limiting: buf.rate_limiting(),
-            backlog_limit: buf.backlog_limit(),
-            lost: buf.lost(),
-            backlog: buf.backlog(),
+            mask: buf.try_mask()?,
+            enabled: buf.try_enabled()?,
+            failure: buf.try_failure()?,
+            pid: buf.try_pid()?,
+            rate_limiting: buf.try_rate_limiting()?,
+            backlog_limit: buf.try_backlog_limit()?,
+            lost: buf.try_lost()?,
+            backlog: buf.try_backlog()?,
             feature_bitmap: buf.feature_bitmap(),
             backlog_wait_time: buf.backlog_wait_time(),
+            backlog_wait_time_actual: buf.backlog_wait_time_actual(),
         })
     }
 }
 
+impl StatusMessage {
+    /// Checked version of `emit()`: serialize the status at the start of
+    /// `buffer`, if it is large enough. Return the number of bytes written.
+    pub fn try_emit(&self, buffer: &mut [u8]) -> Result<usize, AuditEmitError> {
+        AuditEmitError::check_buffer(STATUS_MESSAGE_LEN, buffer)?;
+        self.emit(&mut buffer[..STATUS_MESSAGE_LEN]);
+        Ok(STATUS_MESSAGE_LEN)
+    }
+}
+
 impl Emitable for StatusMessage {
     fn buffer_len(&self) -> usize {
         STATUS_MESSAGE_LEN
@@ -210,5 +713,324 @@ impl Emitable for StatusMessage {
         buffer.set_backlog(self.backlog);
         buffer.set_feature_bitmap(self.feature_bitmap);
         buffer.set_backlog_wait_time(self.backlog_wait_time);
+        buffer.set_backlog_wait_time_actual(self.backlog_wait_time_actual);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::tests::XorShift;
+
+    impl XorShift {
+        pub(crate) fn status(&mut self) -> StatusMessage {
+            StatusMessage {
+                mask: self.next(),
+                enabled: self.below(3),
+                failure: self.below(3),
+                pid: self.next(),
+                rate_limiting: self.next(),
+                backlog_limit: self.next(),
+                lost: self.next(),
+                backlog: self.next(),
+                feature_bitmap: self.next(),
+                backlog_wait_time: self.next(),
+                backlog_wait_time_actual: self.next(),
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip_random_status() {
+        let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..500 {
+            let status = rng.status();
+            let mut buf = vec![0; status.buffer_len()];
+            status.emit(&mut buf);
+            assert_eq!(buf.len(), STATUS_MESSAGE_LEN);
+            let parsed = StatusMessage::parse_with_param(
+                &StatusMessageBuffer::new(&buf[..]),
+                StatusParseMode::Strict,
+            )
+            .unwrap();
+            assert_eq!(parsed, status);
+
+            let mut emitted = vec![0; parsed.buffer_len()];
+            parsed.emit(&mut emitted);
+            assert_eq!(emitted, buf);
+
+            // the older layouts lack the last fields
+            for (i, layout) in STATUS_MESSAGE_LAYOUTS.iter().enumerate() {
+                let mut expected = status.clone();
+                if i < 3 {
+                    expected.backlog_wait_time_actual = 0;
+                }
+                if i < 2 {
+                    expected.backlog_wait_time = 0;
+                }
+                if i < 1 {
+                    expected.feature_bitmap = 0;
+                }
+                let parsed = StatusMessage::parse_with_param(
+                    &StatusMessageBuffer::new(&buf[..layout.len]),
+                    StatusParseMode::Strict,
+                )
+                .unwrap();
+                assert_eq!(parsed, expected, "{}", layout.last_field);
+            }
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        let status = StatusMessage {
+            mask: 0,
+            enabled: 1,
+            failure: AUDIT_FAIL_PRINTK,
+            pid: 1234,
+            rate_limiting: 0,
+            backlog_limit: 8192,
+            lost: 0,
+            backlog: 0,
+            feature_bitmap: AUDIT_FEATURE_BITMAP_BACKLOG_LIMIT
+                | AUDIT_FEATURE_BITMAP_LOST_RESET,
+            backlog_wait_time: 60000,
+            backlog_wait_time_actual: 0,
+        };
+        assert_eq!(
+            status.to_string(),
+            "enabled 1\n\
+            failure 1 (printk)\n\
+            pid 1234\n\
+            rate_limit 0\n\
+            backlog_limit 8192\n\
+            lost 0\n\
+            backlog 0\n\
+            backlog_wait_time 60000\n\
+            backlog_wait_time_actual 0\n\
+            features 0x21 (backlog_limit, lost_reset)"
+        );
+    }
+
+    #[test]
+    fn test_backlog_tuning() {
+        let current = StatusMessage {
+            backlog_limit: 64,
+            backlog_wait_time: 60000,
+            feature_bitmap: AUDIT_FEATURE_BITMAP_BACKLOG_WAIT_TIME,
+            ..Default::default()
+        };
+
+        let raise = current.backlog_tuning(8192, 120000).unwrap();
+        assert_eq!(
+            raise,
+            vec![
+                StatusMessage::new_backlog_limit(8192),
+                StatusMessage::new_backlog_wait_time(120000),
+            ]
+        );
+
+        let lower = current.backlog_tuning(32, 0).unwrap();
+        assert_eq!(
+            lower,
+            vec![
+                StatusMessage::new_backlog_wait_time(0),
+                StatusMessage::new_backlog_limit(32),
+            ]
+        );
+
+        assert!(current.backlog_tuning(64, 60000).unwrap().is_empty());
+
+        let old_kernel = StatusMessage {
+            feature_bitmap: 0,
+            ..current
+        };
+        assert_eq!(
+            old_kernel.backlog_tuning(8192, 1000),
+            Err(StatusTuningError::Unsupported(
+                AUDIT_FEATURE_BITMAP_BACKLOG_WAIT_TIME
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_modes() {
+        let status = StatusMessage {
+            enabled: 1,
+            backlog_limit: 8192,
+            feature_bitmap: AUDIT_FEATURE_BITMAP_BACKLOG_LIMIT,
+            backlog_wait_time: 60000,
+            ..Default::default()
+        };
+        let mut bytes = vec![0; status.buffer_len()];
+        status.emit(&mut bytes);
+
+        // Linux < 5.10
+        let buf = StatusMessageBuffer::new(&bytes[..BACKLOG_WAIT_TIME.end]);
+        for mode in [
+            StatusParseMode::Strict,
+            StatusParseMode::Lenient,
+            StatusParseMode::Padded,
+        ] {
+            let parsed = StatusMessage::parse_with_param(&buf, mode).unwrap();
+            assert_eq!(parsed, status);
+        }
+
+        // unknown layout, with two trailing bytes
+        bytes.extend_from_slice(&[0xff, 0xff]);
+        let buf = StatusMessageBuffer::new(&bytes[..]);
+        StatusMessage::parse_with_param(&buf, StatusParseMode::Strict)
+            .unwrap_err();
+        let parsed =
+            StatusMessage::parse_with_param(&buf, StatusParseMode::Lenient)
+                .unwrap();
+        assert_eq!(parsed, status);
+
+        // truncated in the middle of the original layout
+        let buf = StatusMessageBuffer::new(&bytes[..BACKLOG_LIMIT.end]);
+        StatusMessage::parse_with_param(&buf, StatusParseMode::Lenient)
+            .unwrap_err();
+        let parsed =
+            StatusMessage::parse_with_param(&buf, StatusParseMode::Padded)
+                .unwrap();
+        assert_eq!(parsed.backlog_limit, 8192);
+        assert_eq!(parsed.backlog_wait_time, 0);
+        assert_eq!(buf.try_rate_limiting(), Ok(0));
+        assert_eq!(
+            buf.try_lost(),
+            Err(AuditDecodeError::TruncatedStatus {
+                needed: LOST.end,
+                got: BACKLOG_LIMIT.end
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_layouts() {
+        let status = StatusMessage {
+            enabled: 1,
+            pid: 42,
+            backlog: 3,
+            feature_bitmap: AUDIT_FEATURE_BITMAP_ALL,
+            backlog_wait_time: 60000,
+            backlog_wait_time_actual: 7,
+            ..Default::default()
+        };
+        let mut bytes = vec![0; status.buffer_len()];
+        status.emit(&mut bytes);
+
+        for layout in STATUS_MESSAGE_LAYOUTS.iter() {
+            let buf =
+                StatusMessageBuffer::new_checked(&bytes[..layout.len]).unwrap();
+            assert_eq!(buf.layout(), Some(*layout));
+            let parsed = StatusMessage::parse(&buf).unwrap();
+            let strict =
+                StatusMessage::parse_with_param(&buf, StatusParseMode::Strict)
+                    .unwrap();
+            assert_eq!(parsed, strict);
+            assert_eq!(parsed.pid, 42);
+            assert_eq!(parsed.backlog, 3);
+            // the accessors of the fields missing from the layout return 0
+            // like the parser does, instead of panicking
+            assert_eq!(buf.feature_bitmap(), parsed.feature_bitmap);
+            assert_eq!(buf.backlog_wait_time(), parsed.backlog_wait_time);
+            assert_eq!(
+                buf.backlog_wait_time_actual(),
+                parsed.backlog_wait_time_actual
+            );
+        }
+        let buf = StatusMessageBuffer::new(&bytes[..STATUS_MESSAGE_MIN_LEN]);
+        assert_eq!(buf.backlog_wait_time(), 0);
+    }
+
+    #[test]
+    fn test_lost_from_ack() {
+        let mut ack = ErrorMessage::default();
+        assert_eq!(StatusMessage::lost_from_ack(&ack), Some(0));
+        ack.code = std::num::NonZeroI32::new(42);
+        assert_eq!(StatusMessage::lost_from_ack(&ack), Some(42));
+        ack.code = std::num::NonZeroI32::new(-1);
+        assert_eq!(StatusMessage::lost_from_ack(&ack), None);
+    }
+
+    #[test]
+    fn test_byte_order() {
+        let status = StatusMessage {
+            enabled: 1,
+            pid: 42,
+            backlog_limit: 8192,
+            ..Default::default()
+        };
+        let mut native = vec![0; status.buffer_len()];
+        status.emit(&mut native);
+        for order in [Endianness::Little, Endianness::Big] {
+            let mut bytes = native.clone();
+            for word in bytes.chunks_exact_mut(4) {
+                let value = Endianness::native().read_u32(word);
+                order.write_u32(word, value);
+            }
+            let buf = StatusMessageBuffer::with_byte_order(&bytes[..], order);
+            assert_eq!(buf.byte_order(), order);
+            assert_eq!(buf.pid(), 42);
+            assert_eq!(StatusMessage::parse(&buf).unwrap(), status);
+        }
+    }
+
+    #[test]
+    fn test_status_mask() {
+        assert_eq!(StatusMessage::reset_lost().status_mask(), StatusMask::LOST);
+        let msg = StatusMessage::reset_backlog_wait_time_actual();
+        assert_eq!(msg.mask, 0x80);
+        assert!(StatusMask::all().contains(msg.status_mask()));
+        let msg = StatusMessage::new_pid(42);
+        assert_eq!(msg.status_mask(), StatusMask::PID);
+        assert_eq!(msg.pid, 42);
+    }
+
+    #[test]
+    fn test_try_emit() {
+        let msg = StatusMessage::new_pid(42);
+        let mut buf = [0; STATUS_MESSAGE_LEN + 4];
+        assert_eq!(msg.try_emit(&mut buf), Ok(STATUS_MESSAGE_LEN));
+        assert_eq!(
+            StatusMessage::parse(&StatusMessageBuffer::new(&buf[..])).unwrap(),
+            msg
+        );
+        assert_eq!(
+            msg.try_emit(&mut buf[..8]),
+            Err(AuditEmitError::BufferTooShort {
+                needed: STATUS_MESSAGE_LEN,
+                got: 8,
+            })
+        );
+    }
+
+    #[test]
+    fn test_lost_event_tracker() {
+        let status = |lost| StatusMessage {
+            lost,
+            backlog: 8000,
+            backlog_limit: 8192,
+            ..Default::default()
+        };
+        let mut tracker = LostEventTracker::new();
+        assert_eq!(tracker.observe(&status(10)), None);
+        assert_eq!(tracker.observe(&status(10)), None);
+        let lost = tracker.observe(&status(25)).unwrap();
+        assert_eq!(lost.count, 15);
+        assert_eq!(lost.backlog, 8000);
+        // reset
+        assert_eq!(tracker.observe(&status(3)).unwrap().count, 3);
+
+        assert_eq!(tracker.backlog_increase(&lost), None);
+        tracker.raise_backlog_limit(10000);
+        let msg = tracker.backlog_increase(&lost).unwrap();
+        assert_eq!(msg.status_mask(), StatusMask::BACKLOG_LIMIT);
+        assert_eq!(msg.backlog_limit, 10000);
+        let lost = LostEvents {
+            backlog_limit: 10000,
+            ..lost
+        };
+        assert_eq!(tracker.backlog_increase(&lost), None);
     }
 }
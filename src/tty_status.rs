@@ -0,0 +1,151 @@
+// SPDX-License-Identifier: MIT
+
+use byteorder::{ByteOrder, NativeEndian};
+
+use netlink_packet_utils::{
+    traits::{Emitable, Parseable},
+    DecodeError,
+};
+
+use crate::{AuditDecodeError, Field};
+
+const ENABLED: Field = 0..4;
+const LOG_PASSWD: Field = 4..8;
+pub const TTY_STATUS_MESSAGE_LEN: usize = LOG_PASSWD.end;
+/// Length of the `struct audit_tty_status` before Linux 3.9, which lacks
+/// the `log_passwd` field
+pub const TTY_STATUS_MESSAGE_MIN_LEN: usize = ENABLED.end;
+
+/// Payload of the `AUDIT_TTY_GET` replies and `AUDIT_TTY_SET` requests
+/// (`struct audit_tty_status`)
+#[derive(Debug, PartialEq, Eq, Clone, Default, Hash)]
+#[non_exhaustive]
+pub struct TtyStatusMessage {
+    /// 1 if TTY input auditing is enabled, 0 otherwise
+    pub enabled: u32,
+    /// 1 if passwords are logged too, 0 otherwise
+    pub log_passwd: u32,
+}
+
+impl TtyStatusMessage {
+    pub fn new(enabled: bool, log_passwd: bool) -> Self {
+        TtyStatusMessage {
+            enabled: enabled.into(),
+            log_passwd: log_passwd.into(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[non_exhaustive]
+pub struct TtyStatusMessageBuffer<T> {
+    buffer: T,
+}
+
+impl<T: AsRef<[u8]>> TtyStatusMessageBuffer<T> {
+    pub fn new(buffer: T) -> TtyStatusMessageBuffer<T> {
+        TtyStatusMessageBuffer { buffer }
+    }
+
+    pub fn new_checked(
+        buffer: T,
+    ) -> Result<TtyStatusMessageBuffer<T>, DecodeError> {
+        let buf = Self::new(buffer);
+        buf.check_buffer_length()?;
+        Ok(buf)
+    }
+
+    pub fn check_buffer_length(&self) -> Result<(), AuditDecodeError> {
+        let len = self.buffer.as_ref().len();
+        if len < TTY_STATUS_MESSAGE_MIN_LEN {
+            return Err(AuditDecodeError::TruncatedTtyStatus {
+                needed: TTY_STATUS_MESSAGE_MIN_LEN,
+                got: len,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> T {
+        self.buffer
+    }
+
+    pub fn enabled(&self) -> u32 {
+        NativeEndian::read_u32(&self.buffer.as_ref()[ENABLED])
+    }
+
+    /// Return the `log_passwd` field, or 0 if the buffer is too short to
+    /// hold it
+    pub fn log_passwd(&self) -> u32 {
+        self.buffer
+            .as_ref()
+            .get(LOG_PASSWD)
+            .map(NativeEndian::read_u32)
+            .unwrap_or(0)
+    }
+}
+
+impl<T: AsRef<[u8]> + AsMut<[u8]>> TtyStatusMessageBuffer<T> {
+    pub fn set_enabled(&mut self, value: u32) {
+        NativeEndian::write_u32(&mut self.buffer.as_mut()[ENABLED], value)
+    }
+
+    pub fn set_log_passwd(&mut self, value: u32) {
+        NativeEndian::write_u32(&mut self.buffer.as_mut()[LOG_PASSWD], value)
+    }
+}
+
+impl<T: AsRef<[u8]>> Parseable<TtyStatusMessageBuffer<T>> for TtyStatusMessage {
+    fn parse(buf: &TtyStatusMessageBuffer<T>) -> Result<Self, DecodeError> {
+        Ok(Self::try_parse(buf)?)
+    }
+}
+
+impl TtyStatusMessage {
+    pub(crate) fn try_parse<T: AsRef<[u8]>>(
+        buf: &TtyStatusMessageBuffer<T>,
+    ) -> Result<Self, AuditDecodeError> {
+        buf.check_buffer_length()?;
+        Ok(TtyStatusMessage {
+            enabled: buf.enabled(),
+            log_passwd: buf.log_passwd(),
+        })
+    }
+}
+
+impl Emitable for TtyStatusMessage {
+    fn buffer_len(&self) -> usize {
+        TTY_STATUS_MESSAGE_LEN
+    }
+
+    fn emit(&self, buffer: &mut [u8]) {
+        let mut buffer = TtyStatusMessageBuffer::new(buffer);
+        buffer.set_enabled(self.enabled);
+        buffer.set_log_passwd(self.log_passwd);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tty_status() {
+        let msg = TtyStatusMessage::new(true, false);
+        let mut buf = vec![0; msg.buffer_len()];
+        msg.emit(&mut buf);
+        let parsed = TtyStatusMessage::parse(
+            &TtyStatusMessageBuffer::new_checked(&buf).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(parsed, msg);
+
+        // old kernels only send the enabled field
+        let parsed = TtyStatusMessage::parse(
+            &TtyStatusMessageBuffer::new_checked(&buf[..4]).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(parsed.enabled, 1);
+        assert_eq!(parsed.log_passwd, 0);
+    }
+}